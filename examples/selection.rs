@@ -179,6 +179,8 @@ impl core::System for System {
         )
         .expect("selector");
         selector.selector_type = gs::selection::ViewportSelectorType::Brush;
+        selector.set_scale_factor(&queue, window.scale_factor() as f32);
+        selector.set_edge_width(&queue, 1.0);
 
         log::debug!("Creating selection viewport selection modifier");
         let mut viewport_selection_modifier = gs::editor::NonDestructiveModifier::new(
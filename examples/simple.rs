@@ -99,7 +99,7 @@ struct System {
     device: wgpu::Device,
     config: wgpu::SurfaceConfiguration,
 
-    camera: gs::Camera,
+    camera: gs::SmoothCamera,
     gaussians: gs::core::Gaussians,
     viewer: gs::Viewer,
 }
@@ -160,7 +160,7 @@ impl core::System for System {
             .expect("gaussians");
 
         log::debug!("Creating camera");
-        let camera = gs::Camera::new(0.1..1e4, 60f32.to_radians());
+        let camera = gs::SmoothCamera::new(gs::Camera::new(0.1..1e4, 60f32.to_radians()));
 
         log::debug!("Creating viewer");
         let mut viewer =
@@ -203,33 +203,17 @@ impl core::System for System {
         // Camera movement
         const SPEED: f32 = 1.0;
 
-        let mut forward = 0.0;
-        if input.held_keys.contains(&KeyCode::KeyW) {
-            forward += SPEED * delta_time;
-        }
-        if input.held_keys.contains(&KeyCode::KeyS) {
-            forward -= SPEED * delta_time;
-        }
-
-        let mut right = 0.0;
-        if input.held_keys.contains(&KeyCode::KeyD) {
-            right += SPEED * delta_time;
-        }
-        if input.held_keys.contains(&KeyCode::KeyA) {
-            right -= SPEED * delta_time;
-        }
-
-        self.camera.move_by(forward, right);
-
-        let mut up = 0.0;
-        if input.held_keys.contains(&KeyCode::Space) {
-            up += SPEED * delta_time;
-        }
-        if input.held_keys.contains(&KeyCode::ShiftLeft) {
-            up -= SPEED * delta_time;
-        }
+        let axis = |positive: KeyCode, negative: KeyCode| {
+            input.held_keys.contains(&positive) as i32 as f32
+                - input.held_keys.contains(&negative) as i32 as f32
+        };
 
-        self.camera.move_up(up);
+        self.camera.set_target_velocity(
+            axis(KeyCode::KeyW, KeyCode::KeyS) * SPEED,
+            axis(KeyCode::KeyD, KeyCode::KeyA) * SPEED,
+            axis(KeyCode::Space, KeyCode::ShiftLeft) * SPEED,
+        );
+        self.camera.update(delta_time);
 
         // Camera rotation
         const SENSITIVITY: f32 = 0.15;
@@ -237,8 +221,8 @@ impl core::System for System {
         let yaw = input.mouse_diff.x * SENSITIVITY * delta_time;
         let pitch = input.mouse_diff.y * SENSITIVITY * delta_time;
 
-        self.camera.pitch_by(-pitch);
-        self.camera.yaw_by(-yaw);
+        self.camera.camera.pitch_by(-pitch);
+        self.camera.camera.yaw_by(-yaw);
 
         // Update the viewer
         self.viewer.update_camera(
@@ -0,0 +1,96 @@
+use glam::*;
+
+use crate::core::IterGaussian;
+
+/// Compute a robust bounding sphere of the Gaussians for framing the camera.
+///
+/// The center is the mean of the Gaussian positions weighted by their opacity. The
+/// `outlier_fraction` of the total weight that is farthest from the center, measured by the
+/// Mahalanobis distance under the weighted covariance of the positions, is rejected as floaters.
+/// The center is then recomputed from the remaining Gaussians, and the radius is the distance to
+/// the farthest of them.
+///
+/// `outlier_fraction` is clamped to `[0, 1)`, 0 keeps all Gaussians. Fully transparent Gaussians
+/// are ignored, and `(Vec3::ZERO, 0.0)` is returned if none remains.
+///
+/// This can be used with [`Camera::frame_sphere`](crate::Camera::frame_sphere).
+pub fn bounding_sphere_weighted(
+    gaussians: &impl IterGaussian,
+    outlier_fraction: f32,
+) -> (Vec3, f32) {
+    let points = gaussians
+        .iter_gaussian()
+        .filter(|gaussian| gaussian.color.w > 0)
+        .map(|gaussian| (gaussian.pos, gaussian.color.w as f32 / 255.0))
+        .collect::<Vec<_>>();
+
+    let Some(center) = weighted_mean(&points) else {
+        return (Vec3::ZERO, 0.0);
+    };
+
+    let outlier_fraction = outlier_fraction.clamp(0.0, 1.0 - f32::EPSILON);
+    let points = if outlier_fraction > 0.0 {
+        reject_outliers(points, center, outlier_fraction)
+    } else {
+        points
+    };
+
+    let center = weighted_mean(&points).unwrap_or(center);
+    let radius = points
+        .iter()
+        .map(|(pos, _)| pos.distance(center))
+        .fold(0.0, f32::max);
+
+    (center, radius)
+}
+
+/// Get the weighted mean of the positions.
+fn weighted_mean(points: &[(Vec3, f32)]) -> Option<Vec3> {
+    let total_weight = points.iter().map(|(_, weight)| weight).sum::<f32>();
+    if total_weight <= 0.0 {
+        return None;
+    }
+
+    Some(
+        points
+            .iter()
+            .map(|(pos, weight)| *pos * *weight)
+            .sum::<Vec3>()
+            / total_weight,
+    )
+}
+
+/// Reject the `fraction` of the total weight with the largest Mahalanobis distance to `center`.
+fn reject_outliers(points: Vec<(Vec3, f32)>, center: Vec3, fraction: f32) -> Vec<(Vec3, f32)> {
+    let total_weight = points.iter().map(|(_, weight)| weight).sum::<f32>();
+
+    let cov = points.iter().fold(Mat3::ZERO, |cov, (pos, weight)| {
+        let d = *pos - center;
+        cov + Mat3::from_cols(d * d.x, d * d.y, d * d.z) * *weight
+    }) / total_weight;
+
+    // Regularize so that flat or degenerate distributions are still invertible
+    let epsilon = (cov.x_axis.x + cov.y_axis.y + cov.z_axis.z).max(f32::MIN_POSITIVE) * 1e-6;
+    let inv_cov = (cov + Mat3::from_diagonal(Vec3::splat(epsilon))).inverse();
+
+    let mut points = points
+        .into_iter()
+        .map(|(pos, weight)| {
+            let d = pos - center;
+            (d.dot(inv_cov * d), pos, weight)
+        })
+        .collect::<Vec<_>>();
+    points.sort_by(|(a, ..), (b, ..)| a.total_cmp(b));
+
+    let max_weight = total_weight * (1.0 - fraction);
+    let mut weight_sum = 0.0;
+    points
+        .into_iter()
+        .take_while(|(_, _, weight)| {
+            let keep = weight_sum < max_weight;
+            weight_sum += weight;
+            keep
+        })
+        .map(|(_, pos, weight)| (pos, weight))
+        .collect()
+}
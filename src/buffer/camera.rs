@@ -3,6 +3,7 @@ use glam::*;
 use crate::{
     CameraTrait,
     core::{self, BufferWrapper, FixedSizeBufferWrapper},
+    prefixed_label,
 };
 
 /// The camera buffer.
@@ -12,8 +13,13 @@ pub struct CameraBuffer(wgpu::Buffer);
 impl CameraBuffer {
     /// Create a new camera buffer.
     pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new camera buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Camera Buffer"),
+            label: Some(prefixed_label(label, "Camera Buffer").as_str()),
             size: std::mem::size_of::<CameraPod>() as u64,
             usage: Self::DEFAULT_USAGES,
             mapped_at_creation: false,
@@ -69,7 +75,12 @@ pub struct CameraPod {
 
 impl CameraPod {
     /// Create a new camera.
+    ///
+    /// Each dimension of `size` is clamped to at least 1, so a zero sized texture (e.g. a
+    /// minimized window) does not produce a non-finite projection.
     pub fn new(camera: &impl CameraTrait, size: UVec2) -> Self {
+        let size = size.max(UVec2::ONE);
+
         Self {
             view: camera.view(),
             proj: camera.projection(size.x as f32 / size.y as f32),
@@ -0,0 +1,115 @@
+use glam::*;
+
+use crate::{
+    core::{self, BufferWrapper},
+    prefixed_label,
+};
+
+/// The Gaussians 2D conic storage buffer for
+/// [`PreprocessorCreateOptions::conic2d`](crate::PreprocessorCreateOptions::conic2d).
+///
+/// Each element is the [`Conic2dPod`] of the Gaussian at the same index in
+/// [`GaussiansBuffer`](crate::core::GaussiansBuffer).
+#[derive(Debug, Clone)]
+pub struct Conic2dBuffer(wgpu::Buffer);
+
+impl Conic2dBuffer {
+    /// Create a new Gaussians 2D conic buffer.
+    pub fn new(device: &wgpu::Device, gaussian_count: u32) -> Self {
+        Self::new_with_label(device, "", gaussian_count)
+    }
+
+    /// Create a new Gaussians 2D conic buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str, gaussian_count: u32) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(prefixed_label(label, "Conic 2D Buffer").as_str()),
+            size: (gaussian_count as usize * std::mem::size_of::<Conic2dPod>())
+                as wgpu::BufferAddress,
+            usage: Self::DEFAULT_USAGES,
+            mapped_at_creation: false,
+        });
+
+        Self(buffer)
+    }
+
+    /// Get the number of conics.
+    pub fn len(&self) -> usize {
+        self.0.size() as usize / std::mem::size_of::<Conic2dPod>()
+    }
+
+    /// Check if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Download the conics.
+    pub async fn download_conics(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Vec<Conic2dPod>, core::DownloadBufferError> {
+        self.download::<Conic2dPod>(device, queue).await
+    }
+}
+
+impl BufferWrapper for Conic2dBuffer {
+    const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::from_bits_retain(
+        wgpu::BufferUsages::STORAGE.bits() | wgpu::BufferUsages::COPY_SRC.bits(),
+    );
+
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.0
+    }
+}
+
+impl From<Conic2dBuffer> for wgpu::Buffer {
+    fn from(wrapper: Conic2dBuffer) -> Self {
+        wrapper.0
+    }
+}
+
+/// The POD representation of the projected 2D conic of a Gaussian.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Conic2dPod {
+    /// The conic, i.e. the inverse of the 2D covariance in pixels squared, as `(a, b, c)` of the
+    /// symmetric matrix `[[a, b], [b, c]]`.
+    ///
+    /// The covariance is regularized with the epsilon of the
+    /// [`Cov2dRegularization`](crate::Cov2dRegularization) bound to the
+    /// [`Preprocessor`](crate::Preprocessor), the same as the [`Renderer`](crate::Renderer).
+    pub conic: Vec3,
+    /// Whether the Gaussian is in front of the camera with a non-degenerate covariance, as a `u32`
+    /// boolean.
+    ///
+    /// The conic is zero if this is false.
+    pub valid: u32,
+    /// The projected center in pixel coordinates, with the origin at the top left.
+    ///
+    /// This is zero if the Gaussian is behind the camera.
+    pub center: Vec2,
+    pub _padding: [u32; 2],
+}
+
+impl Conic2dPod {
+    /// Check whether the conic is valid.
+    ///
+    /// See [`Conic2dPod::valid`].
+    pub fn is_valid(&self) -> bool {
+        self.valid != 0
+    }
+
+    /// Get the 2D covariance in pixels squared, as `(xx, xy, yy)`.
+    ///
+    /// Returns [`None`] if the conic is not valid.
+    pub fn covariance(&self) -> Option<Vec3> {
+        if !self.is_valid() {
+            return None;
+        }
+
+        let Vec3 { x: a, y: b, z: c } = self.conic;
+        let det = a * c - b * b;
+
+        Some(Vec3::new(c, -b, a) / det)
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{core::BufferWrapper, wgpu_sort};
+use wgpu::util::DeviceExt;
+
+use crate::{SortKeyBufferUpdateError, core::BufferWrapper, prefixed_label, wgpu_sort};
 
 /// The Gaussians depth storage buffer.
 #[derive(Debug, Clone)]
@@ -7,11 +9,16 @@ pub struct GaussiansDepthBuffer(wgpu::Buffer);
 impl GaussiansDepthBuffer {
     /// Create a new Gaussians depth buffer.
     pub fn new(device: &wgpu::Device, gaussian_count: u32) -> Self {
+        Self::new_with_label(device, "", gaussian_count)
+    }
+
+    /// Create a new Gaussians depth buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str, gaussian_count: u32) -> Self {
         // Must correspond to [`crate::radix_sorter::wgpu_sort::GPUSorter::create_keyval_buffers`].
         let size = wgpu_sort::keys_buffer_size_bytes(gaussian_count);
 
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Gaussians Depth Buffer"),
+            label: Some(prefixed_label(label, "Gaussians Depth Buffer").as_str()),
             size: size as wgpu::BufferAddress,
             usage: Self::DEFAULT_USAGES,
             mapped_at_creation: false,
@@ -34,3 +41,118 @@ impl From<GaussiansDepthBuffer> for wgpu::Buffer {
         wrapper.0
     }
 }
+
+/// The Gaussians linear depth storage buffer.
+///
+/// Each element is the view space depth of the Gaussian at the same index in
+/// [`GaussiansBuffer`](crate::core::GaussiansBuffer), in world units, positive in the forward
+/// direction of the camera.
+#[derive(Debug, Clone)]
+pub struct GaussiansLinearDepthBuffer(wgpu::Buffer);
+
+impl GaussiansLinearDepthBuffer {
+    /// Create a new Gaussians linear depth buffer.
+    pub fn new(device: &wgpu::Device, gaussian_count: u32) -> Self {
+        Self::new_with_label(device, "", gaussian_count)
+    }
+
+    /// Create a new Gaussians linear depth buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str, gaussian_count: u32) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(prefixed_label(label, "Gaussians Linear Depth Buffer").as_str()),
+            size: (gaussian_count as usize * std::mem::size_of::<f32>()) as wgpu::BufferAddress,
+            usage: Self::DEFAULT_USAGES,
+            mapped_at_creation: false,
+        });
+
+        Self(buffer)
+    }
+}
+
+impl BufferWrapper for GaussiansLinearDepthBuffer {
+    const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::from_bits_retain(
+        wgpu::BufferUsages::STORAGE.bits() | wgpu::BufferUsages::COPY_SRC.bits(),
+    );
+
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.0
+    }
+}
+
+impl From<GaussiansLinearDepthBuffer> for wgpu::Buffer {
+    fn from(wrapper: GaussiansLinearDepthBuffer) -> Self {
+        wrapper.0
+    }
+}
+
+/// The custom sort key storage buffer for [`DepthMapping::Custom`](crate::DepthMapping::Custom).
+///
+/// Each element is the sort key of the Gaussian at the same index in
+/// [`GaussiansBuffer`](crate::core::GaussiansBuffer). The keys should be non-negative and less
+/// than 2.0, which is the key of the padding, Gaussians with smaller keys are drawn first.
+#[derive(Debug, Clone)]
+pub struct SortKeyBuffer(wgpu::Buffer);
+
+impl SortKeyBuffer {
+    /// Create a new sort key buffer.
+    pub fn new(device: &wgpu::Device, keys: &[f32]) -> Self {
+        Self::new_with_label(device, "", keys)
+    }
+
+    /// Create a new sort key buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str, keys: &[f32]) -> Self {
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(prefixed_label(label, "Sort Key Buffer").as_str()),
+            contents: bytemuck::cast_slice(keys),
+            usage: Self::DEFAULT_USAGES,
+        });
+
+        Self(buffer)
+    }
+
+    /// Get the number of keys.
+    pub fn len(&self) -> usize {
+        self.0.size() as usize / std::mem::size_of::<f32>()
+    }
+
+    /// Check if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Update the buffer.
+    ///
+    /// `keys` should have the same length as the buffer.
+    pub fn update(
+        &self,
+        queue: &wgpu::Queue,
+        keys: &[f32],
+    ) -> Result<(), SortKeyBufferUpdateError> {
+        if keys.len() != self.len() {
+            return Err(SortKeyBufferUpdateError::CountMismatch {
+                count: keys.len(),
+                expected_count: self.len(),
+            });
+        }
+
+        queue.write_buffer(&self.0, 0, bytemuck::cast_slice(keys));
+
+        Ok(())
+    }
+}
+
+impl BufferWrapper for SortKeyBuffer {
+    const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::from_bits_retain(
+        wgpu::BufferUsages::STORAGE.bits() | wgpu::BufferUsages::COPY_DST.bits(),
+    );
+
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.0
+    }
+}
+
+impl From<SortKeyBuffer> for wgpu::Buffer {
+    fn from(wrapper: SortKeyBuffer) -> Self {
+        wrapper.0
+    }
+}
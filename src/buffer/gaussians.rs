@@ -0,0 +1,101 @@
+use crate::{
+    DownloadGaussianError,
+    core::{BufferWrapper, GaussianPod, GaussiansBuffer, IterGaussian},
+};
+
+/// Create a new [`GaussiansBuffer`] by uploading the Gaussians in chunks.
+///
+/// Unlike [`GaussiansBuffer::new_with_usage`], this does not collect all the Gaussians into a
+/// single [`Vec`] of [`GaussianPod`]. Instead, each chunk of `chunk_len` Gaussians is converted,
+/// written to the buffer, and submitted to the `queue` before the next chunk, which reduces the
+/// peak memory usage for very large models.
+///
+/// `progress` is called after each chunk with the number of uploaded Gaussians and the total
+/// number of Gaussians, which can be used to display upload progress.
+///
+/// `usage` must include [`wgpu::BufferUsages::COPY_DST`].
+pub fn create_gaussians_buffer_chunked<G: GaussianPod>(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    gaussians: &impl IterGaussian,
+    usage: wgpu::BufferUsages,
+    chunk_len: usize,
+    mut progress: impl FnMut(usize, usize),
+) -> GaussiansBuffer<G> {
+    let chunk_len = chunk_len.max(1);
+    let mut iter = gaussians.iter_gaussian();
+    let len = iter.len();
+
+    let buffer = GaussiansBuffer::<G>::new_empty_with_usage(device, len, usage);
+
+    let mut pods = Vec::with_capacity(chunk_len.min(len));
+    let mut uploaded = 0;
+    while uploaded < len {
+        pods.clear();
+        pods.extend(iter.by_ref().take(chunk_len).map(|g| G::from_gaussian(&g)));
+
+        if pods.is_empty() {
+            break;
+        }
+
+        buffer
+            .update_range_with_pod(queue, uploaded, &pods)
+            .expect("chunk within buffer");
+
+        // Flush the staged write so its memory can be reclaimed before the next chunk.
+        queue.submit(None);
+
+        uploaded += pods.len();
+        progress(uploaded, len);
+    }
+
+    buffer
+}
+
+/// Download the [`GaussianPod`] at `index` of the [`GaussiansBuffer`].
+///
+/// Only the `size_of::<G>()` bytes of the Gaussian are copied back from the GPU, so this can be
+/// used to inspect a single Gaussian without downloading the whole buffer.
+///
+/// `gaussians` must be created with [`wgpu::BufferUsages::COPY_SRC`].
+pub async fn download_gaussian<G: GaussianPod>(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    gaussians: &GaussiansBuffer<G>,
+    index: usize,
+) -> Result<G, DownloadGaussianError> {
+    let usage = gaussians.buffer().usage();
+    if !usage.contains(wgpu::BufferUsages::COPY_SRC) {
+        return Err(DownloadGaussianError::MissingCopySrcUsage(usage));
+    }
+
+    if index >= gaussians.len() {
+        return Err(DownloadGaussianError::IndexOutOfBounds {
+            index,
+            len: gaussians.len(),
+        });
+    }
+
+    let size = std::mem::size_of::<G>() as wgpu::BufferAddress;
+
+    let download = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Download Gaussian Buffer"),
+        size,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Download Gaussian Encoder"),
+    });
+    encoder.copy_buffer_to_buffer(
+        gaussians.buffer(),
+        index as wgpu::BufferAddress * size,
+        &download,
+        0,
+        size,
+    );
+    queue.submit(Some(encoder.finish()));
+
+    Ok(wgpu::Buffer::map_download::<G>(&download, device).await?[0])
+}
@@ -1,7 +1,11 @@
 use glam::*;
 use wgpu::util::DeviceExt;
 
-use crate::core::{self, BufferWrapper, FixedSizeBufferWrapper};
+use crate::{
+    RenderQuadMode,
+    core::{self, BufferWrapper, FixedSizeBufferWrapper},
+    prefixed_label,
+};
 
 /// The indirect args storage buffer for [`Renderer`](crate::Renderer).
 #[derive(Debug, Clone)]
@@ -10,10 +14,24 @@ pub struct IndirectArgsBuffer(wgpu::Buffer);
 impl IndirectArgsBuffer {
     /// Create a new indirect args buffer.
     pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_quad_mode(device, RenderQuadMode::default())
+    }
+
+    /// Create a new indirect args buffer for the [`RenderQuadMode`].
+    pub fn new_with_quad_mode(device: &wgpu::Device, quad_mode: RenderQuadMode) -> Self {
+        Self::new_with_label_and_quad_mode(device, "", quad_mode)
+    }
+
+    /// Create a new indirect args buffer for the [`RenderQuadMode`] with additional label.
+    pub fn new_with_label_and_quad_mode(
+        device: &wgpu::Device,
+        label: &str,
+        quad_mode: RenderQuadMode,
+    ) -> Self {
         let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Indirect Args Buffer"),
+            label: Some(prefixed_label(label, "Indirect Args Buffer").as_str()),
             contents: wgpu::util::DrawIndirectArgs {
-                vertex_count: 6,
+                vertex_count: quad_mode.vertex_count(),
                 instance_count: 0,
                 first_vertex: 0,
                 first_instance: 0,
@@ -28,7 +46,9 @@ impl IndirectArgsBuffer {
 
 impl BufferWrapper for IndirectArgsBuffer {
     const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::from_bits_retain(
-        wgpu::BufferUsages::STORAGE.bits() | wgpu::BufferUsages::INDIRECT.bits(),
+        wgpu::BufferUsages::STORAGE.bits()
+            | wgpu::BufferUsages::INDIRECT.bits()
+            | wgpu::BufferUsages::COPY_DST.bits(),
     );
 
     fn buffer(&self) -> &wgpu::Buffer {
@@ -61,8 +81,13 @@ pub struct RadixSortIndirectArgsBuffer(wgpu::Buffer);
 impl RadixSortIndirectArgsBuffer {
     /// Create a new dispatch indirect args buffer.
     pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new dispatch indirect args buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
         let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Radix Sort Indirect Args Buffer"),
+            label: Some(prefixed_label(label, "Radix Sort Indirect Args Buffer").as_str()),
             contents: wgpu::util::DispatchIndirectArgs { x: 1, y: 1, z: 1 }.as_bytes(),
             usage: Self::DEFAULT_USAGES,
         });
@@ -106,8 +131,13 @@ pub struct IndirectIndicesBuffer(wgpu::Buffer);
 impl IndirectIndicesBuffer {
     /// Create a new indirect indices buffer.
     pub fn new(device: &wgpu::Device, gaussian_count: u32) -> Self {
+        Self::new_with_label(device, "", gaussian_count)
+    }
+
+    /// Create a new indirect indices buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str, gaussian_count: u32) -> Self {
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Indirect Indices Buffer"),
+            label: Some(prefixed_label(label, "Indirect Indices Buffer").as_str()),
             size: (gaussian_count * std::mem::size_of::<u32>() as u32) as wgpu::BufferAddress,
             usage: Self::DEFAULT_USAGES,
             mapped_at_creation: false,
@@ -118,7 +148,9 @@ impl IndirectIndicesBuffer {
 }
 
 impl BufferWrapper for IndirectIndicesBuffer {
-    const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::STORAGE;
+    const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::from_bits_retain(
+        wgpu::BufferUsages::STORAGE.bits() | wgpu::BufferUsages::COPY_DST.bits(),
+    );
 
     fn buffer(&self) -> &wgpu::Buffer {
         &self.0
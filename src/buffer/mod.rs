@@ -1,7 +1,17 @@
 mod camera;
+mod conic2d;
 mod depth;
+mod gaussians;
 mod indirect_args;
+mod preprocessor_settings;
+mod renderer_settings;
+mod visible_bounds;
 
 pub use camera::*;
+pub use conic2d::*;
 pub use depth::*;
+pub use gaussians::*;
 pub use indirect_args::*;
+pub use preprocessor_settings::*;
+pub use renderer_settings::*;
+pub use visible_bounds::*;
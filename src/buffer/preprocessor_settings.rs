@@ -0,0 +1,149 @@
+use crate::{
+    core::{self, BufferWrapper, FixedSizeBufferWrapper},
+    prefixed_label,
+};
+
+/// The preprocessor settings buffer for [`Preprocessor`](crate::Preprocessor).
+#[derive(Debug, Clone)]
+pub struct PreprocessorSettingsBuffer(wgpu::Buffer);
+
+impl PreprocessorSettingsBuffer {
+    /// Create a new preprocessor settings buffer.
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new preprocessor settings buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(prefixed_label(label, "Preprocessor Settings Buffer").as_str()),
+            size: std::mem::size_of::<PreprocessorSettingsPod>() as wgpu::BufferAddress,
+            usage: Self::DEFAULT_USAGES,
+            mapped_at_creation: false,
+        });
+
+        Self(buffer)
+    }
+
+    /// Update the preprocessor settings buffer.
+    pub fn update(&self, queue: &wgpu::Queue, settings: &PreprocessorSettings) {
+        self.update_with_pod(queue, &PreprocessorSettingsPod::new(settings));
+    }
+
+    /// Update only the minimum opacity of the preprocessor settings buffer.
+    ///
+    /// See [`PreprocessorSettingsPod::min_opacity`].
+    pub fn update_min_opacity(&self, queue: &wgpu::Queue, min_opacity: f32) {
+        queue.write_buffer(
+            &self.0,
+            std::mem::offset_of!(PreprocessorSettingsPod, min_opacity) as wgpu::BufferAddress,
+            bytemuck::bytes_of(&min_opacity),
+        );
+    }
+
+    /// Update only the minimum pixel size of the preprocessor settings buffer.
+    ///
+    /// See [`PreprocessorSettingsPod::min_pixel_size`].
+    pub fn update_min_pixel_size(&self, queue: &wgpu::Queue, min_pixel_size: f32) {
+        queue.write_buffer(
+            &self.0,
+            std::mem::offset_of!(PreprocessorSettingsPod, min_pixel_size) as wgpu::BufferAddress,
+            bytemuck::bytes_of(&min_pixel_size),
+        );
+    }
+
+    /// Update only whether to skip writing the depths of the preprocessor settings buffer.
+    ///
+    /// See [`PreprocessorSettingsPod::skip_depth`].
+    pub fn update_skip_depth(&self, queue: &wgpu::Queue, skip_depth: bool) {
+        queue.write_buffer(
+            &self.0,
+            std::mem::offset_of!(PreprocessorSettingsPod, skip_depth) as wgpu::BufferAddress,
+            bytemuck::bytes_of(&u32::from(skip_depth)),
+        );
+    }
+
+    /// Update the preprocessor settings buffer with [`PreprocessorSettingsPod`].
+    pub fn update_with_pod(&self, queue: &wgpu::Queue, pod: &PreprocessorSettingsPod) {
+        queue.write_buffer(&self.0, 0, bytemuck::bytes_of(pod));
+    }
+}
+
+impl BufferWrapper for PreprocessorSettingsBuffer {
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.0
+    }
+}
+
+impl From<PreprocessorSettingsBuffer> for wgpu::Buffer {
+    fn from(wrapper: PreprocessorSettingsBuffer) -> Self {
+        wrapper.0
+    }
+}
+
+impl TryFrom<wgpu::Buffer> for PreprocessorSettingsBuffer {
+    type Error = core::FixedSizeBufferWrapperError;
+
+    fn try_from(buffer: wgpu::Buffer) -> Result<Self, Self::Error> {
+        Self::verify_buffer_size(&buffer).map(|()| Self(buffer))
+    }
+}
+
+impl FixedSizeBufferWrapper for PreprocessorSettingsBuffer {
+    type Pod = PreprocessorSettingsPod;
+}
+
+/// The preprocessor settings.
+///
+/// The default value renders the same as without any settings.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PreprocessorSettings {
+    /// The minimum opacity in `[0, 1]` of the Gaussians to be rendered.
+    ///
+    /// See [`PreprocessorSettingsPod::min_opacity`].
+    pub min_opacity: f32,
+    /// The minimum size in pixels of the projected Gaussians to be rendered, 0 to disable.
+    ///
+    /// See [`PreprocessorSettingsPod::min_pixel_size`].
+    pub min_pixel_size: f32,
+}
+
+/// The POD representation of the preprocessor settings.
+///
+/// The zeroed value is the default, which renders the same as without any settings.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PreprocessorSettingsPod {
+    /// The minimum opacity in `[0, 1]` of the Gaussians to be rendered.
+    ///
+    /// Gaussians with a lower opacity are culled before sorting.
+    pub min_opacity: f32,
+    /// The minimum size in pixels of the projected Gaussians to be rendered, 0 to disable.
+    ///
+    /// The size is the length of the projected footprint along its major axis, and Gaussians
+    /// with a smaller footprint are culled before sorting, which skips the sub-pixel Gaussians of
+    /// large scenes viewed from afar. Gaussians in [`GaussianDisplayMode::Point`] are not culled.
+    ///
+    /// [`GaussianDisplayMode::Point`]: crate::core::GaussianDisplayMode::Point
+    pub min_pixel_size: f32,
+    /// Whether to skip writing the depth sort keys and their padding to the
+    /// [`GaussiansDepthBuffer`](crate::GaussiansDepthBuffer), as a `u32` boolean.
+    ///
+    /// The keys are only read by the [`RadixSorter`](crate::RadixSorter), so they can be skipped
+    /// when the sort is skipped, see
+    /// [`Viewer::set_sorting_enabled`](crate::Viewer::set_sorting_enabled).
+    pub skip_depth: u32,
+    pub _padding: u32,
+}
+
+impl PreprocessorSettingsPod {
+    /// Create a new preprocessor settings.
+    pub fn new(settings: &PreprocessorSettings) -> Self {
+        Self {
+            min_opacity: settings.min_opacity,
+            min_pixel_size: settings.min_pixel_size,
+            skip_depth: 0,
+            _padding: 0,
+        }
+    }
+}
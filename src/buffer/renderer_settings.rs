@@ -0,0 +1,295 @@
+use std::ops::Range;
+
+use glam::*;
+
+use crate::{
+    Colormap,
+    core::{self, BufferWrapper, FixedSizeBufferWrapper},
+    prefixed_label,
+};
+
+/// The renderer settings buffer for [`Renderer`](crate::Renderer).
+#[derive(Debug, Clone)]
+pub struct RendererSettingsBuffer(wgpu::Buffer);
+
+impl RendererSettingsBuffer {
+    /// Create a new renderer settings buffer.
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new renderer settings buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(prefixed_label(label, "Renderer Settings Buffer").as_str()),
+            size: std::mem::size_of::<RendererSettingsPod>() as wgpu::BufferAddress,
+            usage: Self::DEFAULT_USAGES,
+            mapped_at_creation: false,
+        });
+
+        Self(buffer)
+    }
+
+    /// Update the renderer settings buffer.
+    pub fn update(&self, queue: &wgpu::Queue, settings: &RendererSettings) {
+        self.update_with_pod(queue, &RendererSettingsPod::new(settings));
+    }
+
+    /// Update only the SH dithering of the renderer settings buffer.
+    ///
+    /// See [`RendererSettingsPod::sh_dither`].
+    pub fn update_sh_dither(&self, queue: &wgpu::Queue, sh_dither: bool) {
+        queue.write_buffer(
+            &self.0,
+            std::mem::offset_of!(RendererSettingsPod, sh_dither) as wgpu::BufferAddress,
+            bytemuck::bytes_of(&(sh_dither as u32)),
+        );
+    }
+
+    /// Update only the SH clamping of the renderer settings buffer.
+    ///
+    /// See [`RendererSettingsPod::sh_clamp`].
+    pub fn update_sh_clamp(&self, queue: &wgpu::Queue, sh_clamp: ShClamp) {
+        queue.write_buffer(
+            &self.0,
+            std::mem::offset_of!(RendererSettingsPod, sh_clamp) as wgpu::BufferAddress,
+            bytemuck::bytes_of(&(sh_clamp as u32)),
+        );
+    }
+
+    /// Update only the distance fade of the renderer settings buffer.
+    ///
+    /// See [`RendererSettingsPod::distance_fade_start`] and
+    /// [`RendererSettingsPod::distance_fade_end`].
+    pub fn update_distance_fade(&self, queue: &wgpu::Queue, start: f32, end: f32) {
+        queue.write_buffer(
+            &self.0,
+            std::mem::offset_of!(RendererSettingsPod, distance_fade_start) as wgpu::BufferAddress,
+            bytemuck::cast_slice(&[start, end]),
+        );
+    }
+
+    /// Update the renderer settings buffer with [`RendererSettingsPod`].
+    pub fn update_with_pod(&self, queue: &wgpu::Queue, pod: &RendererSettingsPod) {
+        queue.write_buffer(&self.0, 0, bytemuck::bytes_of(pod));
+    }
+}
+
+impl BufferWrapper for RendererSettingsBuffer {
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.0
+    }
+}
+
+impl From<RendererSettingsBuffer> for wgpu::Buffer {
+    fn from(wrapper: RendererSettingsBuffer) -> Self {
+        wrapper.0
+    }
+}
+
+impl TryFrom<wgpu::Buffer> for RendererSettingsBuffer {
+    type Error = core::FixedSizeBufferWrapperError;
+
+    fn try_from(buffer: wgpu::Buffer) -> Result<Self, Self::Error> {
+        Self::verify_buffer_size(&buffer).map(|()| Self(buffer))
+    }
+}
+
+impl FixedSizeBufferWrapper for RendererSettingsBuffer {
+    type Pod = RendererSettingsPod;
+}
+
+/// The renderer settings.
+///
+/// The default value renders the same as without any settings.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RendererSettings {
+    /// Whether to apply ordered dithering to the colors decoded from
+    /// [`GaussianShNorm8Config`](crate::core::GaussianShNorm8Config) SH.
+    ///
+    /// This has no effect on the other SH configurations.
+    pub sh_dither: bool,
+    /// The clamping of the SH evaluated colors.
+    pub sh_clamp: ShClamp,
+    /// The depth visualization, `None` to display the colors.
+    pub depth_display: Option<DepthDisplay>,
+    /// The regularization of the projected 2D covariance.
+    pub cov2d_regularization: Cov2dRegularization,
+    /// The range of the view space distance to fade out the Gaussians over, `None` to disable.
+    ///
+    /// See [`RendererSettingsPod::distance_fade_end`].
+    pub distance_fade: Option<Range<f32>>,
+    /// The tonemapping of the colors written to the render target.
+    pub tonemap: Tonemap,
+}
+
+/// The clamping of the colors evaluated from the SH coefficients.
+///
+/// The evaluated color includes the `0.5` offset of the degree 0 coefficient, and can be negative
+/// or above 1 for view directions the SH are poorly fitted to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShClamp {
+    /// Clamp the negative components to 0, same as the reference implementation.
+    ///
+    /// Components above 1 are clamped by the render target, which may blow out highlights.
+    #[default]
+    Zero = 0,
+    /// Clamp the negative components to 0, and softly compress the components above
+    /// [`ShClamp::SOFT_CEILING_KNEE`] towards 1.
+    SoftCeiling = 1,
+}
+
+impl ShClamp {
+    /// The component value where [`ShClamp::SoftCeiling`] starts to compress.
+    pub const SOFT_CEILING_KNEE: f32 = 0.8;
+}
+
+/// The tonemapping of the colors written to the render target.
+///
+/// The colors are tonemapped for each Gaussian before blending, so that a low dynamic range render
+/// target, e.g. [`wgpu::TextureFormat::Rgba8Unorm`], keeps the gradation of the colors above 1
+/// instead of clipping them. The linear HDR render target of
+/// [`Renderer::new_with_hdr_target`](crate::Renderer::new_with_hdr_target) is not tonemapped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tonemap {
+    /// No tonemapping, the components above 1 are clamped by the render target.
+    #[default]
+    None = 0,
+    /// The Reinhard operator `color / (1 + color)`.
+    Reinhard = 1,
+    /// The ACES filmic curve, fitted by Krzysztof Narkowicz.
+    Aces = 2,
+}
+
+impl Tonemap {
+    /// Map the linear color on the CPU side, same as the render shader.
+    pub fn map(&self, color: Vec3) -> Vec3 {
+        let color = color.max(Vec3::ZERO);
+        match self {
+            Self::None => color,
+            Self::Reinhard => color / (1.0 + color),
+            Self::Aces => ((color * (2.51 * color + 0.03))
+                / (color * (2.43 * color + 0.59) + 0.14))
+                .clamp(Vec3::ZERO, Vec3::ONE),
+        }
+    }
+}
+
+/// The depth visualization of the Gaussians, replacing their colors for debugging the sort and
+/// the camera setup.
+///
+/// The view space depth of each Gaussian is normalized in `range` and mapped by the `colormap`
+/// reversed, so that with [`Colormap::Grayscale`] the near end is white and the far end is black.
+/// The opacity of the Gaussians is kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthDisplay {
+    /// The colormap.
+    pub colormap: Colormap,
+    /// The range of the view space depth to map.
+    pub range: Range<f32>,
+}
+
+/// The regularization of the projected 2D covariance of the Gaussians.
+///
+/// Thin Gaussians seen edge-on have a 2D covariance that is singular up to floating point errors,
+/// which may have a negative eigenvalue and are dropped. Adding `epsilon` to the diagonal keeps them
+/// as thin lines of at least `sqrt(epsilon)` pixels in standard deviation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cov2dRegularization {
+    /// The value in pixels squared added to the diagonal of the 2D covariance, 0 to disable.
+    pub epsilon: f32,
+    /// Whether to color the Gaussians with the minor eigenvalue of the 2D covariance below
+    /// `epsilon` in magenta, i.e. the ones shaped mostly by the regularization, for debugging.
+    ///
+    /// The opacity of the Gaussians is kept.
+    pub highlight: bool,
+}
+
+impl Cov2dRegularization {
+    /// The default [`Cov2dRegularization::epsilon`], same as the preprocessor.
+    pub const DEFAULT_EPSILON: f32 = 0.01;
+}
+
+impl Default for Cov2dRegularization {
+    fn default() -> Self {
+        Self {
+            epsilon: Self::DEFAULT_EPSILON,
+            highlight: false,
+        }
+    }
+}
+
+/// The POD representation of the renderer settings.
+///
+/// The zeroed value is the default, which renders the same as without any settings.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct RendererSettingsPod {
+    /// Whether to apply ordered dithering to the colors decoded from
+    /// [`GaussianShNorm8Config`](crate::core::GaussianShNorm8Config) SH, as a `u32` boolean.
+    ///
+    /// This has no effect on the other SH configurations.
+    pub sh_dither: u32,
+    /// The [`ShClamp`] of the SH evaluated colors, as a `u32`.
+    pub sh_clamp: u32,
+    /// The range of the view space depth of the [`DepthDisplay`].
+    pub depth_range: Vec2,
+    /// The [`Colormap`] of the [`DepthDisplay`] plus 1, or 0 to display the colors, as a `u32`.
+    pub depth_colormap: u32,
+    /// The [`Cov2dRegularization::epsilon`] minus [`Cov2dRegularization::DEFAULT_EPSILON`], so
+    /// that the zeroed value is the default.
+    pub cov2d_epsilon: f32,
+    /// The [`Cov2dRegularization::highlight`], as a `u32` boolean.
+    pub cov2d_highlight: u32,
+    /// The view space distance where the Gaussians start to fade out.
+    pub distance_fade_start: f32,
+    /// The view space distance where the Gaussians are fully faded out.
+    ///
+    /// The opacity is multiplied by a smoothstep from 1 at
+    /// [`RendererSettingsPod::distance_fade_start`] to 0 at this distance. The fade is disabled if
+    /// this is not greater than the start, or is infinity.
+    pub distance_fade_end: f32,
+    /// The [`Tonemap`] of the colors written to the render target, as a `u32`.
+    pub tonemap: u32,
+    pub _padding: [u32; 2],
+}
+
+impl RendererSettingsPod {
+    /// Create a new renderer settings.
+    pub fn new(settings: &RendererSettings) -> Self {
+        let RendererSettings {
+            sh_dither,
+            sh_clamp,
+            depth_display,
+            cov2d_regularization,
+            distance_fade,
+            tonemap,
+        } = settings;
+
+        let (depth_range, depth_colormap) = match depth_display {
+            Some(DepthDisplay { colormap, range }) => {
+                (Vec2::new(range.start, range.end), *colormap as u32 + 1)
+            }
+            None => (Vec2::ZERO, 0),
+        };
+
+        let (distance_fade_start, distance_fade_end) = match distance_fade {
+            Some(range) => (range.start, range.end),
+            None => (0.0, 0.0),
+        };
+
+        Self {
+            sh_dither: *sh_dither as u32,
+            sh_clamp: *sh_clamp as u32,
+            depth_range,
+            depth_colormap,
+            cov2d_epsilon: cov2d_regularization.epsilon.max(0.0)
+                - Cov2dRegularization::DEFAULT_EPSILON,
+            cov2d_highlight: cov2d_regularization.highlight as u32,
+            distance_fade_start,
+            distance_fade_end,
+            tonemap: *tonemap as u32,
+            _padding: [0; 2],
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use glam::*;
+
+use crate::{
+    core::{self, BufferWrapper, FixedSizeBufferWrapper},
+    prefixed_label,
+};
+
+/// The visible bounds storage buffer for [`Preprocessor`](crate::Preprocessor).
+///
+/// The preprocessor resets it every time it runs, then writes the axis-aligned bounding box of the
+/// world positions of the Gaussians which survived culling, so it reflects the current view
+/// instead of the whole model.
+#[derive(Debug, Clone)]
+pub struct VisibleBoundsBuffer(wgpu::Buffer);
+
+impl VisibleBoundsBuffer {
+    /// Create a new visible bounds buffer.
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new visible bounds buffer with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(prefixed_label(label, "Visible Bounds Buffer").as_str()),
+            size: std::mem::size_of::<VisibleBoundsPod>() as wgpu::BufferAddress,
+            usage: Self::DEFAULT_USAGES,
+            mapped_at_creation: false,
+        });
+
+        Self(buffer)
+    }
+
+    /// Download the visible bounds as the minimum and maximum corners.
+    ///
+    /// Returns [`None`] if no Gaussian was visible in the last preprocess.
+    pub async fn download_bounds(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Option<(Vec3, Vec3)>, core::DownloadBufferError> {
+        Ok(self.download::<VisibleBoundsPod>(device, queue).await?[0].bounds())
+    }
+}
+
+impl BufferWrapper for VisibleBoundsBuffer {
+    const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::from_bits_retain(
+        wgpu::BufferUsages::STORAGE.bits() | wgpu::BufferUsages::COPY_SRC.bits(),
+    );
+
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.0
+    }
+}
+
+impl From<VisibleBoundsBuffer> for wgpu::Buffer {
+    fn from(wrapper: VisibleBoundsBuffer) -> Self {
+        wrapper.0
+    }
+}
+
+impl TryFrom<wgpu::Buffer> for VisibleBoundsBuffer {
+    type Error = core::FixedSizeBufferWrapperError;
+
+    fn try_from(buffer: wgpu::Buffer) -> Result<Self, Self::Error> {
+        Self::verify_buffer_size(&buffer).map(|()| Self(buffer))
+    }
+}
+
+impl FixedSizeBufferWrapper for VisibleBoundsBuffer {
+    type Pod = VisibleBoundsPod;
+}
+
+/// The POD representation of the visible bounds.
+///
+/// The coordinates are stored as order-preserving integer keys, so that they can be reduced with
+/// integer atomics in the shader, see [`VisibleBoundsPod::key`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct VisibleBoundsPod {
+    /// The keys of the minimum corner.
+    pub min_keys: [i32; 3],
+    /// The keys of the maximum corner.
+    pub max_keys: [i32; 3],
+}
+
+impl VisibleBoundsPod {
+    /// The empty bounds, which the preprocessor resets to.
+    pub const EMPTY: Self = Self {
+        min_keys: [i32::MAX; 3],
+        max_keys: [i32::MIN; 3],
+    };
+
+    /// Create new visible bounds from the minimum and maximum corners.
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self {
+            min_keys: min.to_array().map(Self::key),
+            max_keys: max.to_array().map(Self::key),
+        }
+    }
+
+    /// Get the minimum and maximum corners.
+    ///
+    /// Returns [`None`] if the bounds are empty.
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some((
+            Vec3::from_array(self.min_keys.map(Self::value)),
+            Vec3::from_array(self.max_keys.map(Self::value)),
+        ))
+    }
+
+    /// Check whether the bounds are empty.
+    pub fn is_empty(&self) -> bool {
+        self.min_keys
+            .iter()
+            .zip(self.max_keys.iter())
+            .any(|(min, max)| min > max)
+    }
+
+    /// Convert a float to its order-preserving integer key.
+    ///
+    /// The keys of any two non-NaN floats compare the same as the floats, except that `-0.0` is
+    /// less than `0.0`.
+    pub fn key(value: f32) -> i32 {
+        let bits = value.to_bits() as i32;
+        if bits < 0 { bits ^ i32::MAX } else { bits }
+    }
+
+    /// Convert an order-preserving integer key back to the float.
+    ///
+    /// This is the inverse of [`VisibleBoundsPod::key`].
+    pub fn value(key: i32) -> f32 {
+        let bits = if key < 0 { key ^ i32::MAX } else { key };
+        f32::from_bits(bits as u32)
+    }
+}
+
+impl Default for VisibleBoundsPod {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
@@ -13,6 +13,148 @@ pub trait CameraTrait {
     fn projection(&self, aspect_ratio: f32) -> Mat4;
 }
 
+/// The coordinate system convention of a [`Camera`].
+///
+/// It defines the world directions that [`Camera`] treats as up and as forward when both pitch and
+/// yaw are zero. The right direction is `forward × up`, and the view matrix is always right-handed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateSystem {
+    /// The up direction.
+    pub up: Vec3,
+    /// The forward direction when pitch and yaw are zero.
+    pub forward: Vec3,
+}
+
+impl CoordinateSystem {
+    /// Y-up with +Z forward, the default of [`Camera`].
+    pub const Y_UP_Z_FORWARD: Self = Self {
+        up: Vec3::Y,
+        forward: Vec3::Z,
+    };
+
+    /// Y-up with -Z forward, matching glTF and Three.js.
+    pub const GLTF: Self = Self {
+        up: Vec3::Y,
+        forward: Vec3::NEG_Z,
+    };
+
+    /// Y-down with +Z forward, matching the COLMAP convention used by the INRIA 3D Gaussian
+    /// splatting models.
+    ///
+    /// With this, the models can be viewed upright without rotating them.
+    pub const INRIA: Self = Self {
+        up: Vec3::NEG_Y,
+        forward: Vec3::Z,
+    };
+
+    /// Get the right direction when pitch and yaw are zero.
+    pub fn right(&self) -> Vec3 {
+        self.forward.cross(self.up).normalize()
+    }
+
+    /// Get the forward direction at the pitch and yaw.
+    fn forward_at(&self, pitch: f32, yaw: f32) -> Vec3 {
+        let left = self.up.cross(self.forward);
+
+        left * pitch.cos() * yaw.sin()
+            + self.up * pitch.sin()
+            + self.forward * pitch.cos() * yaw.cos()
+    }
+}
+
+impl Default for CoordinateSystem {
+    fn default() -> Self {
+        Self::Y_UP_Z_FORWARD
+    }
+}
+
+/// The pinhole intrinsics of a capture camera, e.g. from COLMAP `cameras.txt` or a NeRF style
+/// `transforms.json`.
+///
+/// The values are in pixels of an image of `size`, with the origin at the top left corner and y
+/// pointing down. The principal point may be off the image center, which needs an asymmetric
+/// frustum that a single vertical FOV cannot express.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraIntrinsics {
+    /// The focal lengths `(fx, fy)`.
+    pub focal: Vec2,
+    /// The principal point `(cx, cy)`.
+    pub principal_point: Vec2,
+    /// The image size `(width, height)`.
+    pub size: UVec2,
+}
+
+impl CameraIntrinsics {
+    /// Create new intrinsics.
+    pub fn new(fx: f32, fy: f32, cx: f32, cy: f32, width: u32, height: u32) -> Self {
+        Self {
+            focal: Vec2::new(fx, fy),
+            principal_point: Vec2::new(cx, cy),
+            size: UVec2::new(width, height),
+        }
+    }
+
+    /// Get the vertical FOV.
+    pub fn vertical_fov(&self) -> f32 {
+        2.0 * (self.size.y as f32 / (2.0 * self.focal.y)).atan()
+    }
+
+    /// Get the projection matrix mapping the near plane to depth 0 and the far plane to depth 1.
+    ///
+    /// Swap `near` and `far` for a reverse-Z projection.
+    pub fn projection(&self, near: f32, far: f32) -> Mat4 {
+        let size = self.size.as_vec2();
+        let scale = 2.0 * self.focal / size;
+        // The principal point in NDC, with y pointing up
+        let offset = Vec2::new(
+            2.0 * self.principal_point.x / size.x - 1.0,
+            1.0 - 2.0 * self.principal_point.y / size.y,
+        );
+        let r = far / (near - far);
+
+        Mat4::from_cols(
+            Vec4::new(scale.x, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, scale.y, 0.0, 0.0),
+            Vec4::new(-offset.x, -offset.y, r, -1.0),
+            Vec4::new(0.0, 0.0, r * near, 0.0),
+        )
+    }
+}
+
+/// The projection of a [`Camera`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraProjection {
+    /// Perspective projection with the vertical FOV in radians.
+    Perspective { fovy: f32 },
+    /// Orthographic projection with the height of the view volume in world units.
+    ///
+    /// The width is the height multiplied by the aspect ratio of the render target.
+    Orthographic { height: f32 },
+}
+
+impl CameraProjection {
+    /// Get the projection matrix.
+    ///
+    /// `near` may be greater than `far` for a reverse-Z projection.
+    pub fn matrix(&self, aspect_ratio: f32, near: f32, far: f32) -> Mat4 {
+        match *self {
+            Self::Perspective { fovy } => Mat4::perspective_rh(fovy, aspect_ratio, near, far),
+            Self::Orthographic { height } => {
+                let half_height = height * 0.5;
+                let half_width = half_height * aspect_ratio;
+                Mat4::orthographic_rh(
+                    -half_width,
+                    half_width,
+                    -half_height,
+                    half_height,
+                    near,
+                    far,
+                )
+            }
+        }
+    }
+}
+
 /// A camera.
 #[derive(Debug, Clone)]
 pub struct Camera {
@@ -20,33 +162,133 @@ pub struct Camera {
     pub pos: Vec3,
     /// The z range of the camera.
     pub z: Range<f32>,
-    /// The vertical FOV.
-    pub vertical_fov: f32,
+    /// The projection.
+    pub projection: CameraProjection,
     /// The pitch.
     pub pitch: f32,
     /// The yaw.
     pub yaw: f32,
+    /// The coordinate system convention.
+    pub coordinate_system: CoordinateSystem,
+    /// Whether the projection maps the near plane to depth 1 and the far plane to depth 0.
+    ///
+    /// Reverse-Z gives better depth precision when used with a floating point depth buffer, the
+    /// depth stencil state should then clear to 0 and compare with
+    /// [`wgpu::CompareFunction::Greater`]. The [`Preprocessor`](crate::Preprocessor) should use
+    /// [`DepthMapping::LinearReverseZ`](crate::DepthMapping::LinearReverseZ) or
+    /// [`DepthMapping::Logarithmic`](crate::DepthMapping::Logarithmic) to sort correctly.
+    pub reverse_z: bool,
+    /// The optional pinhole intrinsics of a capture camera.
+    ///
+    /// If set, the projection is built from the intrinsics instead of
+    /// [`Camera::projection`] and the aspect ratio of the render target, which should then have
+    /// the same aspect ratio as [`CameraIntrinsics::size`].
+    pub intrinsics: Option<CameraIntrinsics>,
 }
 
 impl Camera {
-    /// Up direction.
+    /// Up direction of the default [`CoordinateSystem`].
     pub const UP: Vec3 = Vec3::Y;
 
     /// The pitch limit.
     pub const PITCH_LIMIT: Range<f32> =
         -std::f32::consts::FRAC_PI_2 + 1e-6..std::f32::consts::FRAC_PI_2 - 1e-6;
 
-    /// Create a new camera.
+    /// Create a new camera with perspective projection.
     pub fn new(z: Range<f32>, vertical_fov: f32) -> Self {
+        Self::new_with_coordinate_system(z, vertical_fov, CoordinateSystem::default())
+    }
+
+    /// Create a new camera with orthographic projection.
+    ///
+    /// `height` is the height of the view volume in world units.
+    pub fn new_orthographic(z: Range<f32>, height: f32) -> Self {
+        Self {
+            projection: CameraProjection::Orthographic { height },
+            ..Self::new(z, std::f32::consts::FRAC_PI_2)
+        }
+    }
+
+    /// Create a new camera with perspective projection and the [`CoordinateSystem`].
+    pub fn new_with_coordinate_system(
+        z: Range<f32>,
+        vertical_fov: f32,
+        coordinate_system: CoordinateSystem,
+    ) -> Self {
         Self {
             pos: Vec3::ZERO,
             z,
-            vertical_fov,
+            projection: CameraProjection::Perspective { fovy: vertical_fov },
             pitch: 0.0,
             yaw: 0.0,
+            coordinate_system,
+            reverse_z: false,
+            intrinsics: None,
         }
     }
 
+    /// Create a new camera matching the pinhole [`CameraIntrinsics`] of a capture camera.
+    ///
+    /// [`Camera::projection`] is set to the perspective projection with
+    /// [`CameraIntrinsics::vertical_fov`], e.g. for [`Camera::frame_sphere`].
+    pub fn from_intrinsics(z: Range<f32>, intrinsics: CameraIntrinsics) -> Self {
+        Self {
+            intrinsics: Some(intrinsics),
+            ..Self::new(z, intrinsics.vertical_fov())
+        }
+    }
+
+    /// Get the near plane.
+    pub fn near(&self) -> f32 {
+        self.z.start
+    }
+
+    /// Get the far plane.
+    pub fn far(&self) -> f32 {
+        self.z.end
+    }
+
+    /// Set the near plane.
+    pub fn set_near(&mut self, near: f32) {
+        self.z.start = near;
+    }
+
+    /// Set the far plane.
+    pub fn set_far(&mut self, far: f32) {
+        self.z.end = far;
+    }
+
+    /// Get the vertical FOV in radians of the perspective projection.
+    ///
+    /// Returns [`None`] for the orthographic projection.
+    #[deprecated(note = "use `Camera::projection` instead")]
+    pub fn vertical_fov(&self) -> Option<f32> {
+        match self.projection {
+            CameraProjection::Perspective { fovy } => Some(fovy),
+            CameraProjection::Orthographic { .. } => None,
+        }
+    }
+
+    /// Move the camera backward from `center` so that the sphere fits in the vertical FOV.
+    ///
+    /// The orientation is kept. For an aspect ratio (width / height) smaller than 1, the sphere
+    /// may be cut off horizontally.
+    ///
+    /// For [`CameraProjection::Orthographic`], the height is set to the diameter instead, and the
+    /// camera is moved backward just enough for the sphere to be in front of the near plane.
+    ///
+    /// The sphere can be computed by [`bounding_sphere_weighted`](crate::bounding_sphere_weighted).
+    pub fn frame_sphere(&mut self, center: Vec3, radius: f32) {
+        let distance = match &mut self.projection {
+            CameraProjection::Perspective { fovy } => radius / (*fovy * 0.5).sin(),
+            CameraProjection::Orthographic { height } => {
+                *height = radius * 2.0;
+                radius + self.z.start
+            }
+        };
+        self.pos = center - self.get_forward() * distance;
+    }
+
     /// Move the camera.
     pub fn move_by(&mut self, forward: f32, right: f32) {
         self.pos += self.get_forward() * forward + self.get_right() * right;
@@ -54,7 +296,7 @@ impl Camera {
 
     /// Move the camera forward.
     pub fn move_up(&mut self, up: f32) {
-        self.pos += Self::UP * up;
+        self.pos += self.coordinate_system.up * up;
     }
 
     /// Apply pitch.
@@ -69,25 +311,282 @@ impl Camera {
 
     /// Get the forward vector.
     pub fn get_forward(&self) -> Vec3 {
-        Vec3::new(
-            self.pitch.cos() * self.yaw.sin(),
-            self.pitch.sin(),
-            self.pitch.cos() * self.yaw.cos(),
-        )
+        self.coordinate_system.forward_at(self.pitch, self.yaw)
     }
 
     /// Get the right vector.
     pub fn get_right(&self) -> Vec3 {
-        self.get_forward().cross(Self::UP).normalize()
+        self.get_forward()
+            .cross(self.coordinate_system.up)
+            .normalize()
     }
 }
 
 impl CameraTrait for Camera {
     fn view(&self) -> Mat4 {
-        Mat4::look_to_rh(self.pos, self.get_forward(), Self::UP)
+        Mat4::look_to_rh(self.pos, self.get_forward(), self.coordinate_system.up)
     }
 
     fn projection(&self, aspect_ratio: f32) -> Mat4 {
-        Mat4::perspective_rh(self.vertical_fov, aspect_ratio, self.z.start, self.z.end)
+        if let Some(intrinsics) = &self.intrinsics {
+            return match self.reverse_z {
+                false => intrinsics.projection(self.z.start, self.z.end),
+                true => intrinsics.projection(self.z.end, self.z.start),
+            };
+        }
+
+        match self.reverse_z {
+            false => self
+                .projection
+                .matrix(aspect_ratio, self.z.start, self.z.end),
+            true => self
+                .projection
+                .matrix(aspect_ratio, self.z.end, self.z.start),
+        }
+    }
+}
+
+/// A [`Camera`] with velocity based smoothing.
+///
+/// Instead of moving the camera by per-frame deltas, set the target velocities from the input and
+/// call [`SmoothCamera::update`] every frame. The velocities approach their targets with
+/// exponential damping, and the motion is integrated exactly over each frame, so it is the same
+/// regardless of the framerate.
+#[derive(Debug, Clone)]
+pub struct SmoothCamera {
+    /// The camera.
+    pub camera: Camera,
+    /// The velocity in units per second, as `(forward, right, up)` relative to the camera.
+    pub velocity: Vec3,
+    /// The target velocity in units per second, as `(forward, right, up)` relative to the camera.
+    pub target_velocity: Vec3,
+    /// The angular velocity in radians per second, as `(pitch, yaw)`.
+    pub angular_velocity: Vec2,
+    /// The target angular velocity in radians per second, as `(pitch, yaw)`.
+    pub target_angular_velocity: Vec2,
+    /// The damping rate per second.
+    ///
+    /// The velocities close `1 - exp(-damping)` of the gap to their targets every second, a larger
+    /// value is more responsive and a smaller value is smoother. A non-positive value disables the
+    /// smoothing, so the velocities are set to their targets immediately.
+    pub damping: f32,
+    /// The maximum speed in units per second.
+    ///
+    /// [`SmoothCamera::target_velocity`] is clamped to this length.
+    pub max_speed: f32,
+}
+
+impl SmoothCamera {
+    /// The default damping rate.
+    pub const DEFAULT_DAMPING: f32 = 10.0;
+
+    /// The default maximum speed.
+    pub const DEFAULT_MAX_SPEED: f32 = 1.0;
+
+    /// Create a new smooth camera with the default damping and maximum speed.
+    pub fn new(camera: Camera) -> Self {
+        Self::new_with(camera, Self::DEFAULT_DAMPING, Self::DEFAULT_MAX_SPEED)
+    }
+
+    /// Create a new smooth camera with the damping and maximum speed.
+    pub fn new_with(camera: Camera, damping: f32, max_speed: f32) -> Self {
+        Self {
+            camera,
+            velocity: Vec3::ZERO,
+            target_velocity: Vec3::ZERO,
+            angular_velocity: Vec2::ZERO,
+            target_angular_velocity: Vec2::ZERO,
+            damping,
+            max_speed,
+        }
+    }
+
+    /// Set the target velocity.
+    ///
+    /// See [`SmoothCamera::target_velocity`].
+    pub fn set_target_velocity(&mut self, forward: f32, right: f32, up: f32) {
+        self.target_velocity = Vec3::new(forward, right, up);
+    }
+
+    /// Set the target angular velocity.
+    ///
+    /// See [`SmoothCamera::target_angular_velocity`].
+    pub fn set_target_angular_velocity(&mut self, pitch: f32, yaw: f32) {
+        self.target_angular_velocity = Vec2::new(pitch, yaw);
+    }
+
+    /// Stop the camera immediately.
+    ///
+    /// Both the velocities and the targets are set to zero.
+    pub fn stop(&mut self) {
+        self.velocity = Vec3::ZERO;
+        self.target_velocity = Vec3::ZERO;
+        self.angular_velocity = Vec2::ZERO;
+        self.target_angular_velocity = Vec2::ZERO;
+    }
+
+    /// Advance the camera by `delta_time` seconds.
+    pub fn update(&mut self, delta_time: f32) {
+        if delta_time <= 0.0 {
+            return;
+        }
+
+        let target_velocity = self.target_velocity.clamp_length_max(self.max_speed);
+
+        let (velocity, displacement) =
+            Self::integrate(self.velocity, target_velocity, self.damping, delta_time);
+        let (angular_velocity, rotation) = Self::integrate(
+            self.angular_velocity,
+            self.target_angular_velocity,
+            self.damping,
+            delta_time,
+        );
+
+        self.velocity = velocity;
+        self.angular_velocity = angular_velocity;
+
+        self.camera.move_by(displacement.x, displacement.y);
+        self.camera.move_up(displacement.z);
+        self.camera.pitch_by(rotation.x);
+        self.camera.yaw_by(rotation.y);
+    }
+
+    /// Integrate a velocity approaching `target` with exponential damping over `delta_time`.
+    ///
+    /// Returns the velocity at the end and the displacement over `delta_time`.
+    fn integrate<T>(velocity: T, target: T, damping: f32, delta_time: f32) -> (T, T)
+    where
+        T: Copy
+            + std::ops::Add<Output = T>
+            + std::ops::Sub<Output = T>
+            + std::ops::Mul<f32, Output = T>,
+    {
+        if damping <= 0.0 {
+            return (target, target * delta_time);
+        }
+
+        let decay = (-damping * delta_time).exp();
+        let gap = velocity - target;
+
+        (
+            target + gap * decay,
+            target * delta_time + gap * ((1.0 - decay) / damping),
+        )
+    }
+}
+
+impl CameraTrait for SmoothCamera {
+    fn view(&self) -> Mat4 {
+        self.camera.view()
+    }
+
+    fn projection(&self, aspect_ratio: f32) -> Mat4 {
+        self.camera.projection(aspect_ratio)
+    }
+}
+
+/// An orbit controller for a [`Camera`], for turntable style inspection around a target.
+///
+/// The camera is placed [`OrbitController::distance`] away from [`OrbitController::target`],
+/// looking at it with [`OrbitController::pitch`] and [`OrbitController::yaw`]. Update the
+/// controller from the input, then call [`OrbitController::apply_to`] to update the camera.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitController {
+    /// The target to orbit around.
+    pub target: Vec3,
+    /// The distance from the camera to the target.
+    pub distance: f32,
+    /// The pitch of the camera.
+    pub pitch: f32,
+    /// The yaw of the camera.
+    pub yaw: f32,
+    /// The coordinate system convention.
+    ///
+    /// This is applied to the camera by [`OrbitController::apply_to`].
+    pub coordinate_system: CoordinateSystem,
+}
+
+impl OrbitController {
+    /// The minimum distance.
+    pub const MIN_DISTANCE: f32 = 1e-4;
+
+    /// Create a new orbit controller with zero pitch and yaw.
+    pub fn new(target: Vec3, distance: f32) -> Self {
+        Self {
+            target,
+            distance: distance.max(Self::MIN_DISTANCE),
+            pitch: 0.0,
+            yaw: 0.0,
+            coordinate_system: CoordinateSystem::default(),
+        }
+    }
+
+    /// Create a new orbit controller around `target` from the position of the `camera`.
+    ///
+    /// The orientation is set to look from the camera at the target, so applying the controller
+    /// keeps the position of the camera.
+    pub fn from_camera(camera: &Camera, target: Vec3) -> Self {
+        let CoordinateSystem { up, forward } = camera.coordinate_system;
+        let offset = target - camera.pos;
+        let dir = offset.normalize_or(camera.get_forward());
+
+        Self {
+            target,
+            distance: offset.length().max(Self::MIN_DISTANCE),
+            pitch: dir
+                .dot(up)
+                .asin()
+                .clamp(Camera::PITCH_LIMIT.start, Camera::PITCH_LIMIT.end),
+            yaw: dir
+                .dot(up.cross(forward))
+                .atan2(dir.dot(forward))
+                .rem_euclid(2.0 * std::f32::consts::PI),
+            coordinate_system: camera.coordinate_system,
+        }
+    }
+
+    /// Orbit around the target.
+    ///
+    /// `delta` is added to the yaw and pitch as `(yaw, pitch)` in radians. The pitch is clamped to
+    /// [`Camera::PITCH_LIMIT`] so the camera does not flip over at the poles.
+    pub fn orbit(&mut self, delta: Vec2) {
+        self.yaw = (self.yaw + delta.x).rem_euclid(2.0 * std::f32::consts::PI);
+        self.pitch =
+            (self.pitch + delta.y).clamp(Camera::PITCH_LIMIT.start, Camera::PITCH_LIMIT.end);
+    }
+
+    /// Move the target in the view plane.
+    ///
+    /// `delta` is the movement as `(right, up)` relative to the camera in world units.
+    pub fn pan(&mut self, delta: Vec2) {
+        let forward = self.forward();
+        let right = forward.cross(self.coordinate_system.up).normalize();
+        let up = right.cross(forward);
+
+        self.target += right * delta.x + up * delta.y;
+    }
+
+    /// Move the camera towards the target.
+    ///
+    /// The distance is scaled by `exp(-amount)`, so a positive `amount` zooms in, and the same
+    /// `amount` zooms by the same ratio at any distance. The distance is kept at least
+    /// [`OrbitController::MIN_DISTANCE`].
+    pub fn zoom(&mut self, amount: f32) {
+        self.distance = (self.distance * (-amount).exp()).max(Self::MIN_DISTANCE);
+    }
+
+    /// Apply the controller to the `camera`.
+    ///
+    /// The position, pitch, yaw, and coordinate system of the camera are set, the rest are kept.
+    pub fn apply_to(&self, camera: &mut Camera) {
+        camera.coordinate_system = self.coordinate_system;
+        camera.pitch = self.pitch;
+        camera.yaw = self.yaw;
+        camera.pos = self.target - self.forward() * self.distance.max(Self::MIN_DISTANCE);
+    }
+
+    /// Get the forward vector of the camera.
+    fn forward(&self) -> Vec3 {
+        self.coordinate_system.forward_at(self.pitch, self.yaw)
     }
 }
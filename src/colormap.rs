@@ -0,0 +1,72 @@
+use glam::*;
+
+/// A color ramp mapping a scalar in `[0, 1]` to a color.
+///
+/// The ramps are defined by evenly spaced control points in [`Colormap::table`] and linearly
+/// interpolated in between. The same control points are mirrored in the
+/// [`shader::colormap`](crate::shader::colormap) module for use in shaders, e.g. for
+/// data-driven coloring of the Gaussians, while [`Colormap::sample`] can be used on the CPU side
+/// for legends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colormap {
+    /// Black to white.
+    #[default]
+    Grayscale,
+    /// The perceptually uniform viridis ramp from matplotlib.
+    Viridis,
+    /// The turbo rainbow ramp from Google.
+    Turbo,
+}
+
+impl Colormap {
+    /// The control points of [`Colormap::Grayscale`].
+    pub const GRAYSCALE: [Vec4; 2] = [Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(1.0, 1.0, 1.0, 1.0)];
+
+    /// The control points of [`Colormap::Viridis`].
+    pub const VIRIDIS: [Vec4; 9] = [
+        Vec4::new(0.2667, 0.0039, 0.3294, 1.0),
+        Vec4::new(0.2784, 0.1765, 0.4824, 1.0),
+        Vec4::new(0.2314, 0.3216, 0.5451, 1.0),
+        Vec4::new(0.1725, 0.4471, 0.5569, 1.0),
+        Vec4::new(0.1294, 0.5686, 0.5490, 1.0),
+        Vec4::new(0.1569, 0.6824, 0.5020, 1.0),
+        Vec4::new(0.3686, 0.7882, 0.3843, 1.0),
+        Vec4::new(0.6784, 0.8627, 0.1882, 1.0),
+        Vec4::new(0.9922, 0.9059, 0.1451, 1.0),
+    ];
+
+    /// The control points of [`Colormap::Turbo`].
+    pub const TURBO: [Vec4; 9] = [
+        Vec4::new(0.1357, 0.0914, 0.1067, 1.0),
+        Vec4::new(0.2686, 0.4148, 0.9348, 1.0),
+        Vec4::new(0.1483, 0.7405, 0.8807, 1.0),
+        Vec4::new(0.2504, 0.9528, 0.5729, 1.0),
+        Vec4::new(0.5885, 0.9819, 0.3132, 1.0),
+        Vec4::new(0.9316, 0.8139, 0.1771, 1.0),
+        Vec4::new(1.0, 0.5017, 0.1140, 1.0),
+        Vec4::new(0.7864, 0.1749, 0.0473, 1.0),
+        Vec4::new(0.5659, 0.0504, 0.0, 1.0),
+    ];
+
+    /// Get the evenly spaced control points of the colormap.
+    pub const fn table(&self) -> &'static [Vec4] {
+        match self {
+            Self::Grayscale => &Self::GRAYSCALE,
+            Self::Viridis => &Self::VIRIDIS,
+            Self::Turbo => &Self::TURBO,
+        }
+    }
+
+    /// Sample the colormap at `t`.
+    ///
+    /// `t` is clamped to `[0, 1]`, NaN is treated as 0.
+    pub fn sample(&self, t: f32) -> Vec4 {
+        let table = self.table();
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        let x = t * (table.len() - 1) as f32;
+        let i = (x.floor() as usize).min(table.len() - 2);
+
+        table[i].lerp(table[i + 1], x - i as f32)
+    }
+}
@@ -0,0 +1,182 @@
+use glam::*;
+use wgpu::util::DeviceExt;
+
+use crate::{
+    CameraPod, IndirectArgsBuffer, IndirectIndicesBuffer,
+    core::{BufferWrapper, IterGaussian, ModelTransformPod},
+};
+
+/// Sorter to cull and sort the Gaussians by depth on the CPU.
+///
+/// This is a fallback of [`Preprocessor`](crate::Preprocessor) and
+/// [`RadixSorter`](crate::RadixSorter) for adapters which cannot run the radix sort, see
+/// [`RadixSorter::is_supported`](crate::RadixSorter::is_supported). It keeps a copy of the
+/// positions and opacities of the Gaussians, and writes the back to front order into [`IndirectIndicesBuffer`]
+/// and the instance count into [`IndirectArgsBuffer`] with the queue, so the preprocessor must not
+/// run after it.
+///
+/// The Gaussians outside the near and far planes or below
+/// [`PreprocessorSettingsPod::min_opacity`](crate::PreprocessorSettingsPod::min_opacity) are
+/// culled, the rest are left to be clipped by the rasterizer, so the Gaussian transform, which
+/// only extends the screen bounds of the frustum culling in the preprocessor, does not change the
+/// rendered image. Unlike the preprocessor,
+/// [`PreprocessorSettingsPod::min_pixel_size`](crate::PreprocessorSettingsPod::min_pixel_size)
+/// and the selection culling are not supported, [`Viewer`](crate::Viewer) returns an error when
+/// they are used with the CPU sort.
+#[derive(Debug, Clone)]
+pub struct CpuSorter {
+    /// The positions of the Gaussians in model space.
+    positions: Vec<Vec3>,
+    /// The opacities of the Gaussians in `[0, 1]`.
+    opacities: Vec<f32>,
+}
+
+impl CpuSorter {
+    /// Create a new CPU sorter.
+    pub fn new(gaussians: &impl IterGaussian) -> Self {
+        let (positions, opacities) = gaussians
+            .iter_gaussian()
+            .map(|g| (g.pos, g.color.w as f32 / 255.0))
+            .unzip();
+
+        Self {
+            positions,
+            opacities,
+        }
+    }
+
+    /// Get the number of Gaussians.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Check if there are no Gaussians.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Compute the indices of the Gaussians which are not culled, from back to front.
+    ///
+    /// The Gaussians are sorted by their view space depth, and Gaussians at the same depth are
+    /// kept in the order of their indices. This matches the [`RadixSorter`](crate::RadixSorter)
+    /// with the depth based [`DepthMapping`](crate::DepthMapping) variants, except for the
+    /// Gaussians whose quantized sort keys tie, which the radix sort may order differently.
+    /// [`DepthMapping::Custom`](crate::DepthMapping::Custom) is not supported.
+    pub fn sorted_indices(
+        &self,
+        camera: &CameraPod,
+        model_transform: &ModelTransformPod,
+        min_opacity: f32,
+    ) -> Vec<u32> {
+        let model = Mat4::from_scale_rotation_translation(
+            model_transform.scale.into(),
+            model_transform.rot,
+            model_transform.pos.into(),
+        );
+        let view = camera.view * model;
+        let proj_view = camera.proj * view;
+
+        let mut keys = self
+            .positions
+            .iter()
+            .zip(&self.opacities)
+            .enumerate()
+            .filter_map(|(index, (pos, opacity))| {
+                // Same comparison as the opacity culling of the preprocessor
+                if *opacity < min_opacity {
+                    return None;
+                }
+
+                let proj_pos = proj_view * pos.extend(1.0);
+                let ndc_z = proj_pos.z / proj_pos.w;
+
+                // Same depth range as the frustum culling of the preprocessor
+                if proj_pos.w <= 0.0 || !(0.0..=1.0).contains(&ndc_z) {
+                    return None;
+                }
+
+                Some((-view.transform_point3(*pos).z, index as u32))
+            })
+            .collect::<Vec<_>>();
+
+        keys.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+        keys.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// Cull and sort the Gaussians, then write the result with the `queue`.
+    ///
+    /// See [`CpuSorter::sorted_indices`] for the order.
+    pub fn sort(
+        &self,
+        queue: &wgpu::Queue,
+        camera: &CameraPod,
+        model_transform: &ModelTransformPod,
+        min_opacity: f32,
+        indirect_args: &IndirectArgsBuffer,
+        indirect_indices: &IndirectIndicesBuffer,
+    ) {
+        let indices = self.sorted_indices(camera, model_transform, min_opacity);
+
+        if !indices.is_empty() {
+            queue.write_buffer(indirect_indices.buffer(), 0, bytemuck::cast_slice(&indices));
+        }
+
+        queue.write_buffer(
+            indirect_args.buffer(),
+            std::mem::offset_of!(wgpu::util::DrawIndirectArgs, instance_count)
+                as wgpu::BufferAddress,
+            bytemuck::bytes_of(&(indices.len() as u32)),
+        );
+    }
+
+    /// Cull and sort the Gaussians, then copy the result in the `encoder`.
+    ///
+    /// Unlike [`CpuSorter::sort`], this is ordered with the other commands in the `encoder`, so
+    /// the Gaussians can be sorted for multiple cameras in the same `encoder`, e.g. for each eye
+    /// of [`Viewer::render_stereo`](crate::Viewer::render_stereo).
+    #[allow(clippy::too_many_arguments)]
+    pub fn sort_with_encoder(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        camera: &CameraPod,
+        model_transform: &ModelTransformPod,
+        min_opacity: f32,
+        indirect_args: &IndirectArgsBuffer,
+        indirect_indices: &IndirectIndicesBuffer,
+    ) {
+        let indices = self.sorted_indices(camera, model_transform, min_opacity);
+
+        if !indices.is_empty() {
+            let staging = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("CPU Sorter Indices Staging Buffer"),
+                contents: bytemuck::cast_slice(&indices),
+                usage: wgpu::BufferUsages::COPY_SRC,
+            });
+
+            encoder.copy_buffer_to_buffer(
+                &staging,
+                0,
+                indirect_indices.buffer(),
+                0,
+                staging.size(),
+            );
+        }
+
+        let staging = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("CPU Sorter Instance Count Staging Buffer"),
+            contents: bytemuck::bytes_of(&(indices.len() as u32)),
+            usage: wgpu::BufferUsages::COPY_SRC,
+        });
+
+        encoder.copy_buffer_to_buffer(
+            &staging,
+            0,
+            indirect_args.buffer(),
+            std::mem::offset_of!(wgpu::util::DrawIndirectArgs, instance_count)
+                as wgpu::BufferAddress,
+            staging.size(),
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use crate::CustomShaderError;
+
+/// Validate a custom WGSL shader against the contract of a pipeline.
+///
+/// The shader must have the `entry_points` with their stages and the pipeline-overridable
+/// constants `overrides`. Every resource binding of the shader must be in `bind_group_layouts`,
+/// indexed by the group, with a compatible binding type. Entries of the layouts which are not used
+/// by the shader are allowed.
+pub(crate) fn validate_wgsl(
+    wgsl: &str,
+    entry_points: &[(&'static str, wgpu::ShaderStages)],
+    overrides: &[&'static str],
+    bind_group_layouts: &[&wgpu::BindGroupLayoutDescriptor],
+) -> Result<(), CustomShaderError> {
+    let module = naga::front::wgsl::parse_str(wgsl)
+        .map_err(|e| CustomShaderError::Parse(e.emit_to_string(wgsl)))?;
+
+    for (name, stage) in entry_points.iter().copied() {
+        let found = module
+            .entry_points
+            .iter()
+            .any(|ep| ep.name == name && shader_stage(ep.stage) == stage);
+
+        if !found {
+            return Err(CustomShaderError::MissingEntryPoint { name, stage });
+        }
+    }
+
+    for name in overrides.iter().copied() {
+        let found = module
+            .overrides
+            .iter()
+            .any(|(_, o)| o.name.as_deref() == Some(name));
+
+        if !found {
+            return Err(CustomShaderError::MissingOverride(name));
+        }
+    }
+
+    for (_, var) in module.global_variables.iter() {
+        let Some(naga::ResourceBinding { group, binding }) = var.binding else {
+            continue;
+        };
+
+        let entry = bind_group_layouts
+            .get(group as usize)
+            .and_then(|layout| layout.entries.iter().find(|e| e.binding == binding))
+            .ok_or(CustomShaderError::UnknownBinding { group, binding })?;
+
+        let compatible = match (entry.ty, var.space) {
+            (
+                wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    ..
+                },
+                naga::AddressSpace::Uniform,
+            ) => true,
+            (
+                wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only },
+                    ..
+                },
+                naga::AddressSpace::Storage { access },
+            ) => !read_only || !access.contains(naga::StorageAccess::STORE),
+            (wgpu::BindingType::Buffer { .. }, _) => false,
+            (_, space) => space == naga::AddressSpace::Handle,
+        };
+
+        if !compatible {
+            return Err(CustomShaderError::BindingTypeMismatch {
+                group,
+                binding,
+                expected: entry.ty,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert the shader stage of naga to wgpu.
+fn shader_stage(stage: naga::ShaderStage) -> wgpu::ShaderStages {
+    match stage {
+        naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
+        naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
+        naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
+        naga::ShaderStage::Task => wgpu::ShaderStages::TASK,
+        naga::ShaderStage::Mesh => wgpu::ShaderStages::MESH,
+    }
+}
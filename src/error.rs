@@ -14,6 +14,8 @@ pub enum RendererCreateError {
     ModelSizeExceedsDeviceLimit { model_size: u64, device_limit: u32 },
     #[error("{0}")]
     WeslCompile(#[from] wesl::Error),
+    #[error("{0}")]
+    CustomShader(#[from] CustomShaderError),
 }
 
 /// The error type for [`Preprocessor::new`](crate::Preprocessor::new).
@@ -29,7 +31,38 @@ pub enum PreprocessorCreateError {
     #[error("{0}")]
     ComputeBundleBuild(#[from] core::ComputeBundleBuildError),
     #[error("{0}")]
+    ComputeBundleCreate(#[from] core::ComputeBundleCreateError),
+    #[error("{0}")]
     WeslCompile(#[from] wesl::Error),
+    #[error("{0}")]
+    CustomShader(#[from] CustomShaderError),
+}
+
+/// The error type for validating the custom shader of
+/// [`Renderer::new_with_wgsl`](crate::Renderer::new_with_wgsl) and
+/// [`Preprocessor::new_with_wgsl`](crate::Preprocessor::new_with_wgsl).
+#[derive(Debug, Error)]
+pub enum CustomShaderError {
+    #[error("failed to parse custom shader: {0}")]
+    Parse(String),
+    #[error("custom shader is missing {stage:?} entry point \"{name}\"")]
+    MissingEntryPoint {
+        name: &'static str,
+        stage: wgpu::ShaderStages,
+    },
+    #[error("custom shader is missing override \"{0}\"")]
+    MissingOverride(&'static str),
+    #[error("custom shader binding @group({group}) @binding({binding}) is not in the layouts")]
+    UnknownBinding { group: u32, binding: u32 },
+    #[error(
+        "custom shader binding @group({group}) @binding({binding}) \
+        does not match the layout entry type {expected:?}"
+    )]
+    BindingTypeMismatch {
+        group: u32,
+        binding: u32,
+        expected: wgpu::BindingType,
+    },
 }
 
 /// The error type for [`Viewer::new`](crate::Viewer::new).
@@ -39,6 +72,95 @@ pub enum ViewerCreateError {
     RendererCreate(#[from] RendererCreateError),
     #[error("{0}")]
     PreprocessorCreate(#[from] PreprocessorCreateError),
+    #[error(
+        "device does not support STORAGE | INDIRECT buffers written by compute shaders, \
+        max storage buffers per shader stage is {max_storage_buffers_per_shader_stage} \
+        and max compute invocations per workgroup is {max_compute_invocations_per_workgroup}"
+    )]
+    UnsupportedIndirectUsage {
+        max_storage_buffers_per_shader_stage: u32,
+        max_compute_invocations_per_workgroup: u32,
+    },
+    #[error("sort keys are required by the custom depth mapping")]
+    MissingSortKeys,
+    #[error("depth mapping {0:?} is not supported")]
+    UnsupportedDepthMapping(crate::DepthMapping),
+    #[cfg(feature = "viewer-selection")]
+    #[error("selection is not supported with CPU sort")]
+    UnsupportedCpuSortSelection,
+}
+
+/// The error type for [`Viewer::update_preprocessor_settings`](crate::Viewer::update_preprocessor_settings).
+#[derive(Debug, Error)]
+pub enum ViewerPreprocessorSettingsError {
+    #[error("minimum pixel size {0} is not supported with CPU sort")]
+    UnsupportedMinPixelSize(f32),
+}
+
+/// The error type for [`SortKeyBuffer::update`](crate::SortKeyBuffer::update).
+#[derive(Debug, Error)]
+pub enum SortKeyBufferUpdateError {
+    #[error("sort keys count mismatch: {count} != {expected_count}")]
+    CountMismatch { count: usize, expected_count: usize },
+}
+
+/// The error type for [`Viewer::render_supersampled`](crate::Viewer::render_supersampled).
+#[derive(Debug, Error)]
+pub enum ViewerRenderSupersampledError {
+    #[error("texture format {0:?} is not supported for supersampling")]
+    UnsupportedTextureFormat(wgpu::TextureFormat),
+    #[error("{0}")]
+    DownloadBuffer(#[from] core::DownloadBufferError),
+}
+
+/// The error type for [`Viewer::download_visible_bounds`](crate::Viewer::download_visible_bounds).
+#[derive(Debug, Error)]
+pub enum ViewerDownloadError {
+    #[error("visible bounds are not enabled by ViewerCreateOptions::visible_bounds")]
+    VisibleBoundsDisabled,
+    #[error("{0}")]
+    DownloadBuffer(#[from] core::DownloadBufferError),
+}
+
+/// The error type for [`download_gaussian`](crate::download_gaussian).
+#[derive(Debug, Error)]
+pub enum DownloadGaussianError {
+    #[error("gaussians buffer usage {0:?} does not contain COPY_SRC")]
+    MissingCopySrcUsage(wgpu::BufferUsages),
+    #[error("index {index} is out of bounds of the gaussians buffer with length {len}")]
+    IndexOutOfBounds { index: usize, len: usize },
+    #[error("{0}")]
+    DownloadBuffer(#[from] core::DownloadBufferError),
+}
+
+/// The error type for [`selection::is_selected`](crate::selection::is_selected).
+#[cfg(feature = "selection")]
+#[derive(Debug, Error)]
+pub enum SelectionIsSelectedError {
+    #[error("index {index} is out of bounds of the selection buffer with length {len}")]
+    IndexOutOfBounds { index: u32, len: u64 },
+    #[error("{0}")]
+    DownloadBuffer(#[from] core::DownloadBufferError),
+}
+
+/// The error type for [`Viewer::extract_selected`](crate::Viewer::extract_selected).
+#[cfg(feature = "viewer-selection")]
+#[derive(Debug, Error)]
+pub enum ViewerExtractSelectedError {
+    #[error(
+        "gaussians are not retained and gaussians buffer usage {0:?} does not contain COPY_SRC"
+    )]
+    MissingCopySrcUsage(wgpu::BufferUsages),
+    #[error("{0}")]
+    DownloadBuffer(#[from] core::DownloadBufferError),
+}
+
+/// The error type for [`Viewer::set_active_selection_layer`](crate::Viewer::set_active_selection_layer).
+#[cfg(feature = "viewer-selection")]
+#[derive(Debug, Error)]
+pub enum SelectionLayerError {
+    #[error("layer {layer} is out of bounds of the selection layers with count {layer_count}")]
+    LayerOutOfBounds { layer: u32, layer_count: u32 },
 }
 
 /// The error type for accessing model in [`MultiModelViewer`](crate::MultiModelViewer).
@@ -48,3 +170,25 @@ pub enum MultiModelViewerAccessError {
     #[error("model with the given key does not exist")]
     ModelNotFound,
 }
+
+/// The error type for appending to a model in [`MultiModelViewer`](crate::MultiModelViewer).
+#[cfg(feature = "multi-model")]
+#[derive(Debug, Error)]
+pub enum MultiModelViewerAppendError {
+    #[error("model with the given key does not exist")]
+    ModelNotFound,
+    #[error("gaussians buffer usage {0:?} does not contain COPY_SRC")]
+    MissingCopySrcUsage(wgpu::BufferUsages),
+}
+
+/// The error type for downloading from a model in [`MultiModelViewer`](crate::MultiModelViewer).
+#[cfg(feature = "multi-model")]
+#[derive(Debug, Error)]
+pub enum MultiModelViewerDownloadError {
+    #[error("model with the given key does not exist")]
+    ModelNotFound,
+    #[error("visible bounds are not enabled by MultiModelViewerCreateOptions::visible_bounds")]
+    VisibleBoundsDisabled,
+    #[error("{0}")]
+    DownloadBuffer(#[from] core::DownloadBufferError),
+}
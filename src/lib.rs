@@ -1,12 +1,20 @@
 #![doc = include_str!("../README.md")]
 
+mod bounding_sphere;
 mod buffer;
 mod camera;
+mod colormap;
+mod cpu_sorter;
+mod custom_shader;
 mod error;
+mod normalize;
 mod preprocessor;
+pub mod quick;
 mod radix_sorter;
 mod renderer;
+mod sh_rotation;
 pub mod shader;
+mod validate;
 mod wesl_utils;
 
 #[cfg(feature = "multi-model")]
@@ -16,21 +24,28 @@ mod multi_model;
 pub mod selection;
 
 use glam::*;
+use wgpu::util::DeviceExt;
 use wgpu_3dgs_core::{
     BufferWrapper, GaussianDisplayMode, GaussianMaxStdDev, GaussianPod, GaussianShDegree,
-    GaussianTransformBuffer, GaussianTransformPod, GaussiansBuffer, IterGaussian,
+    GaussianTransformBuffer, GaussianTransformPod, Gaussians, GaussiansBuffer, IterGaussian,
     ModelTransformBuffer, ModelTransformPod,
 };
 
 #[cfg(feature = "viewer-selection")]
 use wgpu_3dgs_editor::SelectionBuffer;
 
+pub use bounding_sphere::*;
 pub use buffer::*;
 pub use camera::*;
+pub use colormap::*;
+pub use cpu_sorter::*;
 pub use error::*;
+pub use normalize::*;
 pub use preprocessor::*;
 pub use radix_sorter::*;
 pub use renderer::*;
+pub use sh_rotation::*;
+pub use validate::*;
 
 #[cfg(feature = "multi-model")]
 pub use multi_model::*;
@@ -40,6 +55,17 @@ pub use wgpu_3dgs_core as core;
 #[cfg(feature = "editor")]
 pub use wgpu_3dgs_editor as editor;
 
+/// The forward and up directions of the cube map faces in the order of +X, -X, +Y, -Y, +Z, -Z,
+/// before the projection is flipped vertically.
+const CUBEMAP_FACES: [(Vec3, Vec3); 6] = [
+    (Vec3::X, Vec3::NEG_Y),
+    (Vec3::NEG_X, Vec3::NEG_Y),
+    (Vec3::Y, Vec3::Z),
+    (Vec3::NEG_Y, Vec3::NEG_Z),
+    (Vec3::Z, Vec3::NEG_Y),
+    (Vec3::NEG_Z, Vec3::NEG_Y),
+];
+
 /// The default viewer [`GaussianPod`] type.
 pub type DefaultGaussianPod = core::GaussianPodWithShSingleCov3dSingleConfigs;
 
@@ -55,10 +81,16 @@ pub type DefaultGaussianPod = core::GaussianPodWithShSingleCov3dSingleConfigs;
 ///     - [`RadixSortIndirectArgsBuffer`]
 ///     - [`IndirectIndicesBuffer`]
 ///     - [`GaussiansDepthBuffer`]
+///     - [`PreprocessorSettingsBuffer`]
+///     - [`RendererSettingsBuffer`]
+///     - [`VisibleBoundsBuffer`] (optional)
+///     - [`GaussiansLinearDepthBuffer`] (optional)
+///     - [`Conic2dBuffer`] (optional)
 /// - Operations
 ///     - [`Preprocessor`]
-///     - [`RadixSorter`]
+///     - [`RadixSorter`] (unless [`ViewerCreateOptions::cpu_sort`])
 ///     - [`Renderer`]
+///     - [`CpuSorter`] (optional)
 ///
 /// If you wish to manage these buffers yourself, you do not need to use this struct.
 #[derive(Debug)]
@@ -71,14 +103,44 @@ pub struct Viewer<G: GaussianPod = DefaultGaussianPod> {
     pub radix_sort_indirect_args_buffer: RadixSortIndirectArgsBuffer,
     pub indirect_indices_buffer: IndirectIndicesBuffer,
     pub gaussians_depth_buffer: GaussiansDepthBuffer,
+    pub preprocessor_settings_buffer: PreprocessorSettingsBuffer,
+    pub renderer_settings_buffer: RendererSettingsBuffer,
+    pub visible_bounds_buffer: Option<VisibleBoundsBuffer>,
     #[cfg(feature = "viewer-selection")]
     pub selection_buffer: SelectionBuffer,
     #[cfg(feature = "viewer-selection")]
     pub invert_selection_buffer: selection::PreprocessorInvertSelectionBuffer,
+    #[cfg(feature = "viewer-selection")]
+    pub selection_history: selection::SelectionHistory,
+    #[cfg(feature = "viewer-selection")]
+    pub selection_layers_buffer: selection::SelectionLayersBuffer,
+    #[cfg(feature = "viewer-selection")]
+    pub selection_highlight_buffer: selection::SelectionHighlightBuffer,
+    pub gaussians_linear_depth_buffer: Option<GaussiansLinearDepthBuffer>,
+    pub conic2d_buffer: Option<Conic2dBuffer>,
 
     pub preprocessor: Preprocessor<G>,
-    pub radix_sorter: RadixSorter,
+    pub radix_sorter: Option<RadixSorter>,
     pub renderer: Renderer<G>,
+    pub cpu_sorter: Option<CpuSorter>,
+
+    /// Whether the Gaussians are sorted by depth before rendering.
+    sorting_enabled: bool,
+    /// Whether the last preprocessed and sorted order is reused instead of recomputed.
+    sort_frozen: bool,
+    /// The model transform last written by the viewer.
+    model_transform: ModelTransformPod,
+    /// The camera last written by the viewer, only tracked for [`Viewer::cpu_sorter`].
+    camera: Option<CameraPod>,
+    /// The minimum opacity last written by the viewer, only used by [`Viewer::cpu_sorter`].
+    min_opacity: f32,
+    /// The retained source Gaussians.
+    gaussians: Option<Gaussians>,
+    /// The prefix of the labels, see [`ViewerCreateOptions::label_prefix`].
+    label_prefix: String,
+    /// The index of the active selection layer.
+    #[cfg(feature = "viewer-selection")]
+    active_selection_layer: u32,
 }
 
 impl<G: GaussianPod> Viewer<G> {
@@ -103,8 +165,29 @@ impl<G: GaussianPod> Viewer<G> {
         gaussians: &impl IterGaussian,
         options: ViewerCreateOptions,
     ) -> Result<Self, ViewerCreateError> {
+        check_indirect_usage(device)?;
+
+        let cpu_sort = options.cpu_sort || !RadixSorter::is_supported(device);
+
+        if cpu_sort {
+            if options.depth_mapping == DepthMapping::Custom {
+                return Err(ViewerCreateError::UnsupportedDepthMapping(
+                    options.depth_mapping,
+                ));
+            }
+
+            #[cfg(feature = "viewer-selection")]
+            return Err(ViewerCreateError::UnsupportedCpuSortSelection);
+        }
+
+        if options.depth_mapping == DepthMapping::Custom && options.sort_keys.is_none() {
+            return Err(ViewerCreateError::MissingSortKeys);
+        }
+
+        let label = options.label_prefix.as_deref().unwrap_or_default();
+
         log::debug!("Creating camera buffer");
-        let camera_buffer = CameraBuffer::new(device);
+        let camera_buffer = CameraBuffer::new_with_label(device, label);
 
         log::debug!("Creating model transform buffer");
         let model_transform_buffer = ModelTransformBuffer::new(device);
@@ -117,35 +200,83 @@ impl<G: GaussianPod> Viewer<G> {
             GaussiansBuffer::new_with_usage(device, gaussians, options.gaussians_buffer_usage);
 
         log::debug!("Creating indirect args buffer");
-        let indirect_args_buffer = IndirectArgsBuffer::new(device);
+        let indirect_args_buffer =
+            IndirectArgsBuffer::new_with_label_and_quad_mode(device, label, options.quad_mode);
 
         log::debug!("Creating radix sort indirect args buffer");
-        let radix_sort_indirect_args_buffer = RadixSortIndirectArgsBuffer::new(device);
+        let radix_sort_indirect_args_buffer =
+            RadixSortIndirectArgsBuffer::new_with_label(device, label);
 
         // Assuming it is cheap to call `iter_gaussian`.
         let len = gaussians.iter_gaussian().len() as u32;
 
         log::debug!("Creating indirect indices buffer");
-        let indirect_indices_buffer = IndirectIndicesBuffer::new(device, len);
+        let indirect_indices_buffer = IndirectIndicesBuffer::new_with_label(device, label, len);
 
         log::debug!("Creating gaussians depth buffer");
-        let gaussians_depth_buffer = GaussiansDepthBuffer::new(device, len);
+        let gaussians_depth_buffer = GaussiansDepthBuffer::new_with_label(device, label, len);
+
+        log::debug!("Creating preprocessor settings buffer");
+        let preprocessor_settings_buffer =
+            PreprocessorSettingsBuffer::new_with_label(device, label);
+
+        log::debug!("Creating renderer settings buffer");
+        let renderer_settings_buffer = RendererSettingsBuffer::new_with_label(device, label);
+
+        let visible_bounds_buffer = options.visible_bounds.then(|| {
+            log::debug!("Creating visible bounds buffer");
+            VisibleBoundsBuffer::new_with_label(device, label)
+        });
 
         #[cfg(feature = "viewer-selection")]
         let selection_buffer = {
             log::debug!("Creating selection buffer");
-            SelectionBuffer::new(device, len)
+            SelectionBuffer::new_with_label(device, label, len)
         };
 
         #[cfg(feature = "viewer-selection")]
         let invert_selection_buffer = {
             log::debug!("Creating invert selection buffer");
-            selection::PreprocessorInvertSelectionBuffer::new(device)
+            selection::PreprocessorInvertSelectionBuffer::new_with_label(device, label)
         };
 
+        #[cfg(feature = "viewer-selection")]
+        let selection_layers_buffer = {
+            log::debug!("Creating selection layers buffer");
+            selection::SelectionLayersBuffer::new_with_label(
+                device,
+                label,
+                len,
+                options.selection_layer_count,
+            )
+        };
+
+        #[cfg(feature = "viewer-selection")]
+        let selection_highlight_buffer = {
+            log::debug!("Creating selection highlight buffer");
+            selection::SelectionHighlightBuffer::new_with_label(device, label)
+        };
+
+        let gaussians_linear_depth_buffer = options.linear_depth.then(|| {
+            log::debug!("Creating gaussians linear depth buffer");
+            GaussiansLinearDepthBuffer::new_with_label(device, label, len)
+        });
+
+        let conic2d_buffer = options.conic2d.then(|| {
+            log::debug!("Creating conic 2D buffer");
+            Conic2dBuffer::new_with_label(device, label, len)
+        });
+
         log::debug!("Creating preprocessor");
-        let preprocessor = Preprocessor::new(
+        let mut preprocessor = Preprocessor::new(
             device,
+            PreprocessorCreateOptions {
+                depth_mapping: options.depth_mapping,
+                visible_bounds: options.visible_bounds,
+                linear_depth: options.linear_depth,
+                conic2d: options.conic2d,
+                label_prefix: options.label_prefix.clone(),
+            },
             &camera_buffer,
             &model_transform_buffer,
             &gaussian_transform_buffer,
@@ -154,26 +285,62 @@ impl<G: GaussianPod> Viewer<G> {
             &radix_sort_indirect_args_buffer,
             &indirect_indices_buffer,
             &gaussians_depth_buffer,
+            &preprocessor_settings_buffer,
+            visible_bounds_buffer.as_ref(),
+            gaussians_linear_depth_buffer.as_ref(),
+            conic2d_buffer.as_ref(),
+            options.conic2d.then_some(&renderer_settings_buffer),
             #[cfg(feature = "viewer-selection")]
             &selection_buffer,
             #[cfg(feature = "viewer-selection")]
             &invert_selection_buffer,
         )?;
 
-        log::debug!("Creating radix sorter");
-        let radix_sorter =
-            RadixSorter::new(device, &gaussians_depth_buffer, &indirect_indices_buffer);
+        if let Some(sort_keys) = &options.sort_keys {
+            preprocessor.set_sort_keys(device, sort_keys);
+        }
+
+        let (radix_sorter, cpu_sorter) = if cpu_sort {
+            log::debug!("Creating CPU sorter");
+            (None, Some(CpuSorter::new(gaussians)))
+        } else {
+            log::debug!("Creating radix sorter");
+            let radix_sorter = RadixSorter::new_with_options(
+                device,
+                RadixSorterCreateOptions {
+                    pipeline_cache: options.pipeline_cache.clone(),
+                    label_prefix: options.label_prefix.clone(),
+                },
+                &gaussians_depth_buffer,
+                &indirect_indices_buffer,
+            );
+
+            (Some(radix_sorter), None)
+        };
 
         log::debug!("Creating renderer");
         let renderer = Renderer::new(
             device,
             texture_format,
-            options.depth_stencil,
+            RendererCreateOptions {
+                depth_stencil: options.depth_stencil,
+                primitive: options.primitive,
+                quad_mode: options.quad_mode,
+                pipeline_cache: options.pipeline_cache,
+                label_prefix: options.label_prefix.clone(),
+            },
             &camera_buffer,
             &model_transform_buffer,
             &gaussian_transform_buffer,
             &gaussians_buffer,
             &indirect_indices_buffer,
+            &renderer_settings_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &selection_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &selection_layers_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &selection_highlight_buffer,
         )?;
 
         log::info!("Viewer created");
@@ -187,30 +354,84 @@ impl<G: GaussianPod> Viewer<G> {
             radix_sort_indirect_args_buffer,
             indirect_indices_buffer,
             gaussians_depth_buffer,
+            preprocessor_settings_buffer,
+            renderer_settings_buffer,
+            visible_bounds_buffer,
             #[cfg(feature = "viewer-selection")]
             selection_buffer,
             #[cfg(feature = "viewer-selection")]
             invert_selection_buffer,
+            #[cfg(feature = "viewer-selection")]
+            selection_history: selection::SelectionHistory::default(),
+            #[cfg(feature = "viewer-selection")]
+            selection_layers_buffer,
+            #[cfg(feature = "viewer-selection")]
+            selection_highlight_buffer,
+            gaussians_linear_depth_buffer,
+            conic2d_buffer,
 
             preprocessor,
             radix_sorter,
             renderer,
+            cpu_sorter,
+
+            sorting_enabled: true,
+            sort_frozen: false,
+            model_transform: ModelTransformPod::default(),
+            camera: None,
+            min_opacity: 0.0,
+            gaussians: None,
+            label_prefix: label.to_string(),
+            #[cfg(feature = "viewer-selection")]
+            active_selection_layer: 0,
         })
     }
 
+    /// Create a new viewer that retains the source [`Gaussians`].
+    ///
+    /// The retained Gaussians are indexed the same as [`Viewer::gaussians_buffer`], and can be
+    /// accessed by [`Viewer::gaussians`] for CPU side analysis, e.g. resolving picked or selected
+    /// indices to the full Gaussian data.
+    pub fn new_retaining_gaussians(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        gaussians: Gaussians,
+        options: ViewerCreateOptions,
+    ) -> Result<Self, ViewerCreateError> {
+        let mut viewer = Self::new_with_options(device, texture_format, &gaussians, options)?;
+        viewer.gaussians = Some(gaussians);
+        Ok(viewer)
+    }
+
+    /// Get the retained source [`Gaussians`].
+    ///
+    /// This is [`None`] unless the viewer is created by [`Viewer::new_retaining_gaussians`].
+    pub fn gaussians(&self) -> Option<&Gaussians> {
+        self.gaussians.as_ref()
+    }
+
     /// Update the camera.
+    ///
+    /// With [`Viewer::cpu_sorter`], this also culls and sorts the Gaussians.
     pub fn update_camera(
         &mut self,
         queue: &wgpu::Queue,
         camera: &impl CameraTrait,
         texture_size: UVec2,
     ) {
-        self.camera_buffer.update(queue, camera, texture_size);
+        self.update_camera_with_pod(queue, &CameraPod::new(camera, texture_size));
     }
 
     /// Update the camera with [`CameraPod`].
+    ///
+    /// With [`Viewer::cpu_sorter`], this also culls and sorts the Gaussians.
     pub fn update_camera_with_pod(&mut self, queue: &wgpu::Queue, pod: &CameraPod) {
         self.camera_buffer.update_with_pod(queue, pod);
+
+        if self.cpu_sorter.is_some() {
+            self.camera = Some(*pod);
+            self.sort_cpu(queue);
+        }
     }
 
     /// Update the model transform.
@@ -221,16 +442,37 @@ impl<G: GaussianPod> Viewer<G> {
         rot: Quat,
         scale: Vec3,
     ) {
-        self.model_transform_buffer.update(queue, pos, rot, scale);
+        self.update_model_transform_with_pod(queue, &ModelTransformPod::new(pos, rot, scale));
     }
 
     /// Update the model transform with [`ModelTransformPod`].
+    ///
+    /// With [`Viewer::cpu_sorter`], this also culls and sorts the Gaussians.
     pub fn update_model_transform_with_pod(
         &mut self,
         queue: &wgpu::Queue,
         pod: &ModelTransformPod,
     ) {
+        self.model_transform = *pod;
         self.model_transform_buffer.update_with_pod(queue, pod);
+        self.sort_cpu(queue);
+    }
+
+    /// Get the model matrix of the current model transform.
+    ///
+    /// This is the same transform applied in the shaders, so the world position of the Gaussian at
+    /// `index`, e.g. resolved from [`Viewer::gaussians`], is
+    /// `model_matrix().transform_point3(gaussian.pos)`.
+    ///
+    /// Only the model transforms set by [`Viewer::update_model_transform`] and
+    /// [`Viewer::update_model_transform_with_pod`] are tracked, writing to
+    /// [`Viewer::model_transform_buffer`] directly is not reflected.
+    pub fn model_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(
+            self.model_transform.scale.into(),
+            self.model_transform.rot,
+            self.model_transform.pos.into(),
+        )
     }
 
     /// Update the Gaussian transform.
@@ -262,32 +504,1072 @@ impl<G: GaussianPod> Viewer<G> {
         self.gaussian_transform_buffer.update_with_pod(queue, pod);
     }
 
+    /// Update the preprocessor settings.
+    ///
+    /// This keeps [`PreprocessorSettingsPod::skip_depth`] set by
+    /// [`Viewer::set_sorting_enabled`].
+    ///
+    /// With [`Viewer::cpu_sorter`], this also culls and sorts the Gaussians, and returns an error
+    /// if [`PreprocessorSettings::min_pixel_size`] is set.
+    pub fn update_preprocessor_settings(
+        &mut self,
+        queue: &wgpu::Queue,
+        settings: &PreprocessorSettings,
+    ) -> Result<(), ViewerPreprocessorSettingsError> {
+        self.update_preprocessor_settings_with_pod(
+            queue,
+            &PreprocessorSettingsPod {
+                skip_depth: u32::from(!self.sorting_enabled),
+                ..PreprocessorSettingsPod::new(settings)
+            },
+        )
+    }
+
+    /// Set the minimum opacity in `[0, 1]` of the Gaussians to be rendered.
+    ///
+    /// Gaussians with a lower opacity are culled before sorting. This only updates
+    /// [`PreprocessorSettings::min_opacity`] and keeps the other preprocessor settings.
+    ///
+    /// With [`Viewer::cpu_sorter`], this also culls and sorts the Gaussians.
+    pub fn set_min_opacity(&mut self, queue: &wgpu::Queue, min_opacity: f32) {
+        self.min_opacity = min_opacity;
+        self.preprocessor_settings_buffer
+            .update_min_opacity(queue, min_opacity);
+        self.sort_cpu(queue);
+    }
+
+    /// Update the preprocessor settings with [`PreprocessorSettingsPod`].
+    ///
+    /// Unlike [`Viewer::update_preprocessor_settings`], this also overwrites
+    /// [`PreprocessorSettingsPod::skip_depth`], which should be kept consistent with
+    /// [`Viewer::sorting_enabled`].
+    ///
+    /// With [`Viewer::cpu_sorter`], this also culls and sorts the Gaussians, and returns an error
+    /// if [`PreprocessorSettingsPod::min_pixel_size`] is set.
+    pub fn update_preprocessor_settings_with_pod(
+        &mut self,
+        queue: &wgpu::Queue,
+        pod: &PreprocessorSettingsPod,
+    ) -> Result<(), ViewerPreprocessorSettingsError> {
+        self.check_cpu_sort_min_pixel_size(pod.min_pixel_size)?;
+
+        self.min_opacity = pod.min_opacity;
+        self.preprocessor_settings_buffer
+            .update_with_pod(queue, pod);
+        self.sort_cpu(queue);
+
+        Ok(())
+    }
+
+    /// Update the renderer settings.
+    pub fn update_renderer_settings(&mut self, queue: &wgpu::Queue, settings: &RendererSettings) {
+        self.renderer_settings_buffer.update(queue, settings);
+    }
+
+    /// Update the renderer settings with [`RendererSettingsPod`].
+    pub fn update_renderer_settings_with_pod(
+        &mut self,
+        queue: &wgpu::Queue,
+        pod: &RendererSettingsPod,
+    ) {
+        self.renderer_settings_buffer.update_with_pod(queue, pod);
+    }
+
+    /// Set the minimum size in pixels of the projected Gaussians to be rendered, 0 to disable.
+    ///
+    /// Gaussians with a smaller footprint are culled before sorting. This only updates
+    /// [`PreprocessorSettings::min_pixel_size`] and keeps the other preprocessor settings.
+    ///
+    /// With [`Viewer::cpu_sorter`], this returns an error unless `min_pixel_size` is 0.
+    pub fn set_min_pixel_size(
+        &self,
+        queue: &wgpu::Queue,
+        min_pixel_size: f32,
+    ) -> Result<(), ViewerPreprocessorSettingsError> {
+        self.check_cpu_sort_min_pixel_size(min_pixel_size)?;
+
+        self.preprocessor_settings_buffer
+            .update_min_pixel_size(queue, min_pixel_size);
+
+        Ok(())
+    }
+
+    /// Set whether to apply ordered dithering to the colors decoded from Norm8 SH.
+    ///
+    /// This only updates [`RendererSettings::sh_dither`] and keeps the other renderer settings.
+    pub fn set_sh_dither(&self, queue: &wgpu::Queue, sh_dither: bool) {
+        self.renderer_settings_buffer
+            .update_sh_dither(queue, sh_dither);
+    }
+
+    /// Set the clamping of the SH evaluated colors.
+    ///
+    /// This only updates [`RendererSettings::sh_clamp`] and keeps the other renderer settings.
+    pub fn set_sh_clamp(&self, queue: &wgpu::Queue, sh_clamp: ShClamp) {
+        self.renderer_settings_buffer
+            .update_sh_clamp(queue, sh_clamp);
+    }
+
+    /// Set the distance fade of the Gaussians.
+    ///
+    /// The opacity of the Gaussians is faded out by a smoothstep over the view space distance from
+    /// `start` to `end`, e.g. to hide distant floaters like a far fog without deleting them. The
+    /// fade is disabled if `end` is infinity or not greater than `start`, which is the default.
+    ///
+    /// This only updates [`RendererSettings::distance_fade`] and keeps the other renderer settings.
+    pub fn set_distance_fade(&self, queue: &wgpu::Queue, start: f32, end: f32) {
+        self.renderer_settings_buffer
+            .update_distance_fade(queue, start, end);
+    }
+
+    /// Snapshot [`Viewer::selection_buffer`] into [`Viewer::selection_history`].
+    ///
+    /// This should be called before each modification of the selection to be undone.
+    #[cfg(feature = "viewer-selection")]
+    pub fn push_selection_undo(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        self.selection_history
+            .push(device, encoder, &self.selection_buffer);
+    }
+
+    /// Restore [`Viewer::selection_buffer`] to the previous snapshot.
+    ///
+    /// Returns `false` if there is nothing to undo.
+    #[cfg(feature = "viewer-selection")]
+    pub fn undo_selection(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> bool {
+        self.selection_history
+            .undo(device, encoder, &self.selection_buffer)
+    }
+
+    /// Restore [`Viewer::selection_buffer`] to the snapshot before the last undo.
+    ///
+    /// Returns `false` if there is nothing to redo.
+    #[cfg(feature = "viewer-selection")]
+    pub fn redo_selection(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> bool {
+        self.selection_history
+            .redo(device, encoder, &self.selection_buffer)
+    }
+
+    /// Deselect all Gaussians in [`Viewer::selection_buffer`].
+    ///
+    /// The buffer is cleared in the `encoder`, so it is ordered with the other commands of the
+    /// frame. Call [`Viewer::push_selection_undo`] before to make it undoable.
+    #[cfg(feature = "viewer-selection")]
+    pub fn clear_selection(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.clear_buffer(self.selection_buffer.buffer(), 0, None);
+    }
+
+    /// Extract the selected Gaussians in [`Viewer::selection_buffer`] into a new [`Gaussians`].
+    ///
+    /// The Gaussians are taken from [`Viewer::gaussians`] if retained, in which case the source
+    /// format is kept. Otherwise they are downloaded from [`Viewer::gaussians_buffer`], which then
+    /// must be created with [`wgpu::BufferUsages::COPY_SRC`], and returned as
+    /// [`Gaussians::Internal`].
+    ///
+    /// An empty selection returns an empty [`Gaussians`].
+    #[cfg(feature = "viewer-selection")]
+    pub async fn extract_selected(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Gaussians, ViewerExtractSelectedError> {
+        let usage = self.gaussians_buffer.buffer().usage();
+        if self.gaussians.is_none() && !usage.contains(wgpu::BufferUsages::COPY_SRC) {
+            return Err(ViewerExtractSelectedError::MissingCopySrcUsage(usage));
+        }
+
+        let selection = self.selection_buffer.download::<u32>(device, queue).await?;
+        let is_selected = |index: usize| selection[index / 32] & (1 << (index % 32)) != 0;
+
+        Ok(match &self.gaussians {
+            Some(gaussians) => Gaussians::from_gaussians_iter(
+                gaussians
+                    .iter_gaussian()
+                    .enumerate()
+                    .filter(|(i, _)| is_selected(*i))
+                    .map(|(_, gaussian)| gaussian),
+                gaussians.source(),
+            ),
+            None => Gaussians::Internal(
+                self.gaussians_buffer
+                    .download_gaussians(device, queue)
+                    .await?
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| is_selected(*i))
+                    .map(|(_, gaussian)| gaussian)
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Recreate [`Viewer::renderer`] for a new render target `texture_format`.
+    ///
+    /// This is useful when the surface format changes, e.g. when the window is moved to a monitor
+    /// with a different color format. Only the render pipeline and its bind group are recreated,
+    /// the buffers (including the Gaussians), the [`Preprocessor`], and the [`RadixSorter`] are
+    /// kept. The [`Renderer::create_options`], the HDR render target format, and the custom shader
+    /// of the current renderer are preserved.
+    pub fn set_texture_format(
+        &mut self,
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+    ) -> Result<(), RendererCreateError> {
+        let renderer_options = self.renderer.create_options();
+
+        log::debug!("Recreating renderer");
+        self.renderer = match (
+            self.renderer.hdr_texture_format(),
+            self.renderer.custom_wgsl(),
+        ) {
+            (_, Some(wgsl)) => Renderer::new_with_wgsl(
+                device,
+                texture_format,
+                renderer_options,
+                wgsl,
+                &self.camera_buffer,
+                &self.model_transform_buffer,
+                &self.gaussian_transform_buffer,
+                &self.gaussians_buffer,
+                &self.indirect_indices_buffer,
+                &self.renderer_settings_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_layers_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_highlight_buffer,
+            )?,
+            (Some(hdr_texture_format), None) => Renderer::new_with_hdr_target(
+                device,
+                texture_format,
+                hdr_texture_format,
+                renderer_options,
+                &self.camera_buffer,
+                &self.model_transform_buffer,
+                &self.gaussian_transform_buffer,
+                &self.gaussians_buffer,
+                &self.indirect_indices_buffer,
+                &self.renderer_settings_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_layers_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_highlight_buffer,
+            )?,
+            (None, None) => Renderer::new(
+                device,
+                texture_format,
+                renderer_options,
+                &self.camera_buffer,
+                &self.model_transform_buffer,
+                &self.gaussian_transform_buffer,
+                &self.gaussians_buffer,
+                &self.indirect_indices_buffer,
+                &self.renderer_settings_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_layers_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &self.selection_highlight_buffer,
+            )?,
+        };
+
+        Ok(())
+    }
+
+    /// Set whether the Gaussians are sorted by depth before rendering.
+    ///
+    /// Sorting is enabled by default. When disabled, [`Viewer::render`] skips the
+    /// [`RadixSorter`] and the culled Gaussians are drawn in the order they are written by the
+    /// [`Preprocessor`], which is not stable across frames. This is only correct for display
+    /// modes that are independent of the draw order, e.g. opaque or additive points.
+    ///
+    /// The [`Preprocessor`] also skips writing the depth sort keys while disabled, see
+    /// [`PreprocessorSettingsPod::skip_depth`].
+    pub fn set_sorting_enabled(&mut self, queue: &wgpu::Queue, enabled: bool) {
+        self.sorting_enabled = enabled;
+        self.preprocessor_settings_buffer
+            .update_skip_depth(queue, !enabled);
+    }
+
+    /// Get whether the Gaussians are sorted by depth before rendering.
+    pub fn sorting_enabled(&self) -> bool {
+        self.sorting_enabled
+    }
+
+    /// Freeze the sorted order of the Gaussians.
+    ///
+    /// While frozen, [`Viewer::render`] and [`Viewer::render_with_depth`] skip the
+    /// [`Preprocessor`] and the [`RadixSorter`], and reuse the [`Viewer::indirect_indices_buffer`]
+    /// and [`Viewer::indirect_args_buffer`] from the last render, so only the render pass is run.
+    /// This saves the work of sorting every frame while the camera is static.
+    ///
+    /// The culled set and the order are only correct for the camera, model transform, Gaussian
+    /// transform, selection, and Gaussians they were computed with, so
+    /// [`Viewer::unfreeze_sort`] should be called when any of them changes. The order should
+    /// also be computed by at least one render before freezing.
+    pub fn freeze_sort(&mut self) {
+        self.sort_frozen = true;
+    }
+
+    /// Unfreeze the sorted order of the Gaussians.
+    ///
+    /// See [`Viewer::freeze_sort`] for more details.
+    pub fn unfreeze_sort(&mut self) {
+        self.sort_frozen = false;
+    }
+
+    /// Check whether the sorted order of the Gaussians is frozen.
+    pub fn sort_frozen(&self) -> bool {
+        self.sort_frozen
+    }
+
+    /// Download the world space bounds of the Gaussians visible in the last render.
+    ///
+    /// Unlike the bounds of the whole model, this only covers the Gaussians which survived the
+    /// culling of the [`Preprocessor`], e.g. for auto-framing or auto-exposure of what is on
+    /// screen. Returns the minimum and maximum corners, or [`None`] if no Gaussian was visible.
+    ///
+    /// The bounds are computed by the [`Preprocessor`], so they are not updated while the sort is
+    /// frozen, see [`Viewer::freeze_sort`]. This waits for the GPU, so it should not be called
+    /// every frame.
+    ///
+    /// Returns [`ViewerDownloadError::VisibleBoundsDisabled`] if the viewer is not created with
+    /// [`ViewerCreateOptions::visible_bounds`].
+    pub async fn download_visible_bounds(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Option<(Vec3, Vec3)>, ViewerDownloadError> {
+        Ok(self
+            .visible_bounds_buffer
+            .as_ref()
+            .ok_or(ViewerDownloadError::VisibleBoundsDisabled)?
+            .download_bounds(device, queue)
+            .await?)
+    }
+
+    /// Check whether the Gaussian at `index` is selected in [`Viewer::selection_buffer`].
+    ///
+    /// See [`selection::is_selected`] for more details.
+    #[cfg(feature = "viewer-selection")]
+    pub async fn is_selected(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        index: u32,
+    ) -> Result<bool, SelectionIsSelectedError> {
+        selection::is_selected(device, queue, &self.selection_buffer, index).await
+    }
+
+    /// Set whether to render only the selected Gaussians in [`Viewer::selection_buffer`].
+    ///
+    /// By default, the selected Gaussians are hidden and the rest are rendered. When `show` is
+    /// true, this is inverted to isolate the selection, without modifying the selection or the
+    /// Gaussians. The non-rendered Gaussians are culled in the preprocessor by
+    /// [`Viewer::invert_selection_buffer`].
+    #[cfg(feature = "viewer-selection")]
+    pub fn set_show_selection_only(&self, queue: &wgpu::Queue, show: bool) {
+        self.invert_selection_buffer.update(queue, !show);
+    }
+
+    /// Get the number of selection layers.
+    ///
+    /// This is set by [`ViewerCreateOptions::selection_layer_count`].
+    #[cfg(feature = "viewer-selection")]
+    pub fn selection_layer_count(&self) -> u32 {
+        self.selection_layers_buffer.layer_count()
+    }
+
+    /// Get the index of the active selection layer.
+    #[cfg(feature = "viewer-selection")]
+    pub fn active_selection_layer(&self) -> u32 {
+        self.active_selection_layer
+    }
+
+    /// Set the active selection layer.
+    ///
+    /// The active layer is the one held by [`Viewer::selection_buffer`], so the selection
+    /// operations, the queries, and the preprocessor all target it. The previous active layer is
+    /// stored into [`Viewer::selection_layers_buffer`] in the `encoder`, see
+    /// [`selection::SelectionLayersBuffer::switch_active`]. The undo and redo snapshots of
+    /// [`Viewer::selection_history`] belong to the previous layer, so they are discarded.
+    #[cfg(feature = "viewer-selection")]
+    pub fn set_active_selection_layer(
+        &mut self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        layer: u32,
+    ) -> Result<(), SelectionLayerError> {
+        let layer_count = self.selection_layer_count();
+        if layer >= layer_count {
+            return Err(SelectionLayerError::LayerOutOfBounds { layer, layer_count });
+        }
+
+        if layer == self.active_selection_layer {
+            return Ok(());
+        }
+
+        self.selection_layers_buffer.switch_active(
+            encoder,
+            &self.selection_buffer,
+            self.active_selection_layer,
+            layer,
+        );
+        self.selection_highlight_buffer
+            .update_active_layer(queue, layer);
+        self.selection_history =
+            selection::SelectionHistory::new(self.selection_history.capacity());
+        self.active_selection_layer = layer;
+
+        Ok(())
+    }
+
+    /// Update the highlight colors of the selection layers.
+    ///
+    /// See [`selection::SelectionHighlightPod::colors`] for the colors, the first color is for
+    /// layer 0. The layers without a color in `colors` are not highlighted, which is the default.
+    ///
+    /// Note: the selected Gaussians of the active layer are hidden unless
+    /// [`Viewer::set_show_selection_only`] is set, so its highlight is only visible then.
+    #[cfg(feature = "viewer-selection")]
+    pub fn update_selection_highlight(&self, queue: &wgpu::Queue, colors: &[Vec4]) {
+        self.selection_highlight_buffer.update_colors(queue, colors);
+    }
+
+    /// Render the viewer with jittered supersampling and return the averaged image.
+    ///
+    /// This renders `samples` frames, each with the projection offset by a sub-pixel jitter from
+    /// the Halton sequence, downloads them, and averages them in row-major order. It is meant for
+    /// high quality offline renders, e.g. thumbnails, and is too slow to be used every frame.
+    ///
+    /// The returned colors are the normalized values of the render target, so they are still
+    /// encoded in sRGB if [`Renderer::texture_format`] is an sRGB format. The camera buffer is
+    /// updated with `camera` and `size` after rendering.
+    pub async fn render_supersampled(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        camera: &impl CameraTrait,
+        size: UVec2,
+        samples: u32,
+    ) -> Result<Vec<Vec4>, ViewerRenderSupersampledError> {
+        let format = self.renderer.texture_format();
+        let texel_size = match format {
+            wgpu::TextureFormat::Rgba8Unorm
+            | wgpu::TextureFormat::Rgba8UnormSrgb
+            | wgpu::TextureFormat::Bgra8Unorm
+            | wgpu::TextureFormat::Bgra8UnormSrgb => 4,
+            wgpu::TextureFormat::Rgba16Float => 8,
+            wgpu::TextureFormat::Rgba32Float => 16,
+            _ => {
+                return Err(ViewerRenderSupersampledError::UnsupportedTextureFormat(
+                    format,
+                ));
+            }
+        };
+
+        let size = size.max(UVec2::ONE);
+        let pod = CameraPod::new(camera, size);
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&prefixed_label(
+                &self.label_prefix,
+                "Viewer Supersampled Render Texture",
+            )),
+            size: wgpu::Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bytes_per_row =
+            (size.x * texel_size).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let download = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&prefixed_label(
+                &self.label_prefix,
+                "Viewer Supersampled Render Download Buffer",
+            )),
+            size: (bytes_per_row * size.y) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let samples = samples.max(1);
+        let mut accum = vec![Vec4::ZERO; (size.x * size.y) as usize];
+
+        for i in 0..samples {
+            let jitter = Vec2::new(halton(i + 1, 2), halton(i + 1, 3)) - 0.5;
+            let ndc_jitter = jitter * 2.0 / size.as_vec2();
+            self.camera_buffer.update_with_pod(
+                queue,
+                &CameraPod {
+                    proj: Mat4::from_translation(ndc_jitter.extend(0.0)) * pod.proj,
+                    ..pod
+                },
+            );
+
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some(&prefixed_label(
+                    &self.label_prefix,
+                    "Viewer Supersampled Render Encoder",
+                )),
+            });
+
+            self.render(&mut encoder, &texture_view);
+
+            encoder.copy_texture_to_buffer(
+                texture.as_image_copy(),
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &download,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(bytes_per_row),
+                        rows_per_image: Some(size.y),
+                    },
+                },
+                texture.size(),
+            );
+
+            queue.submit(Some(encoder.finish()));
+
+            let bytes = wgpu::Buffer::map_download::<u8>(&download, device).await?;
+
+            for (y, row) in bytes.chunks_exact(bytes_per_row as usize).enumerate() {
+                for (x, texel) in row
+                    .chunks_exact(texel_size as usize)
+                    .take(size.x as usize)
+                    .enumerate()
+                {
+                    accum[y * size.x as usize + x] += decode_texel(format, texel);
+                }
+            }
+        }
+
+        self.camera_buffer.update_with_pod(queue, &pod);
+
+        Ok(accum
+            .into_iter()
+            .map(|color| color / samples as f32)
+            .collect())
+    }
+
+    /// Render the viewer side by side for the left and right eyes.
+    ///
+    /// The left and right halves of the render target are rendered with the camera offset by
+    /// half of `ipd` (the interpupillary distance in world units) to the left and right. The
+    /// Gaussians are preprocessed and sorted for each eye, sharing the same buffers.
+    ///
+    /// `color_ops` and the operations of the optional `depth` attachment apply to the left eye,
+    /// e.g. [`Renderer::DEFAULT_COLOR_OPS`] and [`Renderer::DEFAULT_DEPTH_OPS`] to clear them, and
+    /// the right eye loads what the left eye stored. The `depth` attachment is required if the
+    /// viewer is created with [`ViewerCreateOptions::depth_stencil`], see
+    /// [`Viewer::render_with_depth`].
+    ///
+    /// `texture_size` is the size of the whole render target. The camera buffer is updated with
+    /// `camera` and `texture_size` at the end of the `encoder`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_stereo(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture_view: &wgpu::TextureView,
+        color_ops: wgpu::Operations<wgpu::Color>,
+        depth: Option<(&wgpu::TextureView, wgpu::Operations<f32>)>,
+        camera: &impl CameraTrait,
+        texture_size: UVec2,
+        ipd: f32,
+    ) {
+        let eye_size = UVec2::new(texture_size.x / 2, texture_size.y).max(UVec2::ONE);
+        let eye_pod = CameraPod::new(camera, eye_size);
+
+        for (i, eye_offset) in [-0.5 * ipd, 0.5 * ipd].into_iter().enumerate() {
+            // The eye moves to the right by the offset, so the world moves to the left
+            let pod = CameraPod {
+                view: Mat4::from_translation(Vec3::X * -eye_offset) * eye_pod.view,
+                ..eye_pod
+            };
+            self.copy_camera_pod(device, encoder, &pod);
+
+            self.preprocess_and_sort_pass(device, encoder, &pod);
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&prefixed_label(
+                    &self.label_prefix,
+                    "Viewer Stereo Render Pass",
+                )),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: texture_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: match i {
+                            0 => color_ops.load,
+                            _ => wgpu::LoadOp::Load,
+                        },
+                        ..color_ops
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: depth.map(|(depth_view, depth_ops)| {
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view: depth_view,
+                        depth_ops: Some(wgpu::Operations {
+                            load: match i {
+                                0 => depth_ops.load,
+                                _ => wgpu::LoadOp::Load,
+                            },
+                            ..depth_ops
+                        }),
+                        stencil_ops: None,
+                    }
+                }),
+                ..Default::default()
+            });
+
+            render_pass.set_viewport(
+                (i as u32 * eye_size.x) as f32,
+                0.0,
+                eye_size.x as f32,
+                eye_size.y as f32,
+                0.0,
+                1.0,
+            );
+
+            self.renderer
+                .render_with_pass(&mut render_pass, &self.indirect_args_buffer);
+        }
+
+        let pod = CameraPod::new(camera, texture_size);
+        self.copy_camera_pod(device, encoder, &pod);
+
+        // The order of the CPU sort is kept until the next sort, so restore it for the camera
+        self.sort_cpu_with_encoder(device, encoder, &pod);
+    }
+
+    /// Render the viewer into the six faces of a cube map centered at `center`, e.g. to bake a
+    /// reflection probe.
+    ///
+    /// `face_views` are the views of the faces in the order of +X, -X, +Y, -Y, +Z, -Z, usually
+    /// single array layer views of a cube texture, each of size `face_size`. The face cameras have
+    /// a 90 degree field of view with the near and far planes of `z`, and are oriented so that the
+    /// texture can be sampled as a [`wgpu::TextureViewDimension::Cube`] view. Each face is cleared
+    /// to black, and the Gaussians are preprocessed and sorted for each face, sharing the same
+    /// buffers.
+    ///
+    /// The camera buffer is left with the camera of the last face at the end of the `encoder`,
+    /// so update the camera before rendering with [`Viewer::render`] again.
+    pub fn render_cubemap(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        face_views: [&wgpu::TextureView; 6],
+        center: Vec3,
+        face_size: u32,
+        z: std::ops::Range<f32>,
+    ) {
+        // The faces are oriented for bottom up rows, but the render target rows are top down
+        let proj = Mat4::from_scale(Vec3::new(1.0, -1.0, 1.0))
+            * Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, z.start, z.end);
+
+        for (face_view, (forward, up)) in face_views.into_iter().zip(CUBEMAP_FACES) {
+            let pod = CameraPod {
+                view: Mat4::look_to_rh(center, forward, up),
+                proj,
+                size: Vec2::splat(face_size.max(1) as f32),
+                _padding: [0; 2],
+            };
+            self.copy_camera_pod(device, encoder, &pod);
+
+            self.preprocess_and_sort_pass(device, encoder, &pod);
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&prefixed_label(
+                    &self.label_prefix,
+                    "Viewer Cubemap Render Pass",
+                )),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: face_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                ..Default::default()
+            });
+
+            self.renderer
+                .render_with_pass(&mut render_pass, &self.indirect_args_buffer);
+        }
+    }
+
     /// Render the viewer.
     pub fn render(&self, encoder: &mut wgpu::CommandEncoder, texture_view: &wgpu::TextureView) {
-        self.preprocessor
-            .preprocess(encoder, self.gaussians_buffer.len() as u32);
+        self.preprocess_and_sort(encoder);
+
+        self.renderer
+            .render(encoder, texture_view, &self.indirect_args_buffer);
+    }
+
+    /// Render the viewer with a depth stencil attachment.
+    ///
+    /// The viewer should be created with [`ViewerCreateOptions::depth_stencil`] matching the
+    /// format of `depth_view`. See [`Renderer::render_with_depth`] for the operations, e.g. to
+    /// load the depth of previously rendered opaque geometry instead of clearing it.
+    pub fn render_with_depth(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        texture_view: &wgpu::TextureView,
+        color_ops: wgpu::Operations<wgpu::Color>,
+        depth_view: &wgpu::TextureView,
+        depth_ops: wgpu::Operations<f32>,
+    ) {
+        self.preprocess_and_sort(encoder);
+
+        self.renderer.render_with_depth(
+            encoder,
+            texture_view,
+            color_ops,
+            depth_view,
+            depth_ops,
+            &self.indirect_args_buffer,
+        );
+    }
+
+    /// Run only the preprocess stage of [`Viewer::render`].
+    ///
+    /// This culls the Gaussians, computes their depths, and writes the indirect args, including
+    /// the linear depths and 2D conics if enabled by [`ViewerCreateOptions::linear_depth`] and
+    /// [`ViewerCreateOptions::conic2d`]. Together with
+    /// [`Viewer::sort_only`] and [`Viewer::render_only`], the stages can be run individually, e.g.
+    /// to render the sorted Gaussians with a custom pipeline bound to the viewer buffers.
+    ///
+    /// Unlike [`Viewer::render`], this runs even if the sort is frozen.
+    pub fn preprocess_only(&self, encoder: &mut wgpu::CommandEncoder) {
+        if self.cpu_sorter.is_none() {
+            self.preprocessor
+                .preprocess(encoder, self.gaussians_buffer.len() as u32);
+        }
+    }
 
-        self.radix_sorter
-            .sort(encoder, &self.radix_sort_indirect_args_buffer);
+    /// Run only the sort stage of [`Viewer::render`].
+    ///
+    /// The Gaussians should be preprocessed by [`Viewer::preprocess_only`] first.
+    ///
+    /// Unlike [`Viewer::render`], this runs even if the sort is frozen or sorting is disabled.
+    /// While sorting is disabled, the [`Preprocessor`] does not write the depth sort keys, so
+    /// the keys are left from the last preprocess with sorting enabled.
+    pub fn sort_only(&self, encoder: &mut wgpu::CommandEncoder) {
+        if let Some(radix_sorter) = &self.radix_sorter {
+            radix_sorter.sort(encoder, &self.radix_sort_indirect_args_buffer);
+        }
+    }
 
+    /// Run only the render stage of [`Viewer::render`].
+    ///
+    /// The Gaussians are drawn in the order of the last sort, or the order written by the last
+    /// preprocess if they have never been sorted.
+    pub fn render_only(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        texture_view: &wgpu::TextureView,
+    ) {
         self.renderer
             .render(encoder, texture_view, &self.indirect_args_buffer);
     }
+
+    /// Preprocess and sort the Gaussians for rendering, unless the sort is frozen.
+    fn preprocess_and_sort(&self, encoder: &mut wgpu::CommandEncoder) {
+        if self.sort_frozen {
+            return;
+        }
+
+        self.preprocess_only(encoder);
+
+        if self.sorting_enabled {
+            self.sort_only(encoder);
+        }
+    }
+
+    /// Run the [`Preprocessor`] and the [`RadixSorter`], or [`Viewer::cpu_sorter`], for another
+    /// camera pass with the `camera` copied into the camera buffer in the `encoder`.
+    fn preprocess_and_sort_pass(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        camera: &CameraPod,
+    ) {
+        if self.cpu_sorter.is_some() {
+            self.sort_cpu_with_encoder(device, encoder, camera);
+            return;
+        }
+
+        self.preprocessor
+            .preprocess(encoder, self.gaussians_buffer.len() as u32);
+
+        if self.sorting_enabled {
+            self.sort_only(encoder);
+        }
+    }
+
+    /// Cull and sort the Gaussians with [`Viewer::cpu_sorter`], unless the sort is frozen.
+    fn sort_cpu(&self, queue: &wgpu::Queue) {
+        if self.sort_frozen {
+            return;
+        }
+
+        if let (Some(cpu_sorter), Some(camera)) = (&self.cpu_sorter, &self.camera) {
+            cpu_sorter.sort(
+                queue,
+                camera,
+                &self.model_transform,
+                self.min_opacity,
+                &self.indirect_args_buffer,
+                &self.indirect_indices_buffer,
+            );
+        }
+    }
+
+    /// Cull and sort the Gaussians for the `camera` with [`Viewer::cpu_sorter`] in the `encoder`.
+    fn sort_cpu_with_encoder(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        camera: &CameraPod,
+    ) {
+        if let Some(cpu_sorter) = &self.cpu_sorter {
+            cpu_sorter.sort_with_encoder(
+                device,
+                encoder,
+                camera,
+                &self.model_transform,
+                self.min_opacity,
+                &self.indirect_args_buffer,
+                &self.indirect_indices_buffer,
+            );
+        }
+    }
+
+    /// Check that the `min_pixel_size` is disabled with [`Viewer::cpu_sorter`], which does not
+    /// compute the projected footprints.
+    fn check_cpu_sort_min_pixel_size(
+        &self,
+        min_pixel_size: f32,
+    ) -> Result<(), ViewerPreprocessorSettingsError> {
+        if self.cpu_sorter.is_some() && min_pixel_size > 0.0 {
+            return Err(ViewerPreprocessorSettingsError::UnsupportedMinPixelSize(
+                min_pixel_size,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Copy the [`CameraPod`] into the camera buffer in the `encoder`.
+    ///
+    /// Unlike [`CameraBuffer::update_with_pod`], this is ordered with the other commands in the
+    /// `encoder`, so the camera can be changed between passes.
+    fn copy_camera_pod(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        pod: &CameraPod,
+    ) {
+        let staging = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&prefixed_label(
+                &self.label_prefix,
+                "Viewer Camera Staging Buffer",
+            )),
+            contents: bytemuck::bytes_of(pod),
+            usage: wgpu::BufferUsages::COPY_SRC,
+        });
+
+        encoder.copy_buffer_to_buffer(
+            &staging,
+            0,
+            self.camera_buffer.buffer(),
+            0,
+            std::mem::size_of::<CameraPod>() as wgpu::BufferAddress,
+        );
+    }
+}
+
+/// Prefix the debug `name` with the label `prefix`, leaving `name` unchanged if `prefix` is empty.
+pub(crate) fn prefixed_label(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix} {name}")
+    }
+}
+
+/// Check whether the `device` supports the `STORAGE | INDIRECT` usage of the indirect args buffers.
+///
+/// The indirect args are written by the preprocessor compute shader, so both storage buffers and
+/// compute shaders are required, which are missing on some downlevel backends, e.g. WebGL.
+pub(crate) fn check_indirect_usage(device: &wgpu::Device) -> Result<(), ViewerCreateError> {
+    let limits = device.limits();
+    if limits.max_storage_buffers_per_shader_stage == 0
+        || limits.max_compute_invocations_per_workgroup == 0
+    {
+        return Err(ViewerCreateError::UnsupportedIndirectUsage {
+            max_storage_buffers_per_shader_stage: limits.max_storage_buffers_per_shader_stage,
+            max_compute_invocations_per_workgroup: limits.max_compute_invocations_per_workgroup,
+        });
+    }
+
+    Ok(())
+}
+
+/// Get the `index`-th element of the Halton sequence with `base`, in `[0, 1)`.
+fn halton(mut index: u32, base: u32) -> f32 {
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+/// Decode a texel of the supported `format` in [`Viewer::render_supersampled`].
+fn decode_texel(format: wgpu::TextureFormat, texel: &[u8]) -> Vec4 {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => {
+            U8Vec4::from_slice(texel).as_vec4() / 255.0
+        }
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => {
+            U8Vec4::new(texel[2], texel[1], texel[0], texel[3]).as_vec4() / 255.0
+        }
+        wgpu::TextureFormat::Rgba16Float => Vec4::from_array(
+            bytemuck::pod_read_unaligned::<[half::f16; 4]>(texel).map(half::f16::to_f32),
+        ),
+        wgpu::TextureFormat::Rgba32Float => {
+            Vec4::from_array(bytemuck::pod_read_unaligned::<[f32; 4]>(texel))
+        }
+        _ => unreachable!("unsupported texture format {format:?}"),
+    }
 }
 
 /// The options for creating a [`Viewer`] using [`Viewer::new_with_options`].
 pub struct ViewerCreateOptions {
     /// The optional depth stencil state for the renderer.
     pub depth_stencil: Option<wgpu::DepthStencilState>,
+    /// The primitive state for the renderer.
+    ///
+    /// The default does not cull, which is correct for the screen space quads of the Gaussians.
+    /// The culling and front face may be set to match the raster state conventions of a
+    /// surrounding renderer, the topology should be kept, see [`Renderer::primitive`].
+    pub primitive: wgpu::PrimitiveState,
     /// The usage for the gaussians buffer.
     pub gaussians_buffer_usage: wgpu::BufferUsages,
+    /// The geometry used to draw each Gaussian.
+    pub quad_mode: RenderQuadMode,
+    /// The mapping from the view distance to the depth sort key.
+    pub depth_mapping: DepthMapping,
+    /// The sort keys for [`DepthMapping::Custom`], required with it and ignored otherwise.
+    ///
+    /// It should have the same length as the Gaussians, with the keys non-negative and less than
+    /// 2.0, see [`SortKeyBuffer`]. The keys can be replaced later by
+    /// [`Preprocessor::set_sort_keys`] on [`Viewer::preprocessor`].
+    pub sort_keys: Option<SortKeyBuffer>,
+    /// Whether to also compute the world space bounds of the visible Gaussians.
+    ///
+    /// If enabled, [`Viewer::visible_bounds_buffer`] is filled by [`Viewer::preprocessor`] on
+    /// every [`Viewer::render`], which can be downloaded by [`Viewer::download_visible_bounds`].
+    /// This is disabled by default as the bounds are reduced with atomics in every workgroup.
+    pub visible_bounds: bool,
+    /// Whether to also compute the linear view space depth of the Gaussians.
+    ///
+    /// If enabled, [`Viewer::gaussians_linear_depth_buffer`] is filled by
+    /// [`Viewer::preprocessor`] on every [`Viewer::render`], see
+    /// [`PreprocessorCreateOptions::linear_depth`]. It is not filled with
+    /// [`ViewerCreateOptions::cpu_sort`], as the preprocessor is not run.
+    pub linear_depth: bool,
+    /// Whether to also compute the projected 2D conic of the Gaussians.
+    ///
+    /// If enabled, [`Viewer::conic2d_buffer`] is filled by [`Viewer::preprocessor`] on every
+    /// [`Viewer::render`], which can be downloaded by [`Conic2dBuffer::download_conics`], see
+    /// [`PreprocessorCreateOptions::conic2d`]. It is not filled with
+    /// [`ViewerCreateOptions::cpu_sort`], as the preprocessor is not run.
+    pub conic2d: bool,
+    /// Whether to cull and sort the Gaussians on the CPU with [`CpuSorter`] instead of the
+    /// [`Preprocessor`] and the [`RadixSorter`].
+    ///
+    /// This is for adapters which cannot run the radix sort, and is always enabled if
+    /// [`RadixSorter::is_supported`] is false. The Gaussians are sorted by
+    /// [`Viewer::update_camera`] and [`Viewer::update_model_transform`] instead of
+    /// [`Viewer::render`], and for each camera of [`Viewer::render_stereo`] and
+    /// [`Viewer::render_cubemap`]. Only [`PreprocessorSettings::min_opacity`] of the preprocessor
+    /// settings is applied, setting [`PreprocessorSettings::min_pixel_size`] returns an error, see
+    /// [`CpuSorter`]. [`Viewer::set_sorting_enabled`] has no effect.
+    ///
+    /// [`DepthMapping::Custom`] and the `viewer-selection` feature are not supported, and
+    /// [`Viewer::new_with_options`] returns an error with them.
+    pub cpu_sort: bool,
+    /// The optional prefix of the buffer labels, e.g. to tell the buffers of multiple viewers apart
+    /// in GPU debuggers.
+    ///
+    /// This applies to the buffers, pipelines, bind groups and render passes created by this
+    /// crate, the buffers created by [`wgpu_3dgs_core`] keep their fixed labels. The labels are
+    /// unchanged without a prefix.
+    pub label_prefix: Option<String>,
+    /// The optional pipeline cache for the [`Renderer`] and [`RadixSorter`] pipelines.
+    ///
+    /// The cache can be persisted with [`wgpu::PipelineCache::get_data`] to cut the pipeline
+    /// compilation time of later runs, see [`wgpu::Device::create_pipeline_cache`]. The
+    /// [`Preprocessor`] pipelines are created by [`wgpu_3dgs_core`] without a cache.
+    pub pipeline_cache: Option<wgpu::PipelineCache>,
+    /// The number of selection layers, at least 1.
+    ///
+    /// Each layer is an independent selection, see [`Viewer::set_active_selection_layer`] and
+    /// [`Viewer::update_selection_highlight`]. Only the first
+    /// [`selection::SelectionHighlightPod::MAX_LAYERS`] layers can be highlighted.
+    #[cfg(feature = "viewer-selection")]
+    pub selection_layer_count: u32,
 }
 
 impl Default for ViewerCreateOptions {
     fn default() -> Self {
         Self {
             depth_stencil: None,
+            primitive: wgpu::PrimitiveState::default(),
             gaussians_buffer_usage: GaussiansBuffer::<DefaultGaussianPod>::DEFAULT_USAGES,
+            visible_bounds: false,
+            linear_depth: false,
+            conic2d: false,
+            cpu_sort: false,
+            quad_mode: RenderQuadMode::default(),
+            depth_mapping: DepthMapping::default(),
+            sort_keys: None,
+            label_prefix: None,
+            pipeline_cache: None,
+            #[cfg(feature = "viewer-selection")]
+            selection_layer_count: 1,
         }
     }
 }
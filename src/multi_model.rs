@@ -1,26 +1,52 @@
 use std::{collections::HashMap, hash::Hash};
 
-use crate::*;
+use crate::{core::Gaussian, *};
 
 /// The buffers for [`Viewer`] related to the world.
 #[derive(Debug)]
 pub struct MultiModelViewerWorldBuffers {
     pub camera_buffer: CameraBuffer,
     pub gaussian_transform_buffer: GaussianTransformBuffer,
+    pub preprocessor_settings_buffer: PreprocessorSettingsBuffer,
+    pub renderer_settings_buffer: RendererSettingsBuffer,
+    #[cfg(feature = "viewer-selection")]
+    pub selection_highlight_buffer: selection::SelectionHighlightBuffer,
 }
 
 impl MultiModelViewerWorldBuffers {
     /// Create a new viewer world buffers.
     pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new viewer world buffers with additional label.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
         log::debug!("Creating camera buffer");
-        let camera_buffer = CameraBuffer::new(device);
+        let camera_buffer = CameraBuffer::new_with_label(device, label);
 
         log::debug!("Creating gaussian transform buffer");
         let gaussian_transform_buffer = GaussianTransformBuffer::new(device);
 
+        log::debug!("Creating preprocessor settings buffer");
+        let preprocessor_settings_buffer =
+            PreprocessorSettingsBuffer::new_with_label(device, label);
+
+        log::debug!("Creating renderer settings buffer");
+        let renderer_settings_buffer = RendererSettingsBuffer::new_with_label(device, label);
+
+        #[cfg(feature = "viewer-selection")]
+        let selection_highlight_buffer = {
+            log::debug!("Creating selection highlight buffer");
+            selection::SelectionHighlightBuffer::new_with_label(device, label)
+        };
+
         Self {
             camera_buffer,
             gaussian_transform_buffer,
+            preprocessor_settings_buffer,
+            renderer_settings_buffer,
+            #[cfg(feature = "viewer-selection")]
+            selection_highlight_buffer,
         }
     }
 
@@ -67,27 +93,84 @@ impl MultiModelViewerWorldBuffers {
     ) {
         self.gaussian_transform_buffer.update_with_pod(queue, pod);
     }
+
+    /// Update the preprocessor settings.
+    pub fn update_preprocessor_settings(
+        &mut self,
+        queue: &wgpu::Queue,
+        settings: &PreprocessorSettings,
+    ) {
+        self.preprocessor_settings_buffer.update(queue, settings);
+    }
+
+    /// Update the preprocessor settings with [`PreprocessorSettingsPod`].
+    pub fn update_preprocessor_settings_with_pod(
+        &mut self,
+        queue: &wgpu::Queue,
+        pod: &PreprocessorSettingsPod,
+    ) {
+        self.preprocessor_settings_buffer
+            .update_with_pod(queue, pod);
+    }
+
+    /// Update the renderer settings.
+    pub fn update_renderer_settings(&mut self, queue: &wgpu::Queue, settings: &RendererSettings) {
+        self.renderer_settings_buffer.update(queue, settings);
+    }
+
+    /// Update the renderer settings with [`RendererSettingsPod`].
+    pub fn update_renderer_settings_with_pod(
+        &mut self,
+        queue: &wgpu::Queue,
+        pod: &RendererSettingsPod,
+    ) {
+        self.renderer_settings_buffer.update_with_pod(queue, pod);
+    }
 }
 
 /// The buffers for [`Viewer`] related to the Guassian model.
 #[derive(Debug)]
 pub struct MultiModelViewerGaussianBuffers<G: GaussianPod = DefaultGaussianPod> {
     pub model_transform_buffer: ModelTransformBuffer,
+    /// The per-model Gaussian transform buffer.
+    ///
+    /// If [`None`], the model uses [`MultiModelViewerWorldBuffers::gaussian_transform_buffer`].
+    pub gaussian_transform_buffer: Option<GaussianTransformBuffer>,
     pub gaussians_buffer: GaussiansBuffer<G>,
     pub indirect_args_buffer: IndirectArgsBuffer,
     pub radix_sort_indirect_args_buffer: RadixSortIndirectArgsBuffer,
     pub indirect_indices_buffer: IndirectIndicesBuffer,
     pub gaussians_depth_buffer: GaussiansDepthBuffer,
+    /// The visible bounds of the model.
+    ///
+    /// This is [`None`], unless the model is inserted into a [`MultiModelViewer`] with
+    /// [`MultiModelViewerCreateOptions::visible_bounds`].
+    pub visible_bounds_buffer: Option<VisibleBoundsBuffer>,
     #[cfg(feature = "viewer-selection")]
     pub selection_buffer: SelectionBuffer,
     #[cfg(feature = "viewer-selection")]
     pub invert_selection_buffer: selection::PreprocessorInvertSelectionBuffer,
+    /// The selection layers of the model.
+    ///
+    /// This has a single layer, unless the model is inserted into a [`MultiModelViewer`] with
+    /// more [`MultiModelViewerCreateOptions::selection_layer_count`].
+    #[cfg(feature = "viewer-selection")]
+    pub selection_layers_buffer: selection::SelectionLayersBuffer,
 }
 
 impl<G: GaussianPod> MultiModelViewerGaussianBuffers<G> {
+    /// The default usage for the gaussians buffer.
+    ///
+    /// This is [`GaussiansBuffer::DEFAULT_USAGES`] with [`wgpu::BufferUsages::COPY_SRC`], so the
+    /// model can be grown by [`MultiModelViewerGaussianBuffers::append`].
+    pub const DEFAULT_GAUSSIANS_BUFFER_USAGES: wgpu::BufferUsages =
+        wgpu::BufferUsages::from_bits_retain(
+            GaussiansBuffer::<G>::DEFAULT_USAGES.bits() | wgpu::BufferUsages::COPY_SRC.bits(),
+        );
+
     /// Create a new viewer Gaussian buffers.
     pub fn new(device: &wgpu::Device, gaussians: &impl IterGaussian) -> Self {
-        Self::new_with(device, GaussiansBuffer::<G>::DEFAULT_USAGES, gaussians)
+        Self::new_with(device, Self::DEFAULT_GAUSSIANS_BUFFER_USAGES, gaussians)
     }
 
     /// Create a new viewer Gaussian buffers with custom gaussians buffer usage.
@@ -95,6 +178,20 @@ impl<G: GaussianPod> MultiModelViewerGaussianBuffers<G> {
         device: &wgpu::Device,
         gaussians_buffer_usage: wgpu::BufferUsages,
         gaussians: &impl IterGaussian,
+    ) -> Self {
+        Self::new_with_label(device, "", gaussians_buffer_usage, gaussians)
+    }
+
+    /// Create a new viewer Gaussian buffers with additional label and custom gaussians buffer
+    /// usage.
+    ///
+    /// The label is added to the buffers created by this crate, the buffers created by
+    /// [`wgpu_3dgs_core`] keep their fixed labels.
+    pub fn new_with_label(
+        device: &wgpu::Device,
+        label: &str,
+        gaussians_buffer_usage: wgpu::BufferUsages,
+        gaussians: &impl IterGaussian,
     ) -> Self {
         log::debug!("Creating model transform buffer");
         let model_transform_buffer = ModelTransformBuffer::new(device);
@@ -104,49 +201,61 @@ impl<G: GaussianPod> MultiModelViewerGaussianBuffers<G> {
             GaussiansBuffer::new_with_usage(device, gaussians, gaussians_buffer_usage);
 
         log::debug!("Creating indirect args buffer");
-        let indirect_args_buffer = IndirectArgsBuffer::new(device);
+        let indirect_args_buffer =
+            IndirectArgsBuffer::new_with_label_and_quad_mode(device, label, Default::default());
 
         log::debug!("Creating radix sort indirect args buffer");
-        let radix_sort_indirect_args_buffer = RadixSortIndirectArgsBuffer::new(device);
+        let radix_sort_indirect_args_buffer =
+            RadixSortIndirectArgsBuffer::new_with_label(device, label);
 
         // Assume it is cheap to call `iter_gaussian`.
         let len = gaussians.iter_gaussian().len() as u32;
 
         log::debug!("Creating indirect indices buffer");
-        let indirect_indices_buffer = IndirectIndicesBuffer::new(device, len);
+        let indirect_indices_buffer = IndirectIndicesBuffer::new_with_label(device, label, len);
 
         log::debug!("Creating gaussians depth buffer");
-        let gaussians_depth_buffer = GaussiansDepthBuffer::new(device, len);
+        let gaussians_depth_buffer = GaussiansDepthBuffer::new_with_label(device, label, len);
 
         #[cfg(feature = "viewer-selection")]
         let selection_buffer = {
             log::debug!("Creating selection buffer");
-            SelectionBuffer::new(device, len)
+            SelectionBuffer::new_with_label(device, label, len)
         };
 
         #[cfg(feature = "viewer-selection")]
         let invert_selection_buffer = {
             log::debug!("Creating invert selection buffer");
-            selection::PreprocessorInvertSelectionBuffer::new(device)
+            selection::PreprocessorInvertSelectionBuffer::new_with_label(device, label)
+        };
+
+        #[cfg(feature = "viewer-selection")]
+        let selection_layers_buffer = {
+            log::debug!("Creating selection layers buffer");
+            selection::SelectionLayersBuffer::new_with_label(device, label, len, 1)
         };
 
         Self {
             model_transform_buffer,
+            gaussian_transform_buffer: None,
             gaussians_buffer,
             indirect_args_buffer,
             radix_sort_indirect_args_buffer,
             indirect_indices_buffer,
             gaussians_depth_buffer,
+            visible_bounds_buffer: None,
             #[cfg(feature = "viewer-selection")]
             selection_buffer,
             #[cfg(feature = "viewer-selection")]
             invert_selection_buffer,
+            #[cfg(feature = "viewer-selection")]
+            selection_layers_buffer,
         }
     }
 
     /// Create a new viewer Gaussian buffers with only the count.
     pub fn new_empty(device: &wgpu::Device, count: usize) -> Self {
-        Self::new_empty_with(device, count, GaussiansBuffer::<G>::DEFAULT_USAGES)
+        Self::new_empty_with(device, count, Self::DEFAULT_GAUSSIANS_BUFFER_USAGES)
     }
 
     /// Create a new viewer Gaussian buffers with only the count and custom gaussians buffer usage.
@@ -186,20 +295,119 @@ impl<G: GaussianPod> MultiModelViewerGaussianBuffers<G> {
             selection::PreprocessorInvertSelectionBuffer::new(device)
         };
 
+        #[cfg(feature = "viewer-selection")]
+        let selection_layers_buffer = {
+            log::debug!("Creating selection layers buffer");
+            selection::SelectionLayersBuffer::new(device, count as u32, 1)
+        };
+
         Self {
             model_transform_buffer,
+            gaussian_transform_buffer: None,
             gaussians_buffer,
             indirect_args_buffer,
             radix_sort_indirect_args_buffer,
             indirect_indices_buffer,
             gaussians_depth_buffer,
+            visible_bounds_buffer: None,
             #[cfg(feature = "viewer-selection")]
             selection_buffer,
             #[cfg(feature = "viewer-selection")]
             invert_selection_buffer,
+            #[cfg(feature = "viewer-selection")]
+            selection_layers_buffer,
         }
     }
 
+    /// Append Gaussians to the end of the model.
+    ///
+    /// The Gaussians buffer is grown by copying the existing Gaussians into a new buffer with
+    /// the same usage, which must contain [`wgpu::BufferUsages::COPY_SRC`] as in
+    /// [`MultiModelViewerGaussianBuffers::DEFAULT_GAUSSIANS_BUFFER_USAGES`]. The indirect indices,
+    /// depth, and selection buffers are resized, and the existing selection is kept. The bind
+    /// groups of the model have to be recreated afterwards.
+    pub fn append(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        gaussians: &[Gaussian],
+    ) -> Result<(), MultiModelViewerAppendError> {
+        self.append_with_label(device, queue, "", gaussians)
+    }
+
+    /// Append Gaussians to the end of the model with additional label for the resized buffers.
+    ///
+    /// See [`MultiModelViewerGaussianBuffers::append`] for more details.
+    pub fn append_with_label(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        label: &str,
+        gaussians: &[Gaussian],
+    ) -> Result<(), MultiModelViewerAppendError> {
+        let usage = self.gaussians_buffer.buffer().usage();
+        if !usage.contains(wgpu::BufferUsages::COPY_SRC) {
+            return Err(MultiModelViewerAppendError::MissingCopySrcUsage(usage));
+        }
+
+        if gaussians.is_empty() {
+            return Ok(());
+        }
+
+        let start = self.gaussians_buffer.len();
+        let len = start + gaussians.len();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some(&prefixed_label(label, "Multi Model Viewer Append Encoder")),
+        });
+
+        log::debug!("Growing gaussians buffer from {start} to {len}");
+        let gaussians_buffer = GaussiansBuffer::<G>::new_empty_with_usage(device, len, usage);
+        encoder.copy_buffer_to_buffer(
+            self.gaussians_buffer.buffer(),
+            0,
+            gaussians_buffer.buffer(),
+            0,
+            self.gaussians_buffer.buffer().size(),
+        );
+        gaussians_buffer
+            .update_range(queue, start, gaussians)
+            .expect("appended gaussians fit in the grown buffer");
+
+        log::debug!("Resizing indirect indices buffer");
+        self.indirect_indices_buffer =
+            IndirectIndicesBuffer::new_with_label(device, label, len as u32);
+
+        log::debug!("Resizing gaussians depth buffer");
+        self.gaussians_depth_buffer =
+            GaussiansDepthBuffer::new_with_label(device, label, len as u32);
+
+        #[cfg(feature = "viewer-selection")]
+        {
+            log::debug!("Resizing selection buffer");
+            let selection_buffer = SelectionBuffer::new_with_label(device, label, len as u32);
+            encoder.copy_buffer_to_buffer(
+                self.selection_buffer.buffer(),
+                0,
+                selection_buffer.buffer(),
+                0,
+                self.selection_buffer.buffer().size(),
+            );
+            self.selection_buffer = selection_buffer;
+
+            log::debug!("Resizing selection layers buffer");
+            self.selection_layers_buffer =
+                self.selection_layers_buffer
+                    .resized(device, &mut encoder, len as u32);
+        }
+
+        queue.submit(Some(encoder.finish()));
+
+        self.gaussians_buffer = gaussians_buffer;
+
+        Ok(())
+    }
+
     /// Update the model transform.
     pub fn update_model_transform(
         &mut self,
@@ -219,6 +427,40 @@ impl<G: GaussianPod> MultiModelViewerGaussianBuffers<G> {
     ) {
         self.model_transform_buffer.update_with_pod(queue, pod);
     }
+
+    /// Update the per-model Gaussian transform with [`GaussianTransformPod`].
+    ///
+    /// Returns `true` if the per-model Gaussian transform buffer was newly created, in which case
+    /// the bind groups of the model have to be recreated.
+    pub fn update_gaussian_transform_with_pod(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        pod: &GaussianTransformPod,
+    ) -> bool {
+        let created = self.gaussian_transform_buffer.is_none();
+
+        self.gaussian_transform_buffer
+            .get_or_insert_with(|| {
+                log::debug!("Creating per-model gaussian transform buffer");
+                GaussianTransformBuffer::new(device)
+            })
+            .update_with_pod(queue, pod);
+
+        created
+    }
+
+    /// Get the Gaussian transform buffer used by the model.
+    ///
+    /// This is the per-model buffer if it is set, otherwise the shared buffer in `world_buffers`.
+    pub fn gaussian_transform_buffer_or<'a>(
+        &'a self,
+        world_buffers: &'a MultiModelViewerWorldBuffers,
+    ) -> &'a GaussianTransformBuffer {
+        self.gaussian_transform_buffer
+            .as_ref()
+            .unwrap_or(&world_buffers.gaussian_transform_buffer)
+    }
 }
 
 /// The bind groups for [`MultiModelViewer`].
@@ -239,16 +481,24 @@ impl MultiModelViewerBindGroups {
         gaussian_buffers: &MultiModelViewerGaussianBuffers<G>,
         world_buffers: &MultiModelViewerWorldBuffers,
     ) -> Self {
+        let gaussian_transform_buffer =
+            gaussian_buffers.gaussian_transform_buffer_or(world_buffers);
+
         let preprocessor = preprocessor.create_bind_group(
             device,
             &world_buffers.camera_buffer,
             &gaussian_buffers.model_transform_buffer,
-            &world_buffers.gaussian_transform_buffer,
+            gaussian_transform_buffer,
             &gaussian_buffers.gaussians_buffer,
             &gaussian_buffers.indirect_args_buffer,
             &gaussian_buffers.radix_sort_indirect_args_buffer,
             &gaussian_buffers.indirect_indices_buffer,
             &gaussian_buffers.gaussians_depth_buffer,
+            &world_buffers.preprocessor_settings_buffer,
+            gaussian_buffers.visible_bounds_buffer.as_ref(),
+            None,
+            None,
+            None,
             #[cfg(feature = "viewer-selection")]
             &gaussian_buffers.selection_buffer,
             #[cfg(feature = "viewer-selection")]
@@ -263,9 +513,16 @@ impl MultiModelViewerBindGroups {
             device,
             &world_buffers.camera_buffer,
             &gaussian_buffers.model_transform_buffer,
-            &world_buffers.gaussian_transform_buffer,
+            gaussian_transform_buffer,
             &gaussian_buffers.gaussians_buffer,
             &gaussian_buffers.indirect_indices_buffer,
+            &world_buffers.renderer_settings_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &gaussian_buffers.selection_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &gaussian_buffers.selection_layers_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &world_buffers.selection_highlight_buffer,
         );
 
         Self {
@@ -276,6 +533,84 @@ impl MultiModelViewerBindGroups {
     }
 }
 
+/// The options for [`MultiModelViewer::new_with_options`].
+#[derive(Debug, Clone)]
+pub struct MultiModelViewerCreateOptions {
+    /// The optional depth stencil state for the renderer.
+    pub depth_stencil: Option<wgpu::DepthStencilState>,
+    /// The primitive state for the renderer, see [`ViewerCreateOptions::primitive`].
+    pub primitive: wgpu::PrimitiveState,
+    /// The usage for the gaussians buffer when [`MultiModelViewer::insert_model`] is called.
+    ///
+    /// This sets [`MultiModelViewer::gaussians_buffer_usage`], and can be overridden when
+    /// inserting model using [`MultiModelViewer::insert_model_with`]. The default is
+    /// [`MultiModelViewerGaussianBuffers::DEFAULT_GAUSSIANS_BUFFER_USAGES`], which allows
+    /// [`MultiModelViewer::append_to_model`].
+    pub gaussians_buffer_usage: wgpu::BufferUsages,
+    /// The geometry used to draw each Gaussian.
+    pub quad_mode: RenderQuadMode,
+    /// The mapping from the view distance to the depth sort key.
+    ///
+    /// [`DepthMapping::Custom`] is not supported, as the models do not have sort keys.
+    pub depth_mapping: DepthMapping,
+    /// Whether to also compute the world space bounds of the visible Gaussians of each model, see
+    /// [`ViewerCreateOptions::visible_bounds`].
+    pub visible_bounds: bool,
+    /// The optional prefix of the labels, see [`ViewerCreateOptions::label_prefix`].
+    ///
+    /// This applies to the world buffers, the buffers of the models, the pipelines, the bind
+    /// groups and the render passes created by this crate, the buffers created by [`wgpu_3dgs_core`] keep their fixed
+    /// labels.
+    pub label_prefix: Option<String>,
+    /// The optional pipeline cache, see [`ViewerCreateOptions::pipeline_cache`].
+    pub pipeline_cache: Option<wgpu::PipelineCache>,
+    /// The number of selection layers of each model, see
+    /// [`ViewerCreateOptions::selection_layer_count`].
+    #[cfg(feature = "viewer-selection")]
+    pub selection_layer_count: u32,
+}
+
+impl Default for MultiModelViewerCreateOptions {
+    fn default() -> Self {
+        Self {
+            depth_stencil: None,
+            primitive: wgpu::PrimitiveState::default(),
+            gaussians_buffer_usage:
+                MultiModelViewerGaussianBuffers::<DefaultGaussianPod>::DEFAULT_GAUSSIANS_BUFFER_USAGES,
+            quad_mode: RenderQuadMode::default(),
+            depth_mapping: DepthMapping::default(),
+            visible_bounds: false,
+            label_prefix: None,
+            pipeline_cache: None,
+            #[cfg(feature = "viewer-selection")]
+            selection_layer_count: 1,
+        }
+    }
+}
+
+impl From<ViewerCreateOptions> for MultiModelViewerCreateOptions {
+    /// Convert from [`ViewerCreateOptions`], ignoring [`ViewerCreateOptions::linear_depth`],
+    /// [`ViewerCreateOptions::conic2d`], [`ViewerCreateOptions::cpu_sort`], and
+    /// [`ViewerCreateOptions::sort_keys`].
+    ///
+    /// [`wgpu::BufferUsages::COPY_SRC`] is added to [`ViewerCreateOptions::gaussians_buffer_usage`]
+    /// for [`MultiModelViewer::append_to_model`].
+    fn from(options: ViewerCreateOptions) -> Self {
+        Self {
+            depth_stencil: options.depth_stencil,
+            primitive: options.primitive,
+            gaussians_buffer_usage: options.gaussians_buffer_usage | wgpu::BufferUsages::COPY_SRC,
+            quad_mode: options.quad_mode,
+            depth_mapping: options.depth_mapping,
+            visible_bounds: options.visible_bounds,
+            label_prefix: options.label_prefix,
+            pipeline_cache: options.pipeline_cache,
+            #[cfg(feature = "viewer-selection")]
+            selection_layer_count: options.selection_layer_count,
+        }
+    }
+}
+
 /// The model of the [`MultiModelViewer`].
 #[derive(Debug)]
 pub struct MultiModelViewerModel<G: GaussianPod = DefaultGaussianPod> {
@@ -300,6 +635,17 @@ pub struct MultiModelViewer<G: GaussianPod = DefaultGaussianPod, K: Hash + std::
     /// Can be overridden when inserting model using [`MultiModelViewer::insert_model_with`].
     // If there are more than one of these default, maybe create something like InsertModelOptions
     pub gaussians_buffer_usage: wgpu::BufferUsages,
+
+    /// The prefix of the labels, empty if there is no prefix.
+    label_prefix: String,
+    /// The key of the only model to render, see [`MultiModelViewer::set_solo`].
+    solo: Option<K>,
+    /// The number of selection layers of each model.
+    #[cfg(feature = "viewer-selection")]
+    selection_layer_count: u32,
+    /// The index of the active selection layer of all models.
+    #[cfg(feature = "viewer-selection")]
+    active_selection_layer: u32,
 }
 
 impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
@@ -308,33 +654,72 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
         device: &wgpu::Device,
         texture_format: wgpu::TextureFormat,
     ) -> Result<Self, ViewerCreateError> {
-        Self::new_with_options(device, texture_format, ViewerCreateOptions::default())
+        Self::new_with_options(
+            device,
+            texture_format,
+            MultiModelViewerCreateOptions::default(),
+        )
     }
 
-    /// Create a new viewer with extra [`ViewerCreateOptions`].
+    /// Create a new viewer with extra [`MultiModelViewerCreateOptions`].
     ///
-    /// Note that only [`ViewerCreateOptions::gaussians_buffer_usage`] is used when inserting models
-    /// with [`MultiModelViewer::insert_model`]. You can also override the usage using
-    /// [`MultiModelViewer::insert_model_with`].
+    /// [`ViewerCreateOptions`] is also accepted for compatibility, the options not applicable to
+    /// [`MultiModelViewer`] are ignored. Returns [`ViewerCreateError::UnsupportedDepthMapping`]
+    /// with [`DepthMapping::Custom`].
     pub fn new_with_options(
         device: &wgpu::Device,
         texture_format: wgpu::TextureFormat,
-        options: ViewerCreateOptions,
+        options: impl Into<MultiModelViewerCreateOptions>,
     ) -> Result<Self, ViewerCreateError> {
+        let options = options.into();
+
+        check_indirect_usage(device)?;
+
+        if options.depth_mapping == DepthMapping::Custom {
+            return Err(ViewerCreateError::UnsupportedDepthMapping(
+                options.depth_mapping,
+            ));
+        }
+
         let models = HashMap::new();
 
+        let label = options.label_prefix.as_deref().unwrap_or_default();
+
         log::debug!("Creating world buffers");
-        let world_buffers = MultiModelViewerWorldBuffers::new(device);
+        let world_buffers = MultiModelViewerWorldBuffers::new_with_label(device, label);
 
         log::debug!("Creating preprocessor");
-        let preprocessor = Preprocessor::new_without_bind_group(device)?;
+        let preprocessor = Preprocessor::new_without_bind_group(
+            device,
+            PreprocessorCreateOptions {
+                depth_mapping: options.depth_mapping,
+                visible_bounds: options.visible_bounds,
+                label_prefix: options.label_prefix.clone(),
+                ..Default::default()
+            },
+        )?;
 
         log::debug!("Creating radix sorter");
-        let radix_sorter = RadixSorter::new_without_bind_groups(device);
+        let radix_sorter = RadixSorter::new_without_bind_groups_with_options(
+            device,
+            RadixSorterCreateOptions {
+                pipeline_cache: options.pipeline_cache.clone(),
+                label_prefix: options.label_prefix.clone(),
+            },
+        );
 
         log::debug!("Creating renderer");
-        let renderer =
-            Renderer::new_without_bind_group(device, texture_format, options.depth_stencil)?;
+        let renderer = Renderer::new_without_bind_group(
+            device,
+            texture_format,
+            RendererCreateOptions {
+                depth_stencil: options.depth_stencil,
+                primitive: options.primitive,
+                quad_mode: options.quad_mode,
+                pipeline_cache: options.pipeline_cache,
+                label_prefix: options.label_prefix.clone(),
+            },
+        )?;
 
         log::info!("Viewer created");
 
@@ -346,6 +731,13 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
             renderer,
 
             gaussians_buffer_usage: options.gaussians_buffer_usage,
+
+            label_prefix: label.to_string(),
+            solo: None,
+            #[cfg(feature = "viewer-selection")]
+            selection_layer_count: options.selection_layer_count.max(1),
+            #[cfg(feature = "viewer-selection")]
+            active_selection_layer: 0,
         })
     }
 
@@ -370,8 +762,35 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
         gaussians_buffer_usage: wgpu::BufferUsages,
         gaussians: &impl IterGaussian,
     ) -> Option<MultiModelViewerModel<G>> {
-        let gaussian_buffers =
-            MultiModelViewerGaussianBuffers::new_with(device, gaussians_buffer_usage, gaussians);
+        let label = self.label_prefix.as_str();
+        let mut gaussian_buffers = MultiModelViewerGaussianBuffers::new_with_label(
+            device,
+            label,
+            gaussians_buffer_usage,
+            gaussians,
+        );
+        if self.renderer.quad_mode() != RenderQuadMode::default() {
+            gaussian_buffers.indirect_args_buffer =
+                IndirectArgsBuffer::new_with_label_and_quad_mode(
+                    device,
+                    label,
+                    self.renderer.quad_mode(),
+                );
+        }
+        #[cfg(feature = "viewer-selection")]
+        if self.selection_layer_count != 1 {
+            gaussian_buffers.selection_layers_buffer =
+                selection::SelectionLayersBuffer::new_with_label(
+                    device,
+                    label,
+                    gaussian_buffers.gaussians_buffer.len() as u32,
+                    self.selection_layer_count,
+                );
+        }
+        if self.preprocessor.create_options().visible_bounds {
+            gaussian_buffers.visible_bounds_buffer =
+                Some(VisibleBoundsBuffer::new_with_label(device, label));
+        }
         let bind_groups = MultiModelViewerBindGroups::new(
             device,
             &self.preprocessor,
@@ -389,6 +808,39 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
         )
     }
 
+    /// Append Gaussians to the end of a model, e.g. for live capture.
+    ///
+    /// The model must be inserted with a gaussians buffer usage containing
+    /// [`wgpu::BufferUsages::COPY_SRC`], which is in the default
+    /// [`MultiModelViewerCreateOptions::gaussians_buffer_usage`], see
+    /// [`MultiModelViewerGaussianBuffers::append`].
+    pub fn append_to_model(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key: &K,
+        gaussians: &[Gaussian],
+    ) -> Result<(), MultiModelViewerAppendError> {
+        let model = self
+            .models
+            .get_mut(key)
+            .ok_or(MultiModelViewerAppendError::ModelNotFound)?;
+
+        model
+            .gaussian_buffers
+            .append_with_label(device, queue, &self.label_prefix, gaussians)?;
+        model.bind_groups = MultiModelViewerBindGroups::new(
+            device,
+            &self.preprocessor,
+            &self.radix_sorter,
+            &self.renderer,
+            &model.gaussian_buffers,
+            &self.world_buffers,
+        );
+
+        Ok(())
+    }
+
     /// Remove a model from the viewer.
     pub fn remove_model(&mut self, key: &K) -> Option<MultiModelViewerModel<G>> {
         self.models.remove(key)
@@ -442,7 +894,10 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
         Ok(())
     }
 
-    /// Update the Gaussian transform.
+    /// Update the shared Gaussian transform.
+    ///
+    /// This only affects models without a per-model Gaussian transform, see
+    /// [`MultiModelViewer::update_model_gaussian_transform`].
     pub fn update_gaussian_transform(
         &mut self,
         queue: &wgpu::Queue,
@@ -462,7 +917,10 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
         );
     }
 
-    /// Update the Gaussian transform with [`GaussianTransformPod`].
+    /// Update the shared Gaussian transform with [`GaussianTransformPod`].
+    ///
+    /// This only affects models without a per-model Gaussian transform, see
+    /// [`MultiModelViewer::update_model_gaussian_transform_with_pod`].
     pub fn update_gaussian_transform_with_pod(
         &mut self,
         queue: &wgpu::Queue,
@@ -472,21 +930,318 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
             .update_gaussian_transform_with_pod(queue, pod);
     }
 
+    /// Update the preprocessor settings.
+    pub fn update_preprocessor_settings(
+        &mut self,
+        queue: &wgpu::Queue,
+        settings: &PreprocessorSettings,
+    ) {
+        self.world_buffers
+            .update_preprocessor_settings(queue, settings);
+    }
+
+    /// Update the preprocessor settings with [`PreprocessorSettingsPod`].
+    pub fn update_preprocessor_settings_with_pod(
+        &mut self,
+        queue: &wgpu::Queue,
+        pod: &PreprocessorSettingsPod,
+    ) {
+        self.world_buffers
+            .update_preprocessor_settings_with_pod(queue, pod);
+    }
+
+    /// Update the renderer settings.
+    pub fn update_renderer_settings(&mut self, queue: &wgpu::Queue, settings: &RendererSettings) {
+        self.world_buffers.update_renderer_settings(queue, settings);
+    }
+
+    /// Update the renderer settings with [`RendererSettingsPod`].
+    pub fn update_renderer_settings_with_pod(
+        &mut self,
+        queue: &wgpu::Queue,
+        pod: &RendererSettingsPod,
+    ) {
+        self.world_buffers
+            .update_renderer_settings_with_pod(queue, pod);
+    }
+
+    /// Set the minimum opacity in `[0, 1]` of the Gaussians of all models to be rendered.
+    ///
+    /// See [`Viewer::set_min_opacity`] for more details.
+    pub fn set_min_opacity(&self, queue: &wgpu::Queue, min_opacity: f32) {
+        self.world_buffers
+            .preprocessor_settings_buffer
+            .update_min_opacity(queue, min_opacity);
+    }
+
+    /// Set the minimum size in pixels of the projected Gaussians of all models to be rendered.
+    ///
+    /// See [`Viewer::set_min_pixel_size`] for more details.
+    pub fn set_min_pixel_size(&self, queue: &wgpu::Queue, min_pixel_size: f32) {
+        self.world_buffers
+            .preprocessor_settings_buffer
+            .update_min_pixel_size(queue, min_pixel_size);
+    }
+
+    /// Set whether to apply ordered dithering to the colors decoded from Norm8 SH of all models.
+    ///
+    /// See [`Viewer::set_sh_dither`] for more details.
+    pub fn set_sh_dither(&self, queue: &wgpu::Queue, sh_dither: bool) {
+        self.world_buffers
+            .renderer_settings_buffer
+            .update_sh_dither(queue, sh_dither);
+    }
+
+    /// Set the clamping of the SH evaluated colors of all models.
+    ///
+    /// See [`Viewer::set_sh_clamp`] for more details.
+    pub fn set_sh_clamp(&self, queue: &wgpu::Queue, sh_clamp: ShClamp) {
+        self.world_buffers
+            .renderer_settings_buffer
+            .update_sh_clamp(queue, sh_clamp);
+    }
+
+    /// Set the distance fade of the Gaussians of all models.
+    ///
+    /// See [`Viewer::set_distance_fade`] for more details.
+    pub fn set_distance_fade(&self, queue: &wgpu::Queue, start: f32, end: f32) {
+        self.world_buffers
+            .renderer_settings_buffer
+            .update_distance_fade(queue, start, end);
+    }
+
+    /// Update the Gaussian transform of a single model.
+    ///
+    /// After this call, the model no longer follows the shared Gaussian transform until
+    /// [`MultiModelViewer::reset_model_gaussian_transform`] is called.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_model_gaussian_transform(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key: &K,
+        size: f32,
+        display_mode: GaussianDisplayMode,
+        sh_deg: GaussianShDegree,
+        no_sh0: bool,
+        max_std_dev: GaussianMaxStdDev,
+    ) -> Result<(), MultiModelViewerAccessError> {
+        self.update_model_gaussian_transform_with_pod(
+            device,
+            queue,
+            key,
+            &GaussianTransformPod::new(size, display_mode, sh_deg, no_sh0, max_std_dev),
+        )
+    }
+
+    /// Update the Gaussian transform of a single model with [`GaussianTransformPod`].
+    ///
+    /// After this call, the model no longer follows the shared Gaussian transform until
+    /// [`MultiModelViewer::reset_model_gaussian_transform`] is called.
+    pub fn update_model_gaussian_transform_with_pod(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key: &K,
+        pod: &GaussianTransformPod,
+    ) -> Result<(), MultiModelViewerAccessError> {
+        let model = self
+            .models
+            .get_mut(key)
+            .ok_or(MultiModelViewerAccessError::ModelNotFound)?;
+
+        if model
+            .gaussian_buffers
+            .update_gaussian_transform_with_pod(device, queue, pod)
+        {
+            model.bind_groups = MultiModelViewerBindGroups::new(
+                device,
+                &self.preprocessor,
+                &self.radix_sorter,
+                &self.renderer,
+                &model.gaussian_buffers,
+                &self.world_buffers,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Make a model follow the shared Gaussian transform again.
+    pub fn reset_model_gaussian_transform(
+        &mut self,
+        device: &wgpu::Device,
+        key: &K,
+    ) -> Result<(), MultiModelViewerAccessError> {
+        let model = self
+            .models
+            .get_mut(key)
+            .ok_or(MultiModelViewerAccessError::ModelNotFound)?;
+
+        if model
+            .gaussian_buffers
+            .gaussian_transform_buffer
+            .take()
+            .is_some()
+        {
+            model.bind_groups = MultiModelViewerBindGroups::new(
+                device,
+                &self.preprocessor,
+                &self.radix_sorter,
+                &self.renderer,
+                &model.gaussian_buffers,
+                &self.world_buffers,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Download the world space bounds of the Gaussians of a model visible in the last render.
+    ///
+    /// See [`Viewer::download_visible_bounds`] for more details. Returns
+    /// [`MultiModelViewerDownloadError::VisibleBoundsDisabled`] if the viewer is not created with
+    /// [`MultiModelViewerCreateOptions::visible_bounds`].
+    pub async fn download_model_visible_bounds(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key: &K,
+    ) -> Result<Option<(Vec3, Vec3)>, MultiModelViewerDownloadError> {
+        let model = self
+            .models
+            .get(key)
+            .ok_or(MultiModelViewerDownloadError::ModelNotFound)?;
+
+        Ok(model
+            .gaussian_buffers
+            .visible_bounds_buffer
+            .as_ref()
+            .ok_or(MultiModelViewerDownloadError::VisibleBoundsDisabled)?
+            .download_bounds(device, queue)
+            .await?)
+    }
+
+    /// Deselect all Gaussians of a model.
+    ///
+    /// The selection buffer is cleared in the `encoder`, so it is ordered with the other commands
+    /// of the frame.
+    #[cfg(feature = "viewer-selection")]
+    pub fn clear_model_selection(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        key: &K,
+    ) -> Result<(), MultiModelViewerAccessError> {
+        let model = self
+            .models
+            .get(key)
+            .ok_or(MultiModelViewerAccessError::ModelNotFound)?;
+
+        encoder.clear_buffer(model.gaussian_buffers.selection_buffer.buffer(), 0, None);
+
+        Ok(())
+    }
+
+    /// Get the number of selection layers of each model.
+    ///
+    /// This is set by [`MultiModelViewerCreateOptions::selection_layer_count`].
+    #[cfg(feature = "viewer-selection")]
+    pub fn selection_layer_count(&self) -> u32 {
+        self.selection_layer_count
+    }
+
+    /// Get the index of the active selection layer of all models.
+    #[cfg(feature = "viewer-selection")]
+    pub fn active_selection_layer(&self) -> u32 {
+        self.active_selection_layer
+    }
+
+    /// Set the active selection layer of all models.
+    ///
+    /// See [`Viewer::set_active_selection_layer`], the layer is switched for every model in the
+    /// `encoder`. Models inserted later start with the active layer held by their selection
+    /// buffer.
+    #[cfg(feature = "viewer-selection")]
+    pub fn set_active_selection_layer(
+        &mut self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        layer: u32,
+    ) -> Result<(), SelectionLayerError> {
+        if layer >= self.selection_layer_count {
+            return Err(SelectionLayerError::LayerOutOfBounds {
+                layer,
+                layer_count: self.selection_layer_count,
+            });
+        }
+
+        for model in self.models.values() {
+            model
+                .gaussian_buffers
+                .selection_layers_buffer
+                .switch_active(
+                    encoder,
+                    &model.gaussian_buffers.selection_buffer,
+                    self.active_selection_layer,
+                    layer,
+                );
+        }
+        self.world_buffers
+            .selection_highlight_buffer
+            .update_active_layer(queue, layer);
+        self.active_selection_layer = layer;
+
+        Ok(())
+    }
+
+    /// Update the highlight colors of the selection layers of all models.
+    ///
+    /// See [`Viewer::update_selection_highlight`].
+    #[cfg(feature = "viewer-selection")]
+    pub fn update_selection_highlight(&self, queue: &wgpu::Queue, colors: &[Vec4]) {
+        self.world_buffers
+            .selection_highlight_buffer
+            .update_colors(queue, colors);
+    }
+
+    /// Set the only model to render, or [`None`] to render all models in `keys` again.
+    ///
+    /// The solo model takes precedence over the `keys` of [`MultiModelViewer::render`], i.e. it is
+    /// rendered alone even if it is not in `keys`, and the other models stay loaded.
+    pub fn set_solo(&mut self, key: Option<K>) {
+        self.solo = key;
+    }
+
+    /// Get the key of the only model to render.
+    pub fn solo(&self) -> Option<&K> {
+        self.solo.as_ref()
+    }
+
     /// Render the viewer.
+    ///
+    /// If a solo model is set by [`MultiModelViewer::set_solo`], only it is rendered and `keys`
+    /// is ignored.
     pub fn render(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         texture_view: &wgpu::TextureView,
         keys: &[&K],
     ) -> Result<(), MultiModelViewerAccessError> {
-        let models = keys
-            .iter()
-            .map(|key| {
+        let models = match &self.solo {
+            Some(solo) => vec![
                 self.models
-                    .get(key)
-                    .ok_or(MultiModelViewerAccessError::ModelNotFound)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+                    .get(solo)
+                    .ok_or(MultiModelViewerAccessError::ModelNotFound)?,
+            ],
+            None => keys
+                .iter()
+                .map(|key| {
+                    self.models
+                        .get(key)
+                        .ok_or(MultiModelViewerAccessError::ModelNotFound)
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
 
         for model in models.iter() {
             self.preprocessor.preprocess(
@@ -504,7 +1259,10 @@ impl<G: GaussianPod, K: Hash + std::cmp::Eq> MultiModelViewer<G, K> {
 
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Multi Model Viewer Render Pass"),
+                label: Some(&prefixed_label(
+                    &self.label_prefix,
+                    "Multi Model Viewer Render Pass",
+                )),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                     view: texture_view,
                     resolve_target: None,
@@ -0,0 +1,45 @@
+use glam::*;
+
+use crate::core::{Gaussians, IterGaussian};
+
+/// Compute the axis aligned bounding box of the Gaussian positions.
+///
+/// Returns the minimum and maximum corners, or [`None`] if there is no Gaussian.
+pub fn bounding_box(gaussians: &impl IterGaussian) -> Option<(Vec3, Vec3)> {
+    gaussians.iter_gaussian().fold(None, |bounds, gaussian| {
+        Some(match bounds {
+            Some((min, max)) => (gaussian.pos.min(min), gaussian.pos.max(max)),
+            None => (gaussian.pos, gaussian.pos),
+        })
+    })
+}
+
+/// Recenter the Gaussians to the origin and scale them to fit in a unit cube.
+///
+/// The center of the [`bounding_box`] is moved to the origin, and the longest side is scaled to
+/// 1, so the Gaussian positions are in `[-0.5, 0.5]`. The scales of the Gaussians are scaled
+/// accordingly, and the source representation is kept. If all Gaussians are at the same
+/// position, they are only recentered.
+///
+/// Returns the applied transform, its inverse maps the normalized positions back to the original
+/// coordinates. [`Mat4::IDENTITY`] is returned if there is no Gaussian.
+pub fn normalize_gaussians(gaussians: &mut Gaussians) -> Mat4 {
+    let Some((min, max)) = bounding_box(gaussians) else {
+        return Mat4::IDENTITY;
+    };
+
+    let center = (min + max) * 0.5;
+    let extent = (max - min).max_element();
+    let scale = if extent > 0.0 { 1.0 / extent } else { 1.0 };
+
+    *gaussians = Gaussians::from_gaussians_iter(
+        gaussians.iter_gaussian().map(|mut gaussian| {
+            gaussian.pos = (gaussian.pos - center) * scale;
+            gaussian.scale *= scale;
+            gaussian
+        }),
+        gaussians.source(),
+    );
+
+    Mat4::from_scale(Vec3::splat(scale)) * Mat4::from_translation(-center)
+}
@@ -1,16 +1,78 @@
 use crate::{
-    CameraBuffer, GaussiansDepthBuffer, IndirectArgsBuffer, IndirectIndicesBuffer,
-    PreprocessorCreateError, RadixSortIndirectArgsBuffer,
+    CameraBuffer, Conic2dBuffer, GaussiansDepthBuffer, GaussiansLinearDepthBuffer,
+    IndirectArgsBuffer, IndirectIndicesBuffer, PreprocessorCreateError, PreprocessorSettingsBuffer,
+    RadixSortIndirectArgsBuffer, RendererSettingsBuffer, SortKeyBuffer, VisibleBoundsBuffer,
     core::{
         BufferWrapper, ComputeBundle, ComputeBundleBuilder, GaussianPod, GaussianTransformBuffer,
         GaussiansBuffer, ModelTransformBuffer,
     },
-    wesl_utils,
+    custom_shader, prefixed_label, wesl_utils,
 };
 
 #[cfg(feature = "viewer-selection")]
 use crate::{editor::SelectionBuffer, selection};
 
+/// The mapping from the view distance to the depth sort key in [`Preprocessor`].
+///
+/// The keys in [`GaussiansDepthBuffer`] are 32-bit floats, so their precision is limited to a
+/// 24-bit mantissa. [`DepthMapping::Linear`] uses the NDC depth, which is hyperbolic in the view
+/// distance and computed close to 1.0, so splats far away from the near plane share a few distinct
+/// keys and may be sorted in the wrong order. [`DepthMapping::Logarithmic`] uses the logarithm of
+/// the view distance instead, which keeps a roughly constant relative precision of about `1e-5`
+/// across the whole range, independent of the near and far planes.
+///
+/// For reverse-Z projections, where the NDC depth is 1 at the near plane and 0 at the far plane,
+/// use [`DepthMapping::LinearReverseZ`] instead of [`DepthMapping::Linear`] to keep the back to
+/// front order, see also [`Camera::reverse_z`](crate::Camera::reverse_z).
+///
+/// [`DepthMapping::Custom`] does not depend on the camera at all, the keys are read from a
+/// [`SortKeyBuffer`] set by [`Preprocessor::set_sort_keys`] instead, which allows arbitrary
+/// orderings such as by opacity or by a data attribute.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DepthMapping {
+    /// Map the NDC depth linearly to the key.
+    #[default]
+    Linear,
+    /// Map the logarithm of the view distance to the key.
+    Logarithmic,
+    /// Map the NDC depth of a reverse-Z projection linearly to the key.
+    LinearReverseZ,
+    /// Read the key from a [`SortKeyBuffer`].
+    ///
+    /// The keys must be non-negative and less than 2.0, the key of the padding sorted after the
+    /// visible Gaussians, otherwise the Gaussians may be sorted past the drawn range and not be
+    /// drawn. This is not supported by `MultiModelViewer`.
+    Custom,
+}
+
+/// The options to create a [`Preprocessor`].
+///
+/// The optional outputs are compiled out of the shader and left out of the bind group layout
+/// unless enabled, so they cost nothing when unused.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct PreprocessorCreateOptions {
+    /// The depth mapping, see [`DepthMapping`].
+    pub depth_mapping: DepthMapping,
+    /// Whether to write the bounds of the visible Gaussians to a [`VisibleBoundsBuffer`].
+    pub visible_bounds: bool,
+    /// Whether to write the linear view space depth of every Gaussian to a
+    /// [`GaussiansLinearDepthBuffer`].
+    ///
+    /// The depth is in world units along the forward direction of the camera, i.e. positive for
+    /// Gaussians in front of the camera. It is indexed by the Gaussian index, not the culled index,
+    /// so culled Gaussians also have their depth written.
+    pub linear_depth: bool,
+    /// Whether to write the projected 2D conic of every Gaussian to a [`Conic2dBuffer`].
+    ///
+    /// The 2D covariance is regularized with the epsilon of the
+    /// [`Cov2dRegularization`](crate::Cov2dRegularization) in a [`RendererSettingsBuffer`], the
+    /// same as the [`Renderer`](crate::Renderer). It is indexed by the Gaussian index, not the
+    /// culled index, so culled Gaussians also have their conic written.
+    pub conic2d: bool,
+    /// The optional prefix of the pipeline and bind group labels.
+    pub label_prefix: Option<String>,
+}
+
 /// Preprocessor to preprocess the Gaussians.
 ///
 /// It computes the depth for [`RadixSorter`](crate::RadixSorter) and do frustum culling.
@@ -21,6 +83,12 @@ pub struct Preprocessor<G: GaussianPod, B = wgpu::BindGroup> {
     bind_group_layout: wgpu::BindGroupLayout,
     /// The bind group.
     bind_group: B,
+    /// The sort key bind group layout.
+    sort_key_bind_group_layout: wgpu::BindGroupLayout,
+    /// The sort key bind group, only used with [`DepthMapping::Custom`].
+    sort_key_bind_group: Option<wgpu::BindGroup>,
+    /// The options.
+    options: PreprocessorCreateOptions,
     /// The pre preprocess bundle.
     pre_bundle: ComputeBundle<()>,
     /// The preprocess bundle.
@@ -33,6 +101,10 @@ pub struct Preprocessor<G: GaussianPod, B = wgpu::BindGroup> {
 
 impl<G: GaussianPod, B> Preprocessor<G, B> {
     /// Create the bind group.
+    ///
+    /// The optional outputs must be [`Some`] exactly when enabled in the
+    /// [`PreprocessorCreateOptions`] of this preprocessor, where `conics` and `renderer_settings`
+    /// are both for [`PreprocessorCreateOptions::conic2d`].
     #[allow(clippy::too_many_arguments)]
     pub fn create_bind_group(
         &self,
@@ -45,12 +117,18 @@ impl<G: GaussianPod, B> Preprocessor<G, B> {
         radix_sort_indirect_args: &RadixSortIndirectArgsBuffer,
         indirect_indices: &IndirectIndicesBuffer,
         gaussians_depth: &GaussiansDepthBuffer,
+        preprocessor_settings: &PreprocessorSettingsBuffer,
+        visible_bounds: Option<&VisibleBoundsBuffer>,
+        gaussians_linear_depth: Option<&GaussiansLinearDepthBuffer>,
+        conics: Option<&Conic2dBuffer>,
+        renderer_settings: Option<&RendererSettingsBuffer>,
         #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
         #[cfg(feature = "viewer-selection")]
         invert_selection: &selection::PreprocessorInvertSelectionBuffer,
     ) -> wgpu::BindGroup {
         Preprocessor::create_bind_group_static(
             device,
+            self.options.label_prefix.as_deref().unwrap_or_default(),
             &self.bind_group_layout,
             camera,
             model_transform,
@@ -60,6 +138,11 @@ impl<G: GaussianPod, B> Preprocessor<G, B> {
             radix_sort_indirect_args,
             indirect_indices,
             gaussians_depth,
+            preprocessor_settings,
+            visible_bounds,
+            gaussians_linear_depth,
+            conics,
+            renderer_settings,
             #[cfg(feature = "viewer-selection")]
             selection,
             #[cfg(feature = "viewer-selection")]
@@ -67,6 +150,27 @@ impl<G: GaussianPod, B> Preprocessor<G, B> {
         )
     }
 
+    /// Create the sort key bind group for [`DepthMapping::Custom`].
+    pub fn create_sort_key_bind_group(
+        &self,
+        device: &wgpu::Device,
+        sort_keys: &SortKeyBuffer,
+    ) -> wgpu::BindGroup {
+        let label = self.options.label_prefix.as_deref().unwrap_or_default();
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(prefixed_label(label, "Preprocessor Sort Key Bind Group").as_str()),
+            layout: &self.sort_key_bind_group_layout,
+            entries: &[
+                // Sort key storage buffer
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: sort_keys.buffer().as_entire_binding(),
+                },
+            ],
+        })
+    }
+
     /// Get the number of invocations in one workgroup.
     pub fn workgroup_size(&self) -> u32 {
         self.bundle.workgroup_size()
@@ -77,6 +181,45 @@ impl<G: GaussianPod, B> Preprocessor<G, B> {
         &self.bind_group_layout
     }
 
+    /// Get the sort key bind group layout.
+    pub fn sort_key_bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.sort_key_bind_group_layout
+    }
+
+    /// Get the depth mapping.
+    pub fn depth_mapping(&self) -> DepthMapping {
+        self.options.depth_mapping
+    }
+
+    /// Get the options the preprocessor is created with.
+    pub fn create_options(&self) -> PreprocessorCreateOptions {
+        self.options.clone()
+    }
+
+    /// Dispatch the pre, main, and post preprocess bundles.
+    ///
+    /// The sort key bind group is only bound with [`DepthMapping::Custom`].
+    fn preprocess_with_bind_groups(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group: &wgpu::BindGroup,
+        sort_key_bind_group: Option<&wgpu::BindGroup>,
+        gaussian_count: u32,
+    ) {
+        let bind_groups = std::iter::once(bind_group)
+            .chain(
+                sort_key_bind_group.filter(|_| self.options.depth_mapping == DepthMapping::Custom),
+            )
+            .collect::<Vec<_>>();
+
+        self.pre_bundle.dispatch(encoder, 1, bind_groups.clone());
+
+        self.bundle
+            .dispatch(encoder, gaussian_count, bind_groups.clone());
+
+        self.post_bundle.dispatch(encoder, 1, bind_groups);
+    }
+
     /// Get the pre preprocess bundle.
     pub fn pre_bundle(&self) -> &ComputeBundle<()> {
         &self.pre_bundle
@@ -193,10 +336,21 @@ impl<G: GaussianPod> Preprocessor<G> {
                     },
                     count: None,
                 },
+                // Preprocessor settings uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 8,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
                 // Selection buffer
                 #[cfg(feature = "viewer-selection")]
                 wgpu::BindGroupLayoutEntry {
-                    binding: 8,
+                    binding: 9,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage { read_only: true },
@@ -208,7 +362,7 @@ impl<G: GaussianPod> Preprocessor<G> {
                 // Invert selection buffer
                 #[cfg(feature = "viewer-selection")]
                 wgpu::BindGroupLayoutEntry {
-                    binding: 9,
+                    binding: 10,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
@@ -220,10 +374,87 @@ impl<G: GaussianPod> Preprocessor<G> {
             ],
         };
 
+    /// The visible bounds bind group layout entry, appended to
+    /// [`Preprocessor::BIND_GROUP_LAYOUT_DESCRIPTOR`] with
+    /// [`PreprocessorCreateOptions::visible_bounds`].
+    pub const VISIBLE_BOUNDS_BIND_GROUP_LAYOUT_ENTRY: wgpu::BindGroupLayoutEntry =
+        wgpu::BindGroupLayoutEntry {
+            binding: 11,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+    /// The linear depth bind group layout entry, appended to
+    /// [`Preprocessor::BIND_GROUP_LAYOUT_DESCRIPTOR`] with
+    /// [`PreprocessorCreateOptions::linear_depth`].
+    pub const LINEAR_DEPTH_BIND_GROUP_LAYOUT_ENTRY: wgpu::BindGroupLayoutEntry =
+        wgpu::BindGroupLayoutEntry {
+            binding: 12,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+    /// The 2D conic bind group layout entries, appended to
+    /// [`Preprocessor::BIND_GROUP_LAYOUT_DESCRIPTOR`] with [`PreprocessorCreateOptions::conic2d`].
+    pub const CONIC2D_BIND_GROUP_LAYOUT_ENTRIES: [wgpu::BindGroupLayoutEntry; 2] = [
+        // Conic 2D storage buffer
+        wgpu::BindGroupLayoutEntry {
+            binding: 13,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        // Renderer settings uniform buffer
+        wgpu::BindGroupLayoutEntry {
+            binding: 14,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+    ];
+
+    /// The sort key bind group layout descriptor for [`DepthMapping::Custom`].
+    pub const SORT_KEY_BIND_GROUP_LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> =
+        wgpu::BindGroupLayoutDescriptor {
+            label: Some("Preprocessor Sort Key Bind Group Layout"),
+            entries: &[
+                // Sort key storage buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        };
+
     /// Create a new preprocessor.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         device: &wgpu::Device,
+        options: PreprocessorCreateOptions,
         camera: &CameraBuffer,
         model_transform: &ModelTransformBuffer,
         gaussian_transform: &GaussianTransformBuffer,
@@ -232,6 +463,122 @@ impl<G: GaussianPod> Preprocessor<G> {
         radix_sort_indirect_args: &RadixSortIndirectArgsBuffer,
         indirect_indices: &IndirectIndicesBuffer,
         gaussians_depth: &GaussiansDepthBuffer,
+        preprocessor_settings: &PreprocessorSettingsBuffer,
+        visible_bounds: Option<&VisibleBoundsBuffer>,
+        gaussians_linear_depth: Option<&GaussiansLinearDepthBuffer>,
+        conics: Option<&Conic2dBuffer>,
+        renderer_settings: Option<&RendererSettingsBuffer>,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")]
+        invert_selection: &selection::PreprocessorInvertSelectionBuffer,
+    ) -> Result<Self, PreprocessorCreateError> {
+        Preprocessor::new_impl(
+            device,
+            options,
+            None,
+            camera,
+            model_transform,
+            gaussian_transform,
+            gaussians,
+            indirect_args,
+            radix_sort_indirect_args,
+            indirect_indices,
+            gaussians_depth,
+            preprocessor_settings,
+            visible_bounds,
+            gaussians_linear_depth,
+            conics,
+            renderer_settings,
+            #[cfg(feature = "viewer-selection")]
+            selection,
+            #[cfg(feature = "viewer-selection")]
+            invert_selection,
+        )
+    }
+
+    /// Create a new preprocessor with a custom WGSL shader instead of the built-in shader.
+    ///
+    /// This is for prototyping a fork of the preprocess shader while reusing the buffers and
+    /// passes of this crate, [`Preprocessor::compiled_wgsl`] is a good starting point of the fork.
+    /// The shader must honor the following contract, which is validated before creating the
+    /// pipelines:
+    ///
+    /// - The `pre`, `main`, and `post` compute entry points, dispatched in order with 1,
+    ///   `ceil(gaussian_count / workgroup_size)`, and 1 workgroups.
+    /// - An `override workgroup_size: u32;`, set to [`Preprocessor::workgroup_size`].
+    /// - Every binding in `@group(0)`, at a binding number of
+    ///   [`Preprocessor::bind_group_layout_entries`] with a compatible type, and with
+    ///   [`DepthMapping::Custom`] in `@group(1)`, at a binding number of
+    ///   [`Preprocessor::SORT_KEY_BIND_GROUP_LAYOUT_DESCRIPTOR`]. Unused bindings may be omitted.
+    ///
+    /// The shader is responsible for writing the indirect arguments, indirect indices, and depths
+    /// read by [`RadixSorter`](crate::RadixSorter) and [`Renderer`](crate::Renderer). The layouts
+    /// of the buffer contents are not validated, they should match the built-in shader.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_wgsl(
+        device: &wgpu::Device,
+        options: PreprocessorCreateOptions,
+        wgsl: &str,
+        camera: &CameraBuffer,
+        model_transform: &ModelTransformBuffer,
+        gaussian_transform: &GaussianTransformBuffer,
+        gaussians: &GaussiansBuffer<G>,
+        indirect_args: &IndirectArgsBuffer,
+        radix_sort_indirect_args: &RadixSortIndirectArgsBuffer,
+        indirect_indices: &IndirectIndicesBuffer,
+        gaussians_depth: &GaussiansDepthBuffer,
+        preprocessor_settings: &PreprocessorSettingsBuffer,
+        visible_bounds: Option<&VisibleBoundsBuffer>,
+        gaussians_linear_depth: Option<&GaussiansLinearDepthBuffer>,
+        conics: Option<&Conic2dBuffer>,
+        renderer_settings: Option<&RendererSettingsBuffer>,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")]
+        invert_selection: &selection::PreprocessorInvertSelectionBuffer,
+    ) -> Result<Self, PreprocessorCreateError> {
+        Preprocessor::new_impl(
+            device,
+            options,
+            Some(wgsl),
+            camera,
+            model_transform,
+            gaussian_transform,
+            gaussians,
+            indirect_args,
+            radix_sort_indirect_args,
+            indirect_indices,
+            gaussians_depth,
+            preprocessor_settings,
+            visible_bounds,
+            gaussians_linear_depth,
+            conics,
+            renderer_settings,
+            #[cfg(feature = "viewer-selection")]
+            selection,
+            #[cfg(feature = "viewer-selection")]
+            invert_selection,
+        )
+    }
+
+    /// Create a new preprocessor with an optional custom shader.
+    #[allow(clippy::too_many_arguments)]
+    fn new_impl(
+        device: &wgpu::Device,
+        options: PreprocessorCreateOptions,
+        wgsl: Option<&str>,
+        camera: &CameraBuffer,
+        model_transform: &ModelTransformBuffer,
+        gaussian_transform: &GaussianTransformBuffer,
+        gaussians: &GaussiansBuffer<G>,
+        indirect_args: &IndirectArgsBuffer,
+        radix_sort_indirect_args: &RadixSortIndirectArgsBuffer,
+        indirect_indices: &IndirectIndicesBuffer,
+        gaussians_depth: &GaussiansDepthBuffer,
+        preprocessor_settings: &PreprocessorSettingsBuffer,
+        visible_bounds: Option<&VisibleBoundsBuffer>,
+        gaussians_linear_depth: Option<&GaussiansLinearDepthBuffer>,
+        conics: Option<&Conic2dBuffer>,
+        renderer_settings: Option<&RendererSettingsBuffer>,
         #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
         #[cfg(feature = "viewer-selection")]
         invert_selection: &selection::PreprocessorInvertSelectionBuffer,
@@ -245,7 +592,7 @@ impl<G: GaussianPod> Preprocessor<G> {
             });
         }
 
-        let this = Preprocessor::new_without_bind_group(device)?;
+        let this = Preprocessor::new_without_bind_group_impl(device, options, wgsl)?;
 
         log::debug!("Creating preprocessor bind group");
         let bind_group = this.create_bind_group(
@@ -258,6 +605,11 @@ impl<G: GaussianPod> Preprocessor<G> {
             radix_sort_indirect_args,
             indirect_indices,
             gaussians_depth,
+            preprocessor_settings,
+            visible_bounds,
+            gaussians_linear_depth,
+            conics,
+            renderer_settings,
             #[cfg(feature = "viewer-selection")]
             selection,
             #[cfg(feature = "viewer-selection")]
@@ -267,6 +619,9 @@ impl<G: GaussianPod> Preprocessor<G> {
         Ok(Self {
             bind_group_layout: this.bind_group_layout,
             bind_group,
+            sort_key_bind_group_layout: this.sort_key_bind_group_layout,
+            sort_key_bind_group: None,
+            options: this.options,
             pre_bundle: this.pre_bundle,
             bundle: this.bundle,
             post_bundle: this.post_bundle,
@@ -279,20 +634,85 @@ impl<G: GaussianPod> Preprocessor<G> {
         &self.bind_group
     }
 
+    /// Get the sort key bind group.
+    pub fn sort_key_bind_group(&self) -> Option<&wgpu::BindGroup> {
+        self.sort_key_bind_group.as_ref()
+    }
+
+    /// Set the sort keys for [`DepthMapping::Custom`].
+    ///
+    /// This must be called before [`Preprocessor::preprocess`] with [`DepthMapping::Custom`], and
+    /// the `sort_keys` should have the same length as the Gaussians, with the keys non-negative
+    /// and less than 2.0. It has no effect with the other depth mappings.
+    pub fn set_sort_keys(&mut self, device: &wgpu::Device, sort_keys: &SortKeyBuffer) {
+        self.sort_key_bind_group = Some(self.create_sort_key_bind_group(device, sort_keys));
+    }
+
+    /// Compile the preprocess shader to WGSL without creating the pipelines.
+    ///
+    /// This uses the same features and resolver as [`Preprocessor::new`], which is useful for
+    /// debugging the shader of a [`GaussianPod`] configuration.
+    pub fn compiled_wgsl(options: &PreprocessorCreateOptions) -> Result<String, wesl::Error> {
+        let main_shader: wesl::ModulePath = Preprocessor::<G>::MAIN_SHADER
+            .parse()
+            .expect("preprocess module path");
+
+        Ok(wesl::compile_sourcemap(
+            &main_shader,
+            &wesl_utils::resolver(),
+            &wesl::NoMangler,
+            &Preprocessor::<G>::wesl_compile_options(options),
+        )?
+        .to_string())
+    }
+
     /// Preprocess the Gaussians.
     pub fn preprocess(&self, encoder: &mut wgpu::CommandEncoder, gaussian_count: u32) {
-        self.pre_bundle.dispatch(encoder, 1, [&self.bind_group]);
-
-        self.bundle
-            .dispatch(encoder, gaussian_count, [&self.bind_group]);
+        self.preprocess_with_bind_groups(
+            encoder,
+            &self.bind_group,
+            self.sort_key_bind_group.as_ref(),
+            gaussian_count,
+        );
+    }
 
-        self.post_bundle.dispatch(encoder, 1, [&self.bind_group]);
+    /// Get the WESL compile options.
+    fn wesl_compile_options(options: &PreprocessorCreateOptions) -> wesl::CompileOptions {
+        wesl::CompileOptions {
+            features: wesl::Features {
+                flags: G::features()
+                    .into_iter()
+                    .chain([
+                        ("selection_buffer", cfg!(feature = "viewer-selection")),
+                        (
+                            "depth_mapping_logarithmic",
+                            options.depth_mapping == DepthMapping::Logarithmic,
+                        ),
+                        (
+                            "depth_mapping_linear_reverse_z",
+                            options.depth_mapping == DepthMapping::LinearReverseZ,
+                        ),
+                        (
+                            "depth_mapping_custom",
+                            options.depth_mapping == DepthMapping::Custom,
+                        ),
+                        ("visible_bounds", options.visible_bounds),
+                        ("linear_depth", options.linear_depth),
+                        ("conic2d", options.conic2d),
+                    ])
+                    .map(|(k, v)| (k.to_string(), v.into()))
+                    .collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
     }
 
     /// Create the bind group statically.
     #[allow(clippy::too_many_arguments)]
     fn create_bind_group_static(
         device: &wgpu::Device,
+        label: &str,
         bind_group_layout: &wgpu::BindGroupLayout,
         camera: &CameraBuffer,
         model_transform: &ModelTransformBuffer,
@@ -302,69 +722,142 @@ impl<G: GaussianPod> Preprocessor<G> {
         radix_sort_indirect_args: &RadixSortIndirectArgsBuffer,
         indirect_indices: &IndirectIndicesBuffer,
         gaussians_depth: &GaussiansDepthBuffer,
+        preprocessor_settings: &PreprocessorSettingsBuffer,
+        visible_bounds: Option<&VisibleBoundsBuffer>,
+        gaussians_linear_depth: Option<&GaussiansLinearDepthBuffer>,
+        conics: Option<&Conic2dBuffer>,
+        renderer_settings: Option<&RendererSettingsBuffer>,
         #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
         #[cfg(feature = "viewer-selection")]
         invert_selection: &selection::PreprocessorInvertSelectionBuffer,
     ) -> wgpu::BindGroup {
+        let entries = [
+            // Camera uniform buffer
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera.buffer().as_entire_binding(),
+            },
+            // Model transform uniform buffer
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: model_transform.buffer().as_entire_binding(),
+            },
+            // Gaussian transform uniform buffer
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: gaussian_transform.buffer().as_entire_binding(),
+            },
+            // Gaussian storage buffer
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: gaussians.buffer().as_entire_binding(),
+            },
+            // Indirect args storage buffer
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: indirect_args.buffer().as_entire_binding(),
+            },
+            // Radix sort indirect args storage buffer
+            wgpu::BindGroupEntry {
+                binding: 5,
+                resource: radix_sort_indirect_args.buffer().as_entire_binding(),
+            },
+            // Indirect indices storage buffer
+            wgpu::BindGroupEntry {
+                binding: 6,
+                resource: indirect_indices.buffer().as_entire_binding(),
+            },
+            // Gaussians depth storage buffer
+            wgpu::BindGroupEntry {
+                binding: 7,
+                resource: gaussians_depth.buffer().as_entire_binding(),
+            },
+            // Preprocessor settings uniform buffer
+            wgpu::BindGroupEntry {
+                binding: 8,
+                resource: preprocessor_settings.buffer().as_entire_binding(),
+            },
+            // Selection buffer
+            #[cfg(feature = "viewer-selection")]
+            wgpu::BindGroupEntry {
+                binding: 9,
+                resource: selection.buffer().as_entire_binding(),
+            },
+            // Invert selection buffer
+            #[cfg(feature = "viewer-selection")]
+            wgpu::BindGroupEntry {
+                binding: 10,
+                resource: invert_selection.buffer().as_entire_binding(),
+            },
+        ]
+        .into_iter()
+        .chain(
+            // Visible bounds storage buffer
+            visible_bounds.map(|visible_bounds| wgpu::BindGroupEntry {
+                binding: 11,
+                resource: visible_bounds.buffer().as_entire_binding(),
+            }),
+        )
+        .chain(
+            // Gaussians linear depth storage buffer
+            gaussians_linear_depth.map(|gaussians_linear_depth| wgpu::BindGroupEntry {
+                binding: 12,
+                resource: gaussians_linear_depth.buffer().as_entire_binding(),
+            }),
+        )
+        .chain(
+            // Conic 2D storage buffer
+            conics.map(|conics| wgpu::BindGroupEntry {
+                binding: 13,
+                resource: conics.buffer().as_entire_binding(),
+            }),
+        )
+        .chain(
+            // Renderer settings uniform buffer
+            renderer_settings.map(|renderer_settings| wgpu::BindGroupEntry {
+                binding: 14,
+                resource: renderer_settings.buffer().as_entire_binding(),
+            }),
+        )
+        .collect::<Vec<_>>();
+
         device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Preprocessor Bind Group"),
+            label: Some(prefixed_label(label, "Preprocessor Bind Group").as_str()),
             layout: bind_group_layout,
-            entries: &[
-                // Camera uniform buffer
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: camera.buffer().as_entire_binding(),
-                },
-                // Model transform uniform buffer
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: model_transform.buffer().as_entire_binding(),
-                },
-                // Gaussian transform uniform buffer
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: gaussian_transform.buffer().as_entire_binding(),
-                },
-                // Gaussian storage buffer
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: gaussians.buffer().as_entire_binding(),
-                },
-                // Indirect args storage buffer
-                wgpu::BindGroupEntry {
-                    binding: 4,
-                    resource: indirect_args.buffer().as_entire_binding(),
-                },
-                // Radix sort indirect args storage buffer
-                wgpu::BindGroupEntry {
-                    binding: 5,
-                    resource: radix_sort_indirect_args.buffer().as_entire_binding(),
-                },
-                // Indirect indices storage buffer
-                wgpu::BindGroupEntry {
-                    binding: 6,
-                    resource: indirect_indices.buffer().as_entire_binding(),
-                },
-                // Gaussians depth storage buffer
-                wgpu::BindGroupEntry {
-                    binding: 7,
-                    resource: gaussians_depth.buffer().as_entire_binding(),
-                },
-                // Selection buffer
-                #[cfg(feature = "viewer-selection")]
-                wgpu::BindGroupEntry {
-                    binding: 8,
-                    resource: selection.buffer().as_entire_binding(),
-                },
-                // Invert selection buffer
-                #[cfg(feature = "viewer-selection")]
-                wgpu::BindGroupEntry {
-                    binding: 9,
-                    resource: invert_selection.buffer().as_entire_binding(),
-                },
-            ],
+            entries: &entries,
         })
     }
+
+    /// Get the bind group layout entries with the optional outputs of `options`.
+    ///
+    /// This is [`Preprocessor::BIND_GROUP_LAYOUT_DESCRIPTOR`] followed by the entries of the
+    /// enabled optional outputs.
+    pub fn bind_group_layout_entries(
+        options: &PreprocessorCreateOptions,
+    ) -> Vec<wgpu::BindGroupLayoutEntry> {
+        Preprocessor::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR
+            .entries
+            .iter()
+            .copied()
+            .chain(
+                options
+                    .visible_bounds
+                    .then_some(Preprocessor::<G>::VISIBLE_BOUNDS_BIND_GROUP_LAYOUT_ENTRY),
+            )
+            .chain(
+                options
+                    .linear_depth
+                    .then_some(Preprocessor::<G>::LINEAR_DEPTH_BIND_GROUP_LAYOUT_ENTRY),
+            )
+            .chain(
+                options
+                    .conic2d
+                    .then_some(Preprocessor::<G>::CONIC2D_BIND_GROUP_LAYOUT_ENTRIES)
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect()
+    }
 }
 
 impl<G: GaussianPod> Preprocessor<G, ()> {
@@ -372,32 +865,116 @@ impl<G: GaussianPod> Preprocessor<G, ()> {
     ///
     /// To create a bind group with layout matched to this preprocessor, use the
     /// [`Preprocessor::create_bind_group`] method.
-    pub fn new_without_bind_group(device: &wgpu::Device) -> Result<Self, PreprocessorCreateError> {
+    pub fn new_without_bind_group(
+        device: &wgpu::Device,
+        options: PreprocessorCreateOptions,
+    ) -> Result<Self, PreprocessorCreateError> {
+        Preprocessor::new_without_bind_group_impl(device, options, None)
+    }
+
+    /// Create a new preprocessor with a custom WGSL shader without interally managed bind group.
+    ///
+    /// See [`Preprocessor::new_with_wgsl`] for the contract of the shader.
+    pub fn new_without_bind_group_with_wgsl(
+        device: &wgpu::Device,
+        options: PreprocessorCreateOptions,
+        wgsl: &str,
+    ) -> Result<Self, PreprocessorCreateError> {
+        Preprocessor::new_without_bind_group_impl(device, options, Some(wgsl))
+    }
+
+    /// Create a new preprocessor with an optional custom shader without interally managed bind
+    /// group.
+    fn new_without_bind_group_impl(
+        device: &wgpu::Device,
+        options: PreprocessorCreateOptions,
+        wgsl: Option<&str>,
+    ) -> Result<Self, PreprocessorCreateError> {
         let main_shader: wesl::ModulePath = Preprocessor::<G>::MAIN_SHADER
             .parse()
             .expect("preprocess module path");
 
-        let wesl_compile_options = wesl::CompileOptions {
-            features: wesl::Features {
-                flags: G::features()
-                    .into_iter()
-                    .chain(std::iter::once((
-                        "selection_buffer",
-                        cfg!(feature = "viewer-selection"),
-                    )))
-                    .map(|(k, v)| (k.to_string(), v.into()))
-                    .collect(),
-                ..Default::default()
-            },
-            ..Default::default()
+        let wesl_compile_options = Preprocessor::<G>::wesl_compile_options(&options);
+
+        let label = options.label_prefix.as_deref().unwrap_or_default();
+        let bundle_label = prefixed_label(label, Preprocessor::<G>::LABEL);
+        let pre_bundle_label = prefixed_label(label, &format!("Pre {}", Preprocessor::<G>::LABEL));
+        let post_bundle_label =
+            prefixed_label(label, &format!("Post {}", Preprocessor::<G>::LABEL));
+
+        let bind_group_layout_label = prefixed_label(label, "Preprocessor Bind Group Layout");
+        let bind_group_layout_entries = Preprocessor::<G>::bind_group_layout_entries(&options);
+        let bind_group_layout_descriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some(&bind_group_layout_label),
+            entries: &bind_group_layout_entries,
+        };
+
+        let sort_key_bind_group_layout_label =
+            prefixed_label(label, "Preprocessor Sort Key Bind Group Layout");
+        let sort_key_bind_group_layout_descriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some(&sort_key_bind_group_layout_label),
+            ..Preprocessor::<G>::SORT_KEY_BIND_GROUP_LAYOUT_DESCRIPTOR
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&bind_group_layout_descriptor);
+        let sort_key_bind_group_layout =
+            device.create_bind_group_layout(&sort_key_bind_group_layout_descriptor);
+
+        let bind_group_layouts = match options.depth_mapping {
+            DepthMapping::Custom => vec![
+                &bind_group_layout_descriptor,
+                &sort_key_bind_group_layout_descriptor,
+            ],
+            _ => vec![&bind_group_layout_descriptor],
         };
 
-        let bind_group_layout =
-            device.create_bind_group_layout(&Preprocessor::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR);
+        if let Some(wgsl) = wgsl {
+            log::debug!("Validating preprocessor custom shader");
+            custom_shader::validate_wgsl(
+                wgsl,
+                &[
+                    ("pre", wgpu::ShaderStages::COMPUTE),
+                    ("main", wgpu::ShaderStages::COMPUTE),
+                    ("post", wgpu::ShaderStages::COMPUTE),
+                ],
+                &["workgroup_size"],
+                &bind_group_layouts,
+            )?;
+
+            let create_bundle = |label: &str, entry_point: &str| {
+                ComputeBundle::new_without_bind_groups(
+                    Some(label),
+                    device,
+                    bind_group_layouts.iter().copied(),
+                    wgpu::PipelineCompilationOptions::default(),
+                    wgpu::ShaderSource::Wgsl(wgsl.into()),
+                    entry_point,
+                    None,
+                )
+            };
+
+            let pre_bundle = create_bundle(&pre_bundle_label, "pre")?;
+            let bundle = create_bundle(&bundle_label, "main")?;
+            let post_bundle = create_bundle(&post_bundle_label, "post")?;
+
+            log::info!("Preprocessor created with custom shader");
+
+            return Ok(Self {
+                bind_group_layout,
+                bind_group: (),
+                sort_key_bind_group_layout,
+                sort_key_bind_group: None,
+                options,
+                pre_bundle,
+                bundle,
+                post_bundle,
+                gaussian_pod_marker: std::marker::PhantomData,
+            });
+        }
 
         let pre_bundle = ComputeBundleBuilder::new()
-            .label(format!("Pre {}", Preprocessor::<G>::LABEL).as_str())
-            .bind_group_layout(&Preprocessor::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR)
+            .label(pre_bundle_label.as_str())
+            .bind_group_layouts(bind_group_layouts.clone())
             .entry_point("pre")
             .main_shader(main_shader.clone())
             .wesl_compile_options(wesl_compile_options.clone())
@@ -405,8 +982,8 @@ impl<G: GaussianPod> Preprocessor<G, ()> {
             .build_without_bind_groups(device)?;
 
         let bundle = ComputeBundleBuilder::new()
-            .label(Preprocessor::<G>::LABEL)
-            .bind_group_layout(&Preprocessor::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR)
+            .label(bundle_label.as_str())
+            .bind_group_layouts(bind_group_layouts.clone())
             .entry_point("main")
             .main_shader(main_shader.clone())
             .wesl_compile_options(wesl_compile_options.clone())
@@ -414,8 +991,8 @@ impl<G: GaussianPod> Preprocessor<G, ()> {
             .build_without_bind_groups(device)?;
 
         let post_bundle = ComputeBundleBuilder::new()
-            .label(format!("Post {}", Preprocessor::<G>::LABEL).as_str())
-            .bind_group_layout(&Preprocessor::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR)
+            .label(post_bundle_label.as_str())
+            .bind_group_layouts(bind_group_layouts.clone())
             .entry_point("post")
             .main_shader(main_shader)
             .wesl_compile_options(wesl_compile_options)
@@ -427,6 +1004,9 @@ impl<G: GaussianPod> Preprocessor<G, ()> {
         Ok(Self {
             bind_group_layout,
             bind_group: (),
+            sort_key_bind_group_layout,
+            sort_key_bind_group: None,
+            options,
             pre_bundle,
             bundle,
             post_bundle,
@@ -441,10 +1021,25 @@ impl<G: GaussianPod> Preprocessor<G, ()> {
         bind_group: &wgpu::BindGroup,
         gaussian_count: u32,
     ) {
-        self.pre_bundle.dispatch(encoder, 1, [bind_group]);
-
-        self.bundle.dispatch(encoder, gaussian_count, [bind_group]);
+        self.preprocess_with_bind_groups(encoder, bind_group, None, gaussian_count);
+    }
 
-        self.post_bundle.dispatch(encoder, 1, [bind_group]);
+    /// Preprocess the Gaussians with the sort keys for [`DepthMapping::Custom`].
+    ///
+    /// To create the sort key bind group, use the [`Preprocessor::create_sort_key_bind_group`]
+    /// method.
+    pub fn preprocess_with_sort_keys(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group: &wgpu::BindGroup,
+        sort_key_bind_group: &wgpu::BindGroup,
+        gaussian_count: u32,
+    ) {
+        self.preprocess_with_bind_groups(
+            encoder,
+            bind_group,
+            Some(sort_key_bind_group),
+            gaussian_count,
+        );
     }
 }
@@ -0,0 +1,47 @@
+//! One-shot rendering for quick scripts and tests.
+//!
+//! The functions in this module create a transient [`Viewer`] for every call, so they trade
+//! efficiency for brevity. They are **not** meant to be called every frame, use [`Viewer`]
+//! directly for that instead.
+//!
+//! ```rust ignore
+//! use wgpu_3dgs_viewer as gs;
+//! use wgpu_3dgs_viewer::core::glam::UVec2;
+//!
+//! // Setup wgpu...
+//!
+//! let gaussians = gs::core::Gaussians::read_from_file(model_path, gs::core::GaussiansSource::Ply)
+//!     .expect("gaussians");
+//! let camera = gs::CameraPod::new(&gs::Camera::new(0.1..1e4, 60f32.to_radians()), size);
+//!
+//! gs::quick::render(&device, &queue, &view, format, &camera, &gaussians).expect("render");
+//! ```
+
+use crate::{CameraPod, DefaultGaussianPod, Viewer, ViewerCreateError, core::IterGaussian};
+
+/// Render the Gaussians to the texture view in one shot.
+///
+/// This creates a [`Viewer`], renders a single frame, and submits it to the `queue`. The viewer
+/// is dropped afterwards, so all GPU resources are recreated on every call.
+pub fn render(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    view: &wgpu::TextureView,
+    format: wgpu::TextureFormat,
+    camera: &CameraPod,
+    gaussians: &impl IterGaussian,
+) -> Result<(), ViewerCreateError> {
+    let mut viewer = Viewer::<DefaultGaussianPod>::new(device, format, gaussians)?;
+
+    viewer.update_camera_with_pod(queue, camera);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Quick Render Command Encoder"),
+    });
+
+    viewer.render(&mut encoder, view);
+
+    queue.submit(Some(encoder.finish()));
+
+    Ok(())
+}
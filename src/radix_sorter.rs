@@ -1,9 +1,19 @@
 use crate::{
     GaussiansDepthBuffer, IndirectIndicesBuffer, RadixSortIndirectArgsBuffer, core::BufferWrapper,
+    prefixed_label,
 };
 
 pub type RadixSorterBindGroups = wgpu_sort::InternalSortBuffers;
 
+/// The options for creating a [`RadixSorter`].
+#[derive(Debug, Default, Clone)]
+pub struct RadixSorterCreateOptions {
+    /// The optional pipeline cache for the compute pipelines.
+    pub pipeline_cache: Option<wgpu::PipelineCache>,
+    /// The optional prefix of the pipeline, bind group and internal buffer labels.
+    pub label_prefix: Option<String>,
+}
+
 /// Radix sorter for sorting Gaussians based on their depth (i.e. clipped z value).
 #[derive(Debug)]
 pub struct RadixSorter<B = RadixSorterBindGroups> {
@@ -32,13 +42,43 @@ impl<B> RadixSorter<B> {
 }
 
 impl RadixSorter {
+    /// Check whether the `device` supports the radix sort.
+    ///
+    /// The radix sort needs more workgroup storage than
+    /// [`wgpu::Limits::downlevel_defaults`], if this returns false, creating the sorter fails with
+    /// a validation error. [`CpuSorter`](crate::CpuSorter) can be used instead, which
+    /// [`Viewer`](crate::Viewer) does automatically, see
+    /// [`ViewerCreateOptions::cpu_sort`](crate::ViewerCreateOptions::cpu_sort).
+    pub fn is_supported(device: &wgpu::Device) -> bool {
+        let limits = device.limits();
+
+        limits.max_compute_workgroup_storage_size >= wgpu_sort::WORKGROUP_STORAGE_SIZE
+            && limits.max_compute_invocations_per_workgroup >= wgpu_sort::MAX_WORKGROUP_SIZE
+            && limits.max_compute_workgroup_size_x >= wgpu_sort::MAX_WORKGROUP_SIZE
+    }
+
     /// Create a new radix sorter.
     pub fn new(
         device: &wgpu::Device,
         gaussians_depth: &GaussiansDepthBuffer,
         indirect_indices: &IndirectIndicesBuffer,
     ) -> Self {
-        let this = RadixSorter::new_without_bind_groups(device);
+        RadixSorter::new_with_options(
+            device,
+            RadixSorterCreateOptions::default(),
+            gaussians_depth,
+            indirect_indices,
+        )
+    }
+
+    /// Create a new radix sorter with options.
+    pub fn new_with_options(
+        device: &wgpu::Device,
+        options: RadixSorterCreateOptions,
+        gaussians_depth: &GaussiansDepthBuffer,
+        indirect_indices: &IndirectIndicesBuffer,
+    ) -> Self {
+        let this = RadixSorter::new_without_bind_groups_with_options(device, options);
 
         log::debug!("Creating radix sorter internal sort buffers");
         let internal_sort_buffers =
@@ -69,8 +109,24 @@ impl RadixSorter {
 impl RadixSorter<()> {
     /// Create a new radix sorter without internally managed bind groups.
     pub fn new_without_bind_groups(device: &wgpu::Device) -> Self {
+        RadixSorter::new_without_bind_groups_with_options(
+            device,
+            RadixSorterCreateOptions::default(),
+        )
+    }
+
+    /// Create a new radix sorter with options without internally managed bind groups.
+    pub fn new_without_bind_groups_with_options(
+        device: &wgpu::Device,
+        options: RadixSorterCreateOptions,
+    ) -> Self {
         log::debug!("Creating radix sorter without bind groups");
-        let sorter = wgpu_sort::GPUSorter::new(device, 1);
+        let sorter = wgpu_sort::GPUSorter::new(
+            device,
+            options.label_prefix.as_deref().unwrap_or_default(),
+            1,
+            options.pipeline_cache.as_ref(),
+        );
 
         log::info!("Radix sorter created");
 
@@ -181,6 +237,13 @@ pub(crate) mod wgpu_sort {
     /// currently only 4 byte values are allowed
     const BYTES_PER_PAYLOAD_ELEM: u32 = 4;
 
+    /// the workgroup storage size in bytes of the scatter shaders
+    pub const WORKGROUP_STORAGE_SIZE: u32 =
+        (RS_RADIX_SIZE + RS_SCATTER_BLOCK_ROWS * SCATTER_WG_SIZE) * mem::size_of::<u32>() as u32;
+
+    /// the largest work group size of the shaders
+    pub const MAX_WORKGROUP_SIZE: u32 = HISTOGRAM_WG_SIZE;
+
     /// number of passed used for sorting
     /// we sort 8 bits per pass so 4 passes are required for a 32 bit value
     const NUM_PASSES: u32 = BYTES_PER_PAYLOAD_ELEM;
@@ -193,10 +256,17 @@ pub(crate) mod wgpu_sort {
         prefix_p: wgpu::ComputePipeline,
         scatter_even_p: wgpu::ComputePipeline,
         scatter_odd_p: wgpu::ComputePipeline,
+        /// The prefix of the labels.
+        label: String,
     }
 
     impl GPUSorter {
-        pub fn new(device: &wgpu::Device, subgroup_size: u32) -> Self {
+        pub fn new(
+            device: &wgpu::Device,
+            label: &str,
+            subgroup_size: u32,
+            pipeline_cache: Option<&wgpu::PipelineCache>,
+        ) -> Self {
             // special variables for scatter shade
             let histogram_sg_size = subgroup_size;
             let rs_sweep_0_size = RS_RADIX_SIZE / histogram_sg_size;
@@ -215,7 +285,7 @@ pub(crate) mod wgpu_sort {
 
             let pipeline_layout: wgpu::PipelineLayout =
                 device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                    label: Some("radix sort pipeline layout"),
+                    label: Some(prefixed_label(label, "radix sort pipeline layout").as_str()),
                     bind_group_layouts: &[&bind_group_layout],
                     ..Default::default()
                 });
@@ -258,48 +328,48 @@ pub(crate) mod wgpu_sort {
                 .replace("{scatter_wg_size}", SCATTER_WG_SIZE.to_string().as_str());
 
             let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: Some("Radix sort shader"),
+                label: Some(prefixed_label(label, "Radix sort shader").as_str()),
                 source: wgpu::ShaderSource::Wgsl(shader_code.into()),
             });
             let zero_p = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                label: Some("Zero the histograms"),
+                label: Some(prefixed_label(label, "Zero the histograms").as_str()),
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("zero_histograms"),
                 compilation_options: Default::default(),
-                cache: None,
+                cache: pipeline_cache,
             });
             let histogram_p = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                label: Some("calculate_histogram"),
+                label: Some(prefixed_label(label, "calculate_histogram").as_str()),
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("calculate_histogram"),
                 compilation_options: Default::default(),
-                cache: None,
+                cache: pipeline_cache,
             });
             let prefix_p = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                label: Some("prefix_histogram"),
+                label: Some(prefixed_label(label, "prefix_histogram").as_str()),
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("prefix_histogram"),
                 compilation_options: Default::default(),
-                cache: None,
+                cache: pipeline_cache,
             });
             let scatter_even_p = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                label: Some("scatter_even"),
+                label: Some(prefixed_label(label, "scatter_even").as_str()),
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("scatter_even"),
                 compilation_options: Default::default(),
-                cache: None,
+                cache: pipeline_cache,
             });
             let scatter_odd_p = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                label: Some("scatter_odd"),
+                label: Some(prefixed_label(label, "scatter_odd").as_str()),
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("scatter_odd"),
                 compilation_options: Default::default(),
-                cache: None,
+                cache: pipeline_cache,
             });
 
             Self {
@@ -308,6 +378,7 @@ pub(crate) mod wgpu_sort {
                 prefix_p,
                 scatter_even_p,
                 scatter_odd_p,
+                label: label.to_string(),
             }
         }
 
@@ -427,6 +498,7 @@ pub(crate) mod wgpu_sort {
 
         /// Modified from [`GPUSorter::create_keyval_buffers`].
         fn create_internal_keyval_buffers(
+            &self,
             device: &wgpu::Device,
             length: u32,
         ) -> (wgpu::Buffer, wgpu::Buffer) {
@@ -435,7 +507,9 @@ pub(crate) mod wgpu_sort {
 
             // auxiliary buffer for keys
             let keys_aux = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("radix sort keys auxiliary buffer"),
+                label: Some(
+                    prefixed_label(&self.label, "radix sort keys auxiliary buffer").as_str(),
+                ),
                 size: (count_ru_histo * BYTES_PER_PAYLOAD_ELEM) as u64,
                 usage: wgpu::BufferUsages::STORAGE,
                 mapped_at_creation: false,
@@ -445,7 +519,9 @@ pub(crate) mod wgpu_sort {
 
             // auxiliary buffer for payload/values
             let payload_aux = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("radix sort payload auxiliary buffer"),
+                label: Some(
+                    prefixed_label(&self.label, "radix sort payload auxiliary buffer").as_str(),
+                ),
                 size: payload_size as u64,
                 usage: wgpu::BufferUsages::STORAGE,
                 mapped_at_creation: false,
@@ -475,7 +551,7 @@ pub(crate) mod wgpu_sort {
             let internal_size = (RS_KEYVAL_SIZE + scatter_blocks_ru) * histo_size; // +1 safety
 
             device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Internal radix sort buffer"),
+                label: Some(prefixed_label(&self.label, "Internal radix sort buffer").as_str()),
                 size: internal_size as u64,
                 usage: wgpu::BufferUsages::STORAGE,
                 mapped_at_creation: false,
@@ -740,17 +816,17 @@ pub(crate) mod wgpu_sort {
         ) -> InternalSortBuffers {
             let length = payload.size() as u32 / BYTES_PER_PAYLOAD_ELEM;
 
-            let (keys_b, payload_b) = GPUSorter::create_internal_keyval_buffers(device, length);
+            let (keys_b, payload_b) = self.create_internal_keyval_buffers(device, length);
             let internal_mem_buffer = self.create_internal_mem_buffer(device, length);
 
             let uniform_infos = Self::general_info_data(length);
             let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("radix sort uniform buffer"),
+                label: Some(prefixed_label(&self.label, "radix sort uniform buffer").as_str()),
                 contents: bytemuck::bytes_of(&uniform_infos),
                 usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
             });
             let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("radix sort bind group"),
+                label: Some(prefixed_label(&self.label, "radix sort bind group").as_str()),
                 layout: &Self::bind_group_layout(device),
                 entries: &[
                     wgpu::BindGroupEntry {
@@ -1,10 +1,60 @@
 use crate::{
     CameraBuffer, GaussianPod, GaussianTransformBuffer, GaussiansBuffer, IndirectArgsBuffer,
-    IndirectIndicesBuffer, ModelTransformBuffer, RendererCreateError, core::BufferWrapper,
-    wesl_utils,
+    IndirectIndicesBuffer, ModelTransformBuffer, RendererCreateError, RendererSettingsBuffer,
+    core::BufferWrapper, custom_shader, prefixed_label, wesl_utils,
 };
 
+#[cfg(feature = "viewer-selection")]
+use crate::{editor::SelectionBuffer, selection};
+
+/// The geometry used to draw each Gaussian.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderQuadMode {
+    /// Two triangles covering the bounding quad of the Gaussian.
+    ///
+    /// This covers the exact footprint of the Gaussian.
+    #[default]
+    Quad,
+    /// A single oversized triangle covering the bounding quad of the Gaussian.
+    ///
+    /// This uses fewer vertices and avoids the diagonal seam, at the cost of more discarded
+    /// fragments.
+    Triangle,
+}
+
+impl RenderQuadMode {
+    /// Get the number of vertices to draw for each Gaussian.
+    pub const fn vertex_count(&self) -> u32 {
+        match self {
+            Self::Quad => 6,
+            Self::Triangle => 3,
+        }
+    }
+}
+
+/// The options for creating a [`Renderer`].
+#[derive(Debug, Default, Clone)]
+pub struct RendererCreateOptions {
+    /// The optional depth stencil state.
+    pub depth_stencil: Option<wgpu::DepthStencilState>,
+    /// The primitive state, see [`Renderer::primitive`].
+    pub primitive: wgpu::PrimitiveState,
+    /// The geometry used to draw each Gaussian.
+    pub quad_mode: RenderQuadMode,
+    /// The optional pipeline cache for the render pipeline.
+    pub pipeline_cache: Option<wgpu::PipelineCache>,
+    /// The optional prefix of the pipeline, bind group and render pass labels.
+    pub label_prefix: Option<String>,
+}
+
 /// A renderer for Gaussians.
+///
+/// The fragment shader outputs straight color blended with [`wgpu::BlendState::ALPHA_BLENDING`],
+/// so the render target accumulates the color premultiplied by alpha, i.e. the Gaussians
+/// composited over the clear color. To composite the output over other content, clear to
+/// [`wgpu::Color::TRANSPARENT`] and blend the render target with
+/// [`wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING`]. Clearing to an opaque color, e.g. the
+/// default [`wgpu::Color::BLACK`], leaves an opaque image with nothing to composite.
 #[derive(Debug)]
 pub struct Renderer<G: GaussianPod, B = wgpu::BindGroup> {
     /// The bind group layout.
@@ -13,11 +63,39 @@ pub struct Renderer<G: GaussianPod, B = wgpu::BindGroup> {
     bind_group: B,
     /// The render pipeline.
     pipeline: wgpu::RenderPipeline,
+    /// The texture format.
+    texture_format: wgpu::TextureFormat,
+    /// The texture format of the optional linear HDR render target.
+    hdr_texture_format: Option<wgpu::TextureFormat>,
+    /// The quad mode.
+    quad_mode: RenderQuadMode,
+    /// The depth stencil state.
+    depth_stencil: Option<wgpu::DepthStencilState>,
+    /// The primitive state.
+    primitive: wgpu::PrimitiveState,
+    /// The pipeline cache.
+    pipeline_cache: Option<wgpu::PipelineCache>,
+    /// The custom WGSL shader, [`None`] for the built-in shader.
+    custom_wgsl: Option<String>,
+    /// The prefix of the labels.
+    label_prefix: Option<String>,
     /// The marker for the Gaussian POD type.
     gaussian_pod_marker: std::marker::PhantomData<G>,
 }
 
 impl<G: GaussianPod, B> Renderer<G, B> {
+    /// The default color operations, clearing to black.
+    pub const DEFAULT_COLOR_OPS: wgpu::Operations<wgpu::Color> = wgpu::Operations {
+        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+        store: wgpu::StoreOp::Store,
+    };
+
+    /// The default depth operations, clearing to 1.0, the far plane of a standard projection.
+    pub const DEFAULT_DEPTH_OPS: wgpu::Operations<f32> = wgpu::Operations {
+        load: wgpu::LoadOp::Clear(1.0),
+        store: wgpu::StoreOp::Store,
+    };
+
     /// Create the bind group.
     #[allow(clippy::too_many_arguments)]
     pub fn create_bind_group(
@@ -28,15 +106,28 @@ impl<G: GaussianPod, B> Renderer<G, B> {
         gaussian_transform: &GaussianTransformBuffer,
         gaussians: &GaussiansBuffer<G>,
         indirect_indices: &IndirectIndicesBuffer,
+        renderer_settings: &RendererSettingsBuffer,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")] selection_layers: &selection::SelectionLayersBuffer,
+        #[cfg(feature = "viewer-selection")]
+        selection_highlight: &selection::SelectionHighlightBuffer,
     ) -> wgpu::BindGroup {
         Renderer::create_bind_group_static(
             device,
+            self.label_prefix.as_deref().unwrap_or_default(),
             &self.bind_group_layout,
             camera,
             model_transform,
             gaussian_transform,
             gaussians,
             indirect_indices,
+            renderer_settings,
+            #[cfg(feature = "viewer-selection")]
+            selection,
+            #[cfg(feature = "viewer-selection")]
+            selection_layers,
+            #[cfg(feature = "viewer-selection")]
+            selection_highlight,
         )
     }
 
@@ -49,6 +140,63 @@ impl<G: GaussianPod, B> Renderer<G, B> {
     pub fn pipeline(&self) -> &wgpu::RenderPipeline {
         &self.pipeline
     }
+
+    /// Get the texture format of the render target.
+    pub fn texture_format(&self) -> wgpu::TextureFormat {
+        self.texture_format
+    }
+
+    /// Get the texture format of the linear HDR render target.
+    ///
+    /// This is [`None`] if the renderer only writes to a single render target.
+    pub fn hdr_texture_format(&self) -> Option<wgpu::TextureFormat> {
+        self.hdr_texture_format
+    }
+
+    /// Get the quad mode.
+    ///
+    /// The [`IndirectArgsBuffer`] used for rendering should be created with the same mode, see
+    /// [`IndirectArgsBuffer::new_with_quad_mode`].
+    pub fn quad_mode(&self) -> RenderQuadMode {
+        self.quad_mode
+    }
+
+    /// Get the depth stencil state.
+    pub fn depth_stencil(&self) -> Option<&wgpu::DepthStencilState> {
+        self.depth_stencil.as_ref()
+    }
+
+    /// Get the primitive state.
+    ///
+    /// The quads of the Gaussians are drawn as [`wgpu::PrimitiveTopology::TriangleList`], so the
+    /// topology should be kept, while the culling and polygon mode may be set to match the
+    /// surrounding renderer.
+    pub fn primitive(&self) -> wgpu::PrimitiveState {
+        self.primitive
+    }
+
+    /// Get the pipeline cache the render pipeline is created with.
+    pub fn pipeline_cache(&self) -> Option<&wgpu::PipelineCache> {
+        self.pipeline_cache.as_ref()
+    }
+
+    /// Get the custom WGSL shader the render pipeline is created with.
+    ///
+    /// Returns [`None`] for the built-in shader.
+    pub fn custom_wgsl(&self) -> Option<&str> {
+        self.custom_wgsl.as_deref()
+    }
+
+    /// Get the options the renderer is created with.
+    pub fn create_options(&self) -> RendererCreateOptions {
+        RendererCreateOptions {
+            depth_stencil: self.depth_stencil.clone(),
+            primitive: self.primitive,
+            quad_mode: self.quad_mode,
+            pipeline_cache: self.pipeline_cache.clone(),
+            label_prefix: self.label_prefix.clone(),
+        }
+    }
 }
 
 impl<G: GaussianPod> Renderer<G> {
@@ -112,6 +260,53 @@ impl<G: GaussianPod> Renderer<G> {
                     },
                     count: None,
                 },
+                // Renderer settings uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Selection storage buffer
+                #[cfg(feature = "viewer-selection")]
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Selection layers storage buffer
+                #[cfg(feature = "viewer-selection")]
+                wgpu::BindGroupLayoutEntry {
+                    binding: 7,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Selection highlight uniform buffer
+                #[cfg(feature = "viewer-selection")]
+                wgpu::BindGroupLayoutEntry {
+                    binding: 8,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         };
 
@@ -120,12 +315,181 @@ impl<G: GaussianPod> Renderer<G> {
     pub fn new(
         device: &wgpu::Device,
         texture_format: wgpu::TextureFormat,
-        depth_stencil: Option<wgpu::DepthStencilState>,
+        options: RendererCreateOptions,
+        camera: &CameraBuffer,
+        model_transform: &ModelTransformBuffer,
+        gaussian_transform: &GaussianTransformBuffer,
+        gaussians: &GaussiansBuffer<G>,
+        indirect_indices: &IndirectIndicesBuffer,
+        renderer_settings: &RendererSettingsBuffer,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")] selection_layers: &selection::SelectionLayersBuffer,
+        #[cfg(feature = "viewer-selection")]
+        selection_highlight: &selection::SelectionHighlightBuffer,
+    ) -> Result<Self, RendererCreateError> {
+        Renderer::new_impl(
+            device,
+            texture_format,
+            None,
+            options,
+            None,
+            camera,
+            model_transform,
+            gaussian_transform,
+            gaussians,
+            indirect_indices,
+            renderer_settings,
+            #[cfg(feature = "viewer-selection")]
+            selection,
+            #[cfg(feature = "viewer-selection")]
+            selection_layers,
+            #[cfg(feature = "viewer-selection")]
+            selection_highlight,
+        )
+    }
+
+    /// Create a new renderer with a custom WGSL shader instead of the built-in shader.
+    ///
+    /// This is for prototyping a fork of the render shader while reusing the buffers and passes
+    /// of this crate, [`Renderer::compiled_wgsl`] is a good starting point of the fork. The shader
+    /// must honor the following contract, which is validated before creating the pipeline:
+    ///
+    /// - A `vert_main` vertex entry point, drawn with [`RenderQuadMode::vertex_count`] vertices
+    ///   per instance and one instance per visible Gaussian, without vertex buffers.
+    /// - A `frag_main` fragment entry point, writing the color to the render target of
+    ///   `texture_format` at location 0.
+    /// - Every binding in `@group(0)`, at a binding number of [`Renderer::BIND_GROUP_LAYOUT_DESCRIPTOR`]
+    ///   with a compatible type, i.e. `var<uniform>` for uniform buffers, `var<storage, read>`
+    ///   for read only storage buffers. Unused bindings may be omitted.
+    ///
+    /// The layouts of the buffer contents are not validated, they should match the built-in
+    /// shader, e.g. [`RendererSettingsPod`](crate::RendererSettingsPod) for the renderer settings.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_wgsl(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        options: RendererCreateOptions,
+        wgsl: &str,
+        camera: &CameraBuffer,
+        model_transform: &ModelTransformBuffer,
+        gaussian_transform: &GaussianTransformBuffer,
+        gaussians: &GaussiansBuffer<G>,
+        indirect_indices: &IndirectIndicesBuffer,
+        renderer_settings: &RendererSettingsBuffer,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")] selection_layers: &selection::SelectionLayersBuffer,
+        #[cfg(feature = "viewer-selection")]
+        selection_highlight: &selection::SelectionHighlightBuffer,
+    ) -> Result<Self, RendererCreateError> {
+        Renderer::new_impl(
+            device,
+            texture_format,
+            None,
+            options,
+            Some(wgsl),
+            camera,
+            model_transform,
+            gaussian_transform,
+            gaussians,
+            indirect_indices,
+            renderer_settings,
+            #[cfg(feature = "viewer-selection")]
+            selection,
+            #[cfg(feature = "viewer-selection")]
+            selection_layers,
+            #[cfg(feature = "viewer-selection")]
+            selection_highlight,
+        )
+    }
+
+    /// Create a new renderer with an additional linear HDR render target.
+    ///
+    /// The fragment shader writes the color to the render target of `texture_format` at location
+    /// 0, same as [`Renderer::new`], and the unclamped linear color to the render target of
+    /// `hdr_texture_format` at location 1, e.g. for a bloom pass on bright Gaussians. The HDR
+    /// format should be blendable, e.g. [`wgpu::TextureFormat::Rgba16Float`]. Only the render
+    /// target of `texture_format` is tonemapped by
+    /// [`RendererSettings::tonemap`](crate::RendererSettings::tonemap).
+    ///
+    /// Use [`Renderer::render_with_hdr_target`] to render to both targets.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_hdr_target(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        hdr_texture_format: wgpu::TextureFormat,
+        options: RendererCreateOptions,
+        camera: &CameraBuffer,
+        model_transform: &ModelTransformBuffer,
+        gaussian_transform: &GaussianTransformBuffer,
+        gaussians: &GaussiansBuffer<G>,
+        indirect_indices: &IndirectIndicesBuffer,
+        renderer_settings: &RendererSettingsBuffer,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")] selection_layers: &selection::SelectionLayersBuffer,
+        #[cfg(feature = "viewer-selection")]
+        selection_highlight: &selection::SelectionHighlightBuffer,
+    ) -> Result<Self, RendererCreateError> {
+        Renderer::new_impl(
+            device,
+            texture_format,
+            Some(hdr_texture_format),
+            options,
+            None,
+            camera,
+            model_transform,
+            gaussian_transform,
+            gaussians,
+            indirect_indices,
+            renderer_settings,
+            #[cfg(feature = "viewer-selection")]
+            selection,
+            #[cfg(feature = "viewer-selection")]
+            selection_layers,
+            #[cfg(feature = "viewer-selection")]
+            selection_highlight,
+        )
+    }
+
+    /// Get the bind group.
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    /// Compile the render shader to WGSL without creating the pipeline.
+    ///
+    /// This uses the same features and resolver as [`Renderer::new`], which is useful for
+    /// debugging the shader of a [`GaussianPod`] configuration.
+    pub fn compiled_wgsl(options: &RendererCreateOptions) -> Result<String, wesl::Error> {
+        Renderer::<G>::compile_wgsl(options, false)
+    }
+
+    /// Compile the render shader to WGSL with the additional linear HDR render target.
+    ///
+    /// This uses the same features and resolver as [`Renderer::new_with_hdr_target`].
+    pub fn compiled_wgsl_with_hdr_target(
+        options: &RendererCreateOptions,
+    ) -> Result<String, wesl::Error> {
+        Renderer::<G>::compile_wgsl(options, true)
+    }
+
+    /// Create a new renderer with an optional linear HDR render target and custom shader.
+    #[allow(clippy::too_many_arguments)]
+    fn new_impl(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        hdr_texture_format: Option<wgpu::TextureFormat>,
+        options: RendererCreateOptions,
+        wgsl: Option<&str>,
         camera: &CameraBuffer,
         model_transform: &ModelTransformBuffer,
         gaussian_transform: &GaussianTransformBuffer,
         gaussians: &GaussiansBuffer<G>,
         indirect_indices: &IndirectIndicesBuffer,
+        renderer_settings: &RendererSettingsBuffer,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")] selection_layers: &selection::SelectionLayersBuffer,
+        #[cfg(feature = "viewer-selection")]
+        selection_highlight: &selection::SelectionHighlightBuffer,
     ) -> Result<Self, RendererCreateError> {
         if (device.limits().max_storage_buffer_binding_size as u64) < gaussians.buffer().size() {
             return Err(RendererCreateError::ModelSizeExceedsDeviceLimit {
@@ -134,7 +498,13 @@ impl<G: GaussianPod> Renderer<G> {
             });
         }
 
-        let this = Renderer::new_without_bind_group(device, texture_format, depth_stencil)?;
+        let this = Renderer::new_without_bind_group_impl(
+            device,
+            texture_format,
+            hdr_texture_format,
+            options,
+            wgsl,
+        )?;
 
         log::debug!("Creating renderer bind group");
         let bind_group = this.create_bind_group(
@@ -144,19 +514,62 @@ impl<G: GaussianPod> Renderer<G> {
             gaussian_transform,
             gaussians,
             indirect_indices,
+            renderer_settings,
+            #[cfg(feature = "viewer-selection")]
+            selection,
+            #[cfg(feature = "viewer-selection")]
+            selection_layers,
+            #[cfg(feature = "viewer-selection")]
+            selection_highlight,
         );
 
         Ok(Self {
             bind_group_layout: this.bind_group_layout,
             bind_group,
             pipeline: this.pipeline,
+            texture_format: this.texture_format,
+            hdr_texture_format: this.hdr_texture_format,
+            quad_mode: this.quad_mode,
+            depth_stencil: this.depth_stencil,
+            primitive: this.primitive,
+            pipeline_cache: this.pipeline_cache,
+            custom_wgsl: this.custom_wgsl,
+            label_prefix: this.label_prefix,
             gaussian_pod_marker: std::marker::PhantomData,
         })
     }
 
-    /// Get the bind group.
-    pub fn bind_group(&self) -> &wgpu::BindGroup {
-        &self.bind_group
+    /// Compile the render shader to WGSL.
+    fn compile_wgsl(
+        options: &RendererCreateOptions,
+        hdr_target: bool,
+    ) -> Result<String, wesl::Error> {
+        Ok(wesl::compile_sourcemap(
+            &"wgpu_3dgs_viewer::render"
+                .parse()
+                .expect("render module path"),
+            &wesl_utils::resolver(),
+            &wesl::NoMangler,
+            &wesl::CompileOptions {
+                features: wesl::Features {
+                    flags: G::features()
+                        .into_iter()
+                        .chain([
+                            (
+                                "render_quad_triangle",
+                                options.quad_mode == RenderQuadMode::Triangle,
+                            ),
+                            ("render_hdr_target", hdr_target),
+                            ("selection_highlight", cfg!(feature = "viewer-selection")),
+                        ])
+                        .map(|(k, v)| (k.to_string(), v.into()))
+                        .collect(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?
+        .to_string())
     }
 
     /// Render the scene.
@@ -167,7 +580,10 @@ impl<G: GaussianPod> Renderer<G> {
         indirect_args: &IndirectArgsBuffer,
     ) {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Renderer Render Pass"),
+            label: Some(&prefixed_label(
+                self.label_prefix.as_deref().unwrap_or_default(),
+                "Renderer Render Pass",
+            )),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                 view,
                 resolve_target: None,
@@ -183,6 +599,84 @@ impl<G: GaussianPod> Renderer<G> {
         self.render_with_pass(&mut render_pass, indirect_args);
     }
 
+    /// Render the scene to both the render target and the linear HDR render target.
+    ///
+    /// The renderer must be created by [`Renderer::new_with_hdr_target`].
+    pub fn render_with_hdr_target(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        hdr_view: &wgpu::TextureView,
+        indirect_args: &IndirectArgsBuffer,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&prefixed_label(
+                self.label_prefix.as_deref().unwrap_or_default(),
+                "Renderer Render Pass",
+            )),
+            color_attachments: &[
+                Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                }),
+                Some(wgpu::RenderPassColorAttachment {
+                    view: hdr_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                }),
+            ],
+            ..Default::default()
+        });
+
+        self.render_with_pass(&mut render_pass, indirect_args);
+    }
+
+    /// Render the scene with a depth stencil attachment.
+    ///
+    /// The renderer should be created with a [`wgpu::DepthStencilState`] matching the format of
+    /// `depth_view`. Use [`wgpu::LoadOp::Load`] in `color_ops` and `depth_ops` to draw the
+    /// Gaussians over previously rendered geometry and depth test against it, or
+    /// [`Renderer::DEFAULT_COLOR_OPS`] and [`Renderer::DEFAULT_DEPTH_OPS`] to clear them.
+    pub fn render_with_depth(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        color_ops: wgpu::Operations<wgpu::Color>,
+        depth_view: &wgpu::TextureView,
+        depth_ops: wgpu::Operations<f32>,
+        indirect_args: &IndirectArgsBuffer,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&prefixed_label(
+                self.label_prefix.as_deref().unwrap_or_default(),
+                "Renderer Render Pass",
+            )),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: color_ops,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(depth_ops),
+                stencil_ops: None,
+            }),
+            ..Default::default()
+        });
+
+        self.render_with_pass(&mut render_pass, indirect_args);
+    }
+
     /// Render the scene with a [`wgpu::RenderPass`].
     pub fn render_with_pass(
         &self,
@@ -198,15 +692,21 @@ impl<G: GaussianPod> Renderer<G> {
     #[allow(clippy::too_many_arguments)]
     fn create_bind_group_static(
         device: &wgpu::Device,
+        label: &str,
         bind_group_layout: &wgpu::BindGroupLayout,
         camera: &CameraBuffer,
         model_transform: &ModelTransformBuffer,
         gaussian_transform: &GaussianTransformBuffer,
         gaussians: &GaussiansBuffer<G>,
         indirect_indices: &IndirectIndicesBuffer,
+        renderer_settings: &RendererSettingsBuffer,
+        #[cfg(feature = "viewer-selection")] selection: &SelectionBuffer,
+        #[cfg(feature = "viewer-selection")] selection_layers: &selection::SelectionLayersBuffer,
+        #[cfg(feature = "viewer-selection")]
+        selection_highlight: &selection::SelectionHighlightBuffer,
     ) -> wgpu::BindGroup {
         device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Renderer Bind Group"),
+            label: Some(prefixed_label(label, "Renderer Bind Group").as_str()),
             layout: bind_group_layout,
             entries: &[
                 // Camera uniform buffer
@@ -234,6 +734,29 @@ impl<G: GaussianPod> Renderer<G> {
                     binding: 4,
                     resource: indirect_indices.buffer().as_entire_binding(),
                 },
+                // Renderer settings uniform buffer
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: renderer_settings.buffer().as_entire_binding(),
+                },
+                // Selection storage buffer
+                #[cfg(feature = "viewer-selection")]
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: selection.buffer().as_entire_binding(),
+                },
+                // Selection layers storage buffer
+                #[cfg(feature = "viewer-selection")]
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: selection_layers.buffer().as_entire_binding(),
+                },
+                // Selection highlight uniform buffer
+                #[cfg(feature = "viewer-selection")]
+                wgpu::BindGroupEntry {
+                    binding: 8,
+                    resource: selection_highlight.buffer().as_entire_binding(),
+                },
             ],
         })
     }
@@ -247,42 +770,92 @@ impl<G: GaussianPod> Renderer<G, ()> {
     pub fn new_without_bind_group(
         device: &wgpu::Device,
         texture_format: wgpu::TextureFormat,
-        depth_stencil: Option<wgpu::DepthStencilState>,
+        options: RendererCreateOptions,
     ) -> Result<Self, RendererCreateError> {
+        Renderer::new_without_bind_group_impl(device, texture_format, None, options, None)
+    }
+
+    /// Create a new renderer with a custom WGSL shader without internally managed bind group.
+    ///
+    /// See [`Renderer::new_with_wgsl`] for the contract of the shader.
+    pub fn new_without_bind_group_with_wgsl(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        options: RendererCreateOptions,
+        wgsl: &str,
+    ) -> Result<Self, RendererCreateError> {
+        Renderer::new_without_bind_group_impl(device, texture_format, None, options, Some(wgsl))
+    }
+
+    /// Create a new renderer with an additional linear HDR render target without internally
+    /// managed bind group.
+    ///
+    /// See [`Renderer::new_with_hdr_target`] for the render targets.
+    pub fn new_without_bind_group_with_hdr_target(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        hdr_texture_format: wgpu::TextureFormat,
+        options: RendererCreateOptions,
+    ) -> Result<Self, RendererCreateError> {
+        Renderer::new_without_bind_group_impl(
+            device,
+            texture_format,
+            Some(hdr_texture_format),
+            options,
+            None,
+        )
+    }
+
+    /// Create a new renderer with an optional linear HDR render target and custom shader without
+    /// internally managed bind group.
+    fn new_without_bind_group_impl(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        hdr_texture_format: Option<wgpu::TextureFormat>,
+        options: RendererCreateOptions,
+        wgsl: Option<&str>,
+    ) -> Result<Self, RendererCreateError> {
+        let source: std::borrow::Cow<str> = match wgsl {
+            Some(wgsl) => {
+                log::debug!("Validating renderer custom shader");
+                custom_shader::validate_wgsl(
+                    wgsl,
+                    &[
+                        ("vert_main", wgpu::ShaderStages::VERTEX),
+                        ("frag_main", wgpu::ShaderStages::FRAGMENT),
+                    ],
+                    &[],
+                    &[&Renderer::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR],
+                )?;
+                wgsl.into()
+            }
+            None => Renderer::<G>::compile_wgsl(&options, hdr_texture_format.is_some())?.into(),
+        };
+
+        let label = options.label_prefix.as_deref().unwrap_or_default();
+
         log::debug!("Creating renderer bind group layout");
-        let bind_group_layout =
-            device.create_bind_group_layout(&Renderer::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR);
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(prefixed_label(label, "Renderer Bind Group Layout").as_str()),
+            ..Renderer::<G>::BIND_GROUP_LAYOUT_DESCRIPTOR
+        });
 
         log::debug!("Creating renderer pipeline layout");
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Renderer Pipeline Layout"),
+            label: Some(prefixed_label(label, "Renderer Pipeline Layout").as_str()),
             bind_group_layouts: &[&bind_group_layout],
             ..Default::default()
         });
 
         log::debug!("Creating renderer shader");
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Renderer Shader"),
-            source: wgpu::ShaderSource::Wgsl(
-                wesl::compile_sourcemap(
-                    &"wgpu_3dgs_viewer::render"
-                        .parse()
-                        .expect("render module path"),
-                    &wesl_utils::resolver(),
-                    &wesl::NoMangler,
-                    &wesl::CompileOptions {
-                        features: G::wesl_features(),
-                        ..Default::default()
-                    },
-                )?
-                .to_string()
-                .into(),
-            ),
+            label: Some(prefixed_label(label, "Renderer Shader").as_str()),
+            source: wgpu::ShaderSource::Wgsl(source),
         });
 
         log::debug!("Creating renderer pipeline");
         let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Renderer Pipeline"),
+            label: Some(prefixed_label(label, "Renderer Pipeline").as_str()),
             layout: Some(&pipeline_layout),
             vertex: wgpu::VertexState {
                 module: &shader,
@@ -293,18 +866,24 @@ impl<G: GaussianPod> Renderer<G, ()> {
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
                 entry_point: Some("frag_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: texture_format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
+                targets: &[texture_format]
+                    .into_iter()
+                    .chain(hdr_texture_format)
+                    .map(|format| {
+                        Some(wgpu::ColorTargetState {
+                            format,
+                            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
                 compilation_options: Default::default(),
             }),
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil,
+            primitive: options.primitive,
+            depth_stencil: options.depth_stencil.clone(),
             multisample: wgpu::MultisampleState::default(),
             multiview_mask: None,
-            cache: None,
+            cache: options.pipeline_cache.as_ref(),
         });
 
         log::info!("Renderer created");
@@ -313,6 +892,14 @@ impl<G: GaussianPod> Renderer<G, ()> {
             bind_group_layout,
             bind_group: (),
             pipeline,
+            texture_format,
+            hdr_texture_format,
+            quad_mode: options.quad_mode,
+            depth_stencil: options.depth_stencil,
+            primitive: options.primitive,
+            pipeline_cache: options.pipeline_cache,
+            custom_wgsl: wgsl.map(str::to_string),
+            label_prefix: options.label_prefix,
             gaussian_pod_marker: std::marker::PhantomData,
         })
     }
@@ -326,7 +913,10 @@ impl<G: GaussianPod> Renderer<G, ()> {
         indirect_args: &IndirectArgsBuffer,
     ) {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Renderer Render Pass"),
+            label: Some(&prefixed_label(
+                self.label_prefix.as_deref().unwrap_or_default(),
+                "Renderer Render Pass",
+            )),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                 view,
                 resolve_target: None,
@@ -342,6 +932,87 @@ impl<G: GaussianPod> Renderer<G, ()> {
         self.render_with_pass(&mut render_pass, bind_group, indirect_args);
     }
 
+    /// Render the scene to both the render target and the linear HDR render target.
+    ///
+    /// The renderer must be created by [`Renderer::new_with_hdr_target`].
+    pub fn render_with_hdr_target(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        hdr_view: &wgpu::TextureView,
+        bind_group: &wgpu::BindGroup,
+        indirect_args: &IndirectArgsBuffer,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&prefixed_label(
+                self.label_prefix.as_deref().unwrap_or_default(),
+                "Renderer Render Pass",
+            )),
+            color_attachments: &[
+                Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                }),
+                Some(wgpu::RenderPassColorAttachment {
+                    view: hdr_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                }),
+            ],
+            ..Default::default()
+        });
+
+        self.render_with_pass(&mut render_pass, bind_group, indirect_args);
+    }
+
+    /// Render the scene with a depth stencil attachment.
+    ///
+    /// The renderer should be created with a [`wgpu::DepthStencilState`] matching the format of
+    /// `depth_view`. Use [`wgpu::LoadOp::Load`] in `color_ops` and `depth_ops` to draw the
+    /// Gaussians over previously rendered geometry and depth test against it, or
+    /// [`Renderer::DEFAULT_COLOR_OPS`] and [`Renderer::DEFAULT_DEPTH_OPS`] to clear them.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_with_depth(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        color_ops: wgpu::Operations<wgpu::Color>,
+        depth_view: &wgpu::TextureView,
+        depth_ops: wgpu::Operations<f32>,
+        bind_group: &wgpu::BindGroup,
+        indirect_args: &IndirectArgsBuffer,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&prefixed_label(
+                self.label_prefix.as_deref().unwrap_or_default(),
+                "Renderer Render Pass",
+            )),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: color_ops,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(depth_ops),
+                stencil_ops: None,
+            }),
+            ..Default::default()
+        });
+
+        self.render_with_pass(&mut render_pass, bind_group, indirect_args);
+    }
+
     /// Render the scene with a [`wgpu::RenderPass`].
     pub fn render_with_pass(
         &self,
@@ -1,7 +1,10 @@
 use glam::*;
 use wgpu::util::DeviceExt;
 
-use crate::core::{self, BufferWrapper, FixedSizeBufferWrapper};
+use crate::{
+    core::{self, BufferWrapper, FixedSizeBufferWrapper},
+    prefixed_label,
+};
 
 /// A viewport selection texture for the compute bundle created by
 /// [`selection::create_viewport_bundle`](crate::selection::create_viewport_bundle).
@@ -26,7 +29,10 @@ impl ViewportTexture {
             dimension: wgpu::TextureDimension::D2,
             view_formats: &[],
             format: wgpu::TextureFormat::R8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
         });
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -43,6 +49,50 @@ impl ViewportTexture {
     pub fn view(&self) -> &wgpu::TextureView {
         &self.view
     }
+
+    /// Check whether no pixel of the selection mask is set.
+    ///
+    /// This downloads the whole texture, so it should not be called every frame.
+    pub async fn is_empty(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<bool, core::DownloadBufferError> {
+        let size = self.texture.size();
+        let bytes_per_row = size
+            .width
+            .next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let download = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Viewport Selection Texture Download Buffer"),
+            size: (bytes_per_row * size.height) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Viewport Selection Texture Is Empty Encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            self.texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &download,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let bytes = wgpu::Buffer::map_download::<u8>(&download, device).await?;
+
+        Ok(bytes
+            .chunks_exact(bytes_per_row as usize)
+            .all(|row| row[..size.width as usize].iter().all(|&texel| texel == 0)))
+    }
 }
 
 /// The position buffer for [`ViewportTexture`].
@@ -97,7 +147,10 @@ impl FixedSizeBufferWrapper for ViewportTexturePosBuffer {
 
 /// The f32 buffer for [`ViewportTexture`].
 ///
-/// This is used for [`ViewportTextureBrushRenderer`](crate::selection::ViewportTextureBrushRenderer).
+/// This is used for the brush radius of
+/// [`ViewportTextureBrushRenderer`](crate::selection::ViewportTextureBrushRenderer) and the edge
+/// width of both [`ViewportTextureBrushRenderer`](crate::selection::ViewportTextureBrushRenderer)
+/// and [`ViewportTextureRectangleRenderer`](crate::selection::ViewportTextureRectangleRenderer).
 #[derive(Debug, Clone)]
 pub struct ViewportTextureF32Buffer(wgpu::Buffer);
 
@@ -155,8 +208,15 @@ impl PreprocessorInvertSelectionBuffer {
     ///
     /// Note: the initial value is true.
     pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new invert selection buffer with additional label.
+    ///
+    /// Note: the initial value is true.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
         let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Preprocessor Invert Selection Buffer"),
+            label: Some(prefixed_label(label, "Preprocessor Invert Selection Buffer").as_str()),
             contents: bytemuck::bytes_of(&1u32),
             usage: Self::DEFAULT_USAGES,
         });
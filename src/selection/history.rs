@@ -0,0 +1,164 @@
+use std::collections::VecDeque;
+
+use crate::{core::BufferWrapper, editor::SelectionBuffer};
+
+/// A bounded undo and redo history of a [`SelectionBuffer`].
+///
+/// The snapshots are copies of the selection buffer kept on the GPU, so pushing, undoing, and
+/// redoing only record buffer copies in the encoder without any readback. Each snapshot takes
+/// `ceil(n / 32)` words for `n` Gaussians, which is small compared to the Gaussians buffer.
+#[derive(Debug)]
+pub struct SelectionHistory {
+    /// The undo snapshots, the most recent one at the back.
+    undo: VecDeque<wgpu::Buffer>,
+    /// The redo snapshots, the most recent one at the back.
+    redo: Vec<wgpu::Buffer>,
+    /// The unused snapshot buffers for reuse.
+    pool: Vec<wgpu::Buffer>,
+    /// The maximum number of undo snapshots.
+    capacity: usize,
+}
+
+impl SelectionHistory {
+    /// The default maximum number of undo snapshots.
+    pub const DEFAULT_CAPACITY: usize = 32;
+
+    /// Create a new selection history keeping at most `capacity` undo snapshots.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo: VecDeque::with_capacity(capacity),
+            redo: Vec::new(),
+            pool: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Get the maximum number of undo snapshots.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Check whether there is a snapshot to undo to.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Check whether there is a snapshot to redo to.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Snapshot the current `selection`, which should be called before modifying it.
+    ///
+    /// The redo snapshots are discarded. If there are already [`SelectionHistory::capacity`]
+    /// undo snapshots, the oldest one is discarded.
+    pub fn push(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        selection: &SelectionBuffer,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.pool.append(&mut self.redo);
+        if self.undo.len() == self.capacity {
+            self.pool.extend(self.undo.pop_front());
+        }
+
+        let snapshot = self.snapshot(device, encoder, selection);
+        self.undo.push_back(snapshot);
+    }
+
+    /// Restore the `selection` to the most recent undo snapshot.
+    ///
+    /// The current `selection` is kept as a redo snapshot. Returns `false` if there is nothing
+    /// to undo.
+    pub fn undo(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        selection: &SelectionBuffer,
+    ) -> bool {
+        let Some(snapshot) = self.undo.pop_back() else {
+            return false;
+        };
+
+        let current = self.snapshot(device, encoder, selection);
+        self.redo.push(current);
+        self.restore(encoder, snapshot, selection);
+
+        true
+    }
+
+    /// Restore the `selection` to the most recent redo snapshot.
+    ///
+    /// The current `selection` is kept as an undo snapshot. Returns `false` if there is nothing
+    /// to redo.
+    pub fn redo(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        selection: &SelectionBuffer,
+    ) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+
+        let current = self.snapshot(device, encoder, selection);
+        self.undo.push_back(current);
+        self.restore(encoder, snapshot, selection);
+
+        true
+    }
+
+    /// Discard all undo and redo snapshots.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.pool.clear();
+    }
+
+    /// Copy the `selection` into a snapshot buffer, reusing a pooled one if possible.
+    fn snapshot(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        selection: &SelectionBuffer,
+    ) -> wgpu::Buffer {
+        let size = selection.buffer().size();
+        let snapshot = match self.pool.iter().position(|buffer| buffer.size() == size) {
+            Some(i) => self.pool.swap_remove(i),
+            None => device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Selection History Snapshot Buffer"),
+                size,
+                usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+        };
+
+        encoder.copy_buffer_to_buffer(selection.buffer(), 0, &snapshot, 0, size);
+
+        snapshot
+    }
+
+    /// Copy the `snapshot` back into the `selection` and return the buffer to the pool.
+    fn restore(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        snapshot: wgpu::Buffer,
+        selection: &SelectionBuffer,
+    ) {
+        let size = snapshot.size().min(selection.buffer().size());
+        encoder.copy_buffer_to_buffer(&snapshot, 0, selection.buffer(), 0, size);
+
+        self.pool.push(snapshot);
+    }
+}
+
+impl Default for SelectionHistory {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
@@ -0,0 +1,275 @@
+use glam::*;
+
+use crate::{
+    core::{self, BufferWrapper, FixedSizeBufferWrapper},
+    editor::SelectionBuffer,
+    prefixed_label,
+};
+
+/// The storage of the selection layers.
+///
+/// Each layer is a slot of the same size as a [`SelectionBuffer`] of the Gaussians, laid out one
+/// after another. The active layer is edited in place in the [`SelectionBuffer`] bound to the
+/// [`Preprocessor`](crate::Preprocessor) and the selection operations, while the other layers are
+/// kept in their slots. [`SelectionLayersBuffer::switch_active`] swaps the active layer in and out
+/// with buffer copies, so switching does not require recreating any bind group.
+#[derive(Debug, Clone)]
+pub struct SelectionLayersBuffer {
+    /// The buffer.
+    buffer: wgpu::Buffer,
+    /// The number of layers.
+    layer_count: u32,
+    /// The additional label.
+    label: String,
+}
+
+impl SelectionLayersBuffer {
+    /// Create a new selection layers buffer.
+    ///
+    /// There is at least one layer.
+    pub fn new(device: &wgpu::Device, gaussian_count: u32, layer_count: u32) -> Self {
+        Self::new_with_label(device, "", gaussian_count, layer_count)
+    }
+
+    /// Create a new selection layers buffer with additional label.
+    ///
+    /// There is at least one layer.
+    pub fn new_with_label(
+        device: &wgpu::Device,
+        label: &str,
+        gaussian_count: u32,
+        layer_count: u32,
+    ) -> Self {
+        let layer_count = layer_count.max(1);
+        let layer_size = Self::layer_size_of(gaussian_count);
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(prefixed_label(label, "Selection Layers Buffer").as_str()),
+            size: layer_size * layer_count as wgpu::BufferAddress,
+            usage: Self::DEFAULT_USAGES,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            buffer,
+            layer_count,
+            label: label.to_string(),
+        }
+    }
+
+    /// Get the number of layers.
+    pub fn layer_count(&self) -> u32 {
+        self.layer_count
+    }
+
+    /// Get the size in bytes of each layer.
+    pub fn layer_size(&self) -> wgpu::BufferAddress {
+        self.buffer.size() / self.layer_count as wgpu::BufferAddress
+    }
+
+    /// Switch the active layer from `from` to `to` in the `encoder`.
+    ///
+    /// The `selection` holding the layer `from` is stored into its slot, then the slot of the
+    /// layer `to` is loaded into the `selection`.
+    pub fn switch_active(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        selection: &SelectionBuffer,
+        from: u32,
+        to: u32,
+    ) {
+        if from == to {
+            return;
+        }
+
+        let layer_size = self.layer_size();
+
+        encoder.copy_buffer_to_buffer(
+            selection.buffer(),
+            0,
+            &self.buffer,
+            from as wgpu::BufferAddress * layer_size,
+            layer_size,
+        );
+        encoder.copy_buffer_to_buffer(
+            &self.buffer,
+            to as wgpu::BufferAddress * layer_size,
+            selection.buffer(),
+            0,
+            layer_size,
+        );
+    }
+
+    /// Create a copy of the layers for `gaussian_count` Gaussians in the `encoder`.
+    ///
+    /// The copy has the same label. The selection of the existing Gaussians in each layer is kept,
+    /// and the new Gaussians are not selected.
+    pub fn resized(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        gaussian_count: u32,
+    ) -> Self {
+        let resized = Self::new_with_label(device, &self.label, gaussian_count, self.layer_count);
+
+        let layer_size = self.layer_size();
+        let resized_layer_size = resized.layer_size();
+
+        for layer in 0..self.layer_count as wgpu::BufferAddress {
+            encoder.copy_buffer_to_buffer(
+                &self.buffer,
+                layer * layer_size,
+                &resized.buffer,
+                layer * resized_layer_size,
+                layer_size.min(resized_layer_size),
+            );
+        }
+
+        resized
+    }
+
+    /// Get the size in bytes of each layer for `gaussian_count` Gaussians, same as the
+    /// [`SelectionBuffer`].
+    fn layer_size_of(gaussian_count: u32) -> wgpu::BufferAddress {
+        (gaussian_count.div_ceil(32) as usize * std::mem::size_of::<u32>()) as wgpu::BufferAddress
+    }
+}
+
+impl BufferWrapper for SelectionLayersBuffer {
+    const DEFAULT_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::from_bits_retain(
+        wgpu::BufferUsages::STORAGE.bits()
+            | wgpu::BufferUsages::COPY_DST.bits()
+            | wgpu::BufferUsages::COPY_SRC.bits(),
+    );
+
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+    }
+}
+
+impl From<SelectionLayersBuffer> for wgpu::Buffer {
+    fn from(wrapper: SelectionLayersBuffer) -> Self {
+        wrapper.buffer
+    }
+}
+
+/// The selection highlight buffer for [`Renderer`](crate::Renderer).
+///
+/// This holds the highlight color of each selection layer and the index of the active layer.
+#[derive(Debug, Clone)]
+pub struct SelectionHighlightBuffer(wgpu::Buffer);
+
+impl SelectionHighlightBuffer {
+    /// Create a new selection highlight buffer.
+    ///
+    /// Note: the initial value does not highlight any layer.
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_label(device, "")
+    }
+
+    /// Create a new selection highlight buffer with additional label.
+    ///
+    /// Note: the initial value does not highlight any layer.
+    pub fn new_with_label(device: &wgpu::Device, label: &str) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(prefixed_label(label, "Selection Highlight Buffer").as_str()),
+            size: std::mem::size_of::<SelectionHighlightPod>() as wgpu::BufferAddress,
+            usage: Self::DEFAULT_USAGES,
+            mapped_at_creation: false,
+        });
+
+        Self(buffer)
+    }
+
+    /// Update the highlight colors of the layers.
+    ///
+    /// See [`SelectionHighlightPod::colors`] for the colors. The layers without a color in
+    /// `colors` are not highlighted.
+    pub fn update_colors(&self, queue: &wgpu::Queue, colors: &[Vec4]) {
+        let mut padded = [Vec4::ZERO; SelectionHighlightPod::MAX_LAYERS];
+        padded
+            .iter_mut()
+            .zip(colors)
+            .for_each(|(padded, color)| *padded = *color);
+
+        queue.write_buffer(&self.0, 0, bytemuck::cast_slice(&padded));
+    }
+
+    /// Update the index of the active layer.
+    pub fn update_active_layer(&self, queue: &wgpu::Queue, active_layer: u32) {
+        queue.write_buffer(
+            &self.0,
+            std::mem::offset_of!(SelectionHighlightPod, active_layer) as wgpu::BufferAddress,
+            bytemuck::bytes_of(&active_layer),
+        );
+    }
+
+    /// Update the selection highlight buffer with [`SelectionHighlightPod`].
+    pub fn update_with_pod(&self, queue: &wgpu::Queue, pod: &SelectionHighlightPod) {
+        queue.write_buffer(&self.0, 0, bytemuck::bytes_of(pod));
+    }
+}
+
+impl BufferWrapper for SelectionHighlightBuffer {
+    fn buffer(&self) -> &wgpu::Buffer {
+        &self.0
+    }
+}
+
+impl From<SelectionHighlightBuffer> for wgpu::Buffer {
+    fn from(wrapper: SelectionHighlightBuffer) -> Self {
+        wrapper.0
+    }
+}
+
+impl TryFrom<wgpu::Buffer> for SelectionHighlightBuffer {
+    type Error = core::FixedSizeBufferWrapperError;
+
+    fn try_from(buffer: wgpu::Buffer) -> Result<Self, Self::Error> {
+        Self::verify_buffer_size(&buffer).map(|()| Self(buffer))
+    }
+}
+
+impl FixedSizeBufferWrapper for SelectionHighlightBuffer {
+    type Pod = SelectionHighlightPod;
+}
+
+/// The POD representation of the selection highlight.
+///
+/// The zeroed value is the default, which does not highlight any layer.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SelectionHighlightPod {
+    /// The highlight color of each layer.
+    ///
+    /// The RGB of the selected Gaussians is mixed towards the color by its alpha, so an alpha of
+    /// 0 disables the highlight of the layer. Gaussians in multiple layers are mixed in the order
+    /// of the layers.
+    pub colors: [Vec4; Self::MAX_LAYERS],
+    /// The index of the active layer, whose selection is read from the [`SelectionBuffer`]
+    /// instead of the [`SelectionLayersBuffer`].
+    pub active_layer: u32,
+    pub _padding: [u32; 3],
+}
+
+impl SelectionHighlightPod {
+    /// The maximum number of layers that can be highlighted.
+    ///
+    /// There can be more layers in [`SelectionLayersBuffer`], the rest are not highlighted.
+    pub const MAX_LAYERS: usize = 8;
+
+    /// Create a new selection highlight.
+    ///
+    /// The layers without a color in `colors` are not highlighted.
+    pub fn new(colors: &[Vec4], active_layer: u32) -> Self {
+        let mut pod = Self {
+            active_layer,
+            ..Default::default()
+        };
+        pod.colors
+            .iter_mut()
+            .zip(colors)
+            .for_each(|(pod_color, color)| *pod_color = *color);
+        pod
+    }
+}
@@ -77,12 +77,18 @@
 //! [`editor`](crate::editor) module, and read through its documentation.
 
 mod buffer;
+mod history;
+mod layer;
+mod query;
 mod viewport;
 mod viewport_selector;
 mod viewport_texture_brush;
 mod viewport_texture_rectangle;
 
 pub use buffer::*;
+pub use history::*;
+pub use layer::*;
+pub use query::*;
 pub use viewport::*;
 pub use viewport_selector::*;
 pub use viewport_texture_brush::*;
@@ -0,0 +1,39 @@
+use crate::{SelectionIsSelectedError, core::BufferWrapper, editor::SelectionBuffer};
+
+/// Check whether the Gaussian at `index` is selected in the [`SelectionBuffer`].
+///
+/// Only the 4-byte word containing the bit of `index` is copied back from the GPU, so this is
+/// cheap enough to be used for single Gaussian queries, e.g. tooltips on hover.
+pub async fn is_selected(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    selection: &SelectionBuffer,
+    index: u32,
+) -> Result<bool, SelectionIsSelectedError> {
+    const WORD_SIZE: wgpu::BufferAddress = std::mem::size_of::<u32>() as wgpu::BufferAddress;
+
+    let offset = (index / 32) as wgpu::BufferAddress * WORD_SIZE;
+    if offset + WORD_SIZE > selection.buffer().size() {
+        return Err(SelectionIsSelectedError::IndexOutOfBounds {
+            index,
+            len: selection.buffer().size() / WORD_SIZE * 32,
+        });
+    }
+
+    let download = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Selection Is Selected Download Buffer"),
+        size: WORD_SIZE,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Selection Is Selected Encoder"),
+    });
+    encoder.copy_buffer_to_buffer(selection.buffer(), offset, &download, 0, WORD_SIZE);
+    queue.submit(Some(encoder.finish()));
+
+    let word = wgpu::Buffer::map_download::<u32>(&download, device).await?[0];
+
+    Ok(word & (1 << (index % 32)) != 0)
+}
@@ -38,6 +38,14 @@ pub const VIEWPORT_BIND_GROUP_LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor
 ///
 /// - Bind group 0 is [`SelectionBundle::GAUSSIANS_BIND_GROUP_LAYOUT_DESCRIPTOR`].
 /// - Bind group 1 is [`VIEWPORT_BIND_GROUP_LAYOUT_DESCRIPTOR`].
+///
+/// The operation runs one invocation per Gaussian of the model, each projecting the center and
+/// reading a single texel of the [`ViewportTexture`](super::ViewportTexture). So the cost scales
+/// with the total number of Gaussians, not with the area of the rectangle or brush strokes nor the
+/// number of selected Gaussians, and is roughly the cost of the frustum culling in
+/// [`Preprocessor`](crate::Preprocessor). Gaussians outside of the view frustum are rejected
+/// before the texel read. There is no spatial acceleration, so for region queries on very large
+/// models, prefer evaluating the selection once on release instead of every frame while dragging.
 pub fn create_viewport_bundle<G: GaussianPod>(device: &wgpu::Device) -> ComputeBundle<()> {
     let mut resolver = wesl::PkgResolver::new();
     resolver.add_package(&core::shader::PACKAGE);
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use glam::*;
 
 use crate::{
@@ -37,6 +39,10 @@ pub enum ViewportSelectorType {
 /// - [`clear`](Self::clear): called to clear the selection texture (e.g., mouse button up).
 /// - [`render`](Self::render): called to render the selection to the viewport texture (e.g., every frame).
 ///
+/// The selector type can be switched with [`set_type`](Self::set_type) between selections
+/// without clearing, e.g. brush on left drag and rectangle on right drag, to combine them into one
+/// selection mask.
+///
 /// Also, don't forget to evaluate and apply the selection using either a
 /// [`editor::SelectionBundle`](crate::editor::SelectionBundle) or a selection modifier like
 /// [`editor::BasicSelectionModifier`](crate::editor::BasicSelectionModifier) after the selection is
@@ -174,6 +180,15 @@ pub struct ViewportSelector {
     /// The radius of the brush selection.
     brush_radius: f32,
 
+    /// The bounds of [`ViewportSelector::brush_radius`], [`None`] for the default bounds.
+    brush_radius_bounds: Option<RangeInclusive<f32>>,
+
+    /// The width of the antialiased edge of the selection.
+    edge_width: f32,
+
+    /// The scale factor of the viewport, e.g. the DPI scale factor of the window.
+    scale_factor: f32,
+
     /// The buffer for [`ViewportSelector::start_pos`].
     start_buffer: ViewportTexturePosBuffer,
 
@@ -183,9 +198,19 @@ pub struct ViewportSelector {
     /// The buffer for [`ViewportSelector::brush_radius`].
     radius_buffer: ViewportTextureF32Buffer,
 
+    /// The buffer for [`ViewportSelector::edge_width`].
+    edge_width_buffer: ViewportTextureF32Buffer,
+
     /// The viewport texture holding the selection.
     viewport_texture: ViewportTexture,
 
+    /// The snapshot of [`ViewportSelector::viewport_texture`] taken when the selector type is
+    /// switched, which the rectangle selection is drawn over.
+    base_texture: ViewportTexture,
+
+    /// Whether [`ViewportSelector::base_texture`] holds a snapshot since the last clear.
+    has_base: bool,
+
     /// The rectangle renderer for viewport selection.
     rectangle_renderer: ViewportTextureRectangleRenderer,
 
@@ -193,6 +218,8 @@ pub struct ViewportSelector {
     brush_renderer: ViewportTextureBrushRenderer,
 
     /// The selector type.
+    ///
+    /// Use [`ViewportSelector::set_type`] to switch the type while keeping the selection.
     pub selector_type: ViewportSelectorType,
 }
 
@@ -200,6 +227,15 @@ impl ViewportSelector {
     /// The default brush radius.
     pub const DEFAULT_BRUSH_RADIUS: f32 = 50.0;
 
+    /// The default maximum brush radius, as a fraction of the smaller dimension of the viewport.
+    pub const DEFAULT_BRUSH_RADIUS_MAX_VIEWPORT_FRACTION: f32 = 0.5;
+
+    /// The default edge width, which disables antialiasing.
+    pub const DEFAULT_EDGE_WIDTH: f32 = 0.0;
+
+    /// The default scale factor.
+    pub const DEFAULT_SCALE_FACTOR: f32 = 1.0;
+
     /// Create a new viewport selector.
     pub fn new(
         device: &wgpu::Device,
@@ -209,15 +245,23 @@ impl ViewportSelector {
     ) -> Result<Self, RendererCreateError> {
         let start_buffer = ViewportTexturePosBuffer::new(device);
         let end_buffer = ViewportTexturePosBuffer::new(device);
+        let brush_radius = Self::DEFAULT_BRUSH_RADIUS.min(Self::default_brush_radius_max(
+            viewport_size,
+            Self::DEFAULT_SCALE_FACTOR,
+        ));
         let radius_buffer = ViewportTextureF32Buffer::new(device);
-        radius_buffer.update(queue, Self::DEFAULT_BRUSH_RADIUS);
+        radius_buffer.update(queue, brush_radius);
+        let edge_width_buffer = ViewportTextureF32Buffer::new(device);
+        edge_width_buffer.update(queue, Self::DEFAULT_EDGE_WIDTH);
         let viewport_texture = ViewportTexture::new(device, viewport_size);
+        let base_texture = ViewportTexture::new(device, viewport_size);
         let rectangle_renderer = ViewportTextureRectangleRenderer::new(
             device,
             &viewport_texture,
             camera,
             &start_buffer,
             &end_buffer,
+            &edge_width_buffer,
         )?;
         let brush_renderer = ViewportTextureBrushRenderer::new(
             device,
@@ -226,18 +270,25 @@ impl ViewportSelector {
             &start_buffer,
             &end_buffer,
             &radius_buffer,
+            &edge_width_buffer,
         )?;
 
         Ok(Self {
             start_pos: None,
             end_pos: None,
-            brush_radius: Self::DEFAULT_BRUSH_RADIUS,
+            brush_radius,
+            brush_radius_bounds: None,
+            edge_width: Self::DEFAULT_EDGE_WIDTH,
+            scale_factor: Self::DEFAULT_SCALE_FACTOR,
 
             start_buffer,
             end_buffer,
             radius_buffer,
+            edge_width_buffer,
 
             viewport_texture,
+            base_texture,
+            has_base: false,
 
             rectangle_renderer,
             brush_renderer,
@@ -248,6 +299,10 @@ impl ViewportSelector {
 
     /// Start the selection at the given position.
     pub fn start(&mut self, queue: &wgpu::Queue, pos: Vec2) {
+        // The brush radius may be clamped by a resize without the queue
+        self.radius_buffer
+            .update(queue, self.brush_radius * self.scale_factor);
+
         self.start_pos = Some(pos);
         self.start_buffer.update(queue, pos);
         self.end_pos = Some(pos);
@@ -271,8 +326,39 @@ impl ViewportSelector {
         }
     }
 
+    /// Set the selector type, keeping the selection rendered so far.
+    ///
+    /// The viewport texture is snapshotted in the `encoder`, and the following selections are
+    /// added to it until [`ViewportSelector::clear`]. The positions of the selection in progress are
+    /// reset, so [`ViewportSelector::start`] should be called before the next
+    /// [`ViewportSelector::update`].
+    pub fn set_type(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        selector_type: ViewportSelectorType,
+    ) {
+        if self.selector_type == selector_type {
+            return;
+        }
+
+        encoder.copy_texture_to_texture(
+            self.viewport_texture.texture().as_image_copy(),
+            self.base_texture.texture().as_image_copy(),
+            self.viewport_texture.texture().size(),
+        );
+
+        self.has_base = true;
+        self.start_pos = None;
+        self.end_pos = None;
+        self.selector_type = selector_type;
+    }
+
     /// Clear the selection viewport texture.
+    ///
+    /// This also discards the selection kept by [`ViewportSelector::set_type`].
     pub fn clear(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        self.has_base = false;
+
         encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Viewport Selection Clear Pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -289,11 +375,42 @@ impl ViewportSelector {
     }
 
     /// Render the selection rectangle.
+    ///
+    /// Nothing is rendered after [`ViewportSelector::set_type`] until the selection is started.
     pub fn render(&self, encoder: &mut wgpu::CommandEncoder) {
         match self.selector_type {
+            ViewportSelectorType::Rectangle if self.has_base => {
+                // Redraw the rectangle over the snapshot instead of clearing
+                encoder.copy_texture_to_texture(
+                    self.base_texture.texture().as_image_copy(),
+                    self.viewport_texture.texture().as_image_copy(),
+                    self.viewport_texture.texture().size(),
+                );
+
+                if self.start_pos.is_none() {
+                    return;
+                }
+
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Viewport Selection Rectangle Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: self.viewport_texture.view(),
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    ..Default::default()
+                });
+
+                self.rectangle_renderer.render_with_pass(&mut render_pass);
+            }
             ViewportSelectorType::Rectangle => self
                 .rectangle_renderer
                 .render(encoder, &self.viewport_texture),
+            ViewportSelectorType::Brush if self.has_base && self.start_pos.is_none() => {}
             ViewportSelectorType::Brush => {
                 self.brush_renderer.render(encoder, &self.viewport_texture)
             }
@@ -301,20 +418,121 @@ impl ViewportSelector {
     }
 
     /// Get the viewport texture.
+    ///
+    /// This is the selection mask, which can be sampled for custom compositing of the selection
+    /// overlay, or checked with [`ViewportTexture::is_empty`].
     pub fn texture(&self) -> &ViewportTexture {
         &self.viewport_texture
     }
 
     /// Set the brush radius.
+    ///
+    /// The radius is in logical pixels, it is multiplied by the scale factor set by
+    /// [`ViewportSelector::set_scale_factor`]. It is clamped to
+    /// [`ViewportSelector::brush_radius_bounds`].
     pub fn set_brush_radius(&mut self, queue: &wgpu::Queue, radius: f32) {
-        self.brush_radius = radius;
-        self.radius_buffer.update(queue, radius);
+        let bounds = self.brush_radius_bounds();
+        self.brush_radius = radius.clamp(*bounds.start(), *bounds.end());
+        self.radius_buffer
+            .update(queue, self.brush_radius * self.scale_factor);
+    }
+
+    /// Get the brush radius.
+    pub fn brush_radius(&self) -> f32 {
+        self.brush_radius
+    }
+
+    /// Set the bounds of the brush radius in logical pixels.
+    ///
+    /// [`None`] uses the default bounds from 0 to
+    /// [`ViewportSelector::DEFAULT_BRUSH_RADIUS_MAX_VIEWPORT_FRACTION`] of the viewport, which
+    /// follow [`ViewportSelector::resize`] and [`ViewportSelector::set_scale_factor`]. The current
+    /// brush radius is clamped to the new bounds.
+    ///
+    /// Reversed bounds are swapped, and bounds with NaN are ignored, keeping the current bounds.
+    pub fn set_brush_radius_bounds(
+        &mut self,
+        queue: &wgpu::Queue,
+        bounds: Option<RangeInclusive<f32>>,
+    ) {
+        let bounds = match bounds {
+            Some(bounds) if bounds.start().is_nan() || bounds.end().is_nan() => return,
+            Some(bounds) => {
+                let (start, end) = bounds.into_inner();
+                Some(start.min(end)..=start.max(end))
+            }
+            None => None,
+        };
+
+        self.brush_radius_bounds = bounds;
+        self.set_brush_radius(queue, self.brush_radius);
+    }
+
+    /// Set the minimum and maximum of the brush radius in logical pixels.
+    ///
+    /// This is the same as [`ViewportSelector::set_brush_radius_bounds`] with `min..=max`.
+    pub fn set_radius_bounds(&mut self, queue: &wgpu::Queue, min: f32, max: f32) {
+        self.set_brush_radius_bounds(queue, Some(min..=max));
+    }
+
+    /// Get the bounds of the brush radius in logical pixels.
+    pub fn brush_radius_bounds(&self) -> RangeInclusive<f32> {
+        self.brush_radius_bounds.clone().unwrap_or_else(|| {
+            let size = self.viewport_texture.texture().size();
+            0.0..=Self::default_brush_radius_max(
+                UVec2::new(size.width, size.height),
+                self.scale_factor,
+            )
+        })
+    }
+
+    /// Set the width of the antialiased edge of the selection.
+    ///
+    /// The width is in logical pixels, it is multiplied by the scale factor set by
+    /// [`ViewportSelector::set_scale_factor`]. A width of 0 disables antialiasing.
+    pub fn set_edge_width(&mut self, queue: &wgpu::Queue, edge_width: f32) {
+        self.edge_width = edge_width;
+        self.edge_width_buffer
+            .update(queue, self.edge_width * self.scale_factor);
+    }
+
+    /// Get the width of the antialiased edge of the selection.
+    pub fn edge_width(&self) -> f32 {
+        self.edge_width
+    }
+
+    /// Set the scale factor of the viewport, e.g. the DPI scale factor of the window.
+    ///
+    /// This scales the brush radius and edge width, so they look the same on high-DPI displays.
+    pub fn set_scale_factor(&mut self, queue: &wgpu::Queue, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+        self.set_brush_radius(queue, self.brush_radius);
+        self.edge_width_buffer
+            .update(queue, self.edge_width * self.scale_factor);
+    }
+
+    /// Get the scale factor of the viewport.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
     }
 
     /// Update the viewport size.
     ///
-    /// After calling this method, you need to update bind groups that uses this texture.
+    /// After calling this method, you need to update bind groups that uses this texture. The brush
+    /// radius is clamped to [`ViewportSelector::brush_radius_bounds`], which follow the new size
+    /// by default, taking effect from the next [`ViewportSelector::start`].
     pub fn resize(&mut self, device: &wgpu::Device, new_size: UVec2) {
         self.viewport_texture = ViewportTexture::new(device, new_size);
+        self.base_texture = ViewportTexture::new(device, new_size);
+        self.has_base = false;
+
+        let bounds = self.brush_radius_bounds();
+        self.brush_radius = self.brush_radius.clamp(*bounds.start(), *bounds.end());
+    }
+
+    /// Get the default maximum brush radius in logical pixels for the viewport size.
+    fn default_brush_radius_max(viewport_size: UVec2, scale_factor: f32) -> f32 {
+        Self::DEFAULT_BRUSH_RADIUS_MAX_VIEWPORT_FRACTION * viewport_size.min_element() as f32
+            / scale_factor
     }
 }
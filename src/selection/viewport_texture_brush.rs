@@ -26,6 +26,7 @@ impl<B> ViewportTextureBrushRenderer<B> {
         start: &ViewportTexturePosBuffer,
         end: &ViewportTexturePosBuffer,
         radius: &ViewportTextureF32Buffer,
+        edge_width: &ViewportTextureF32Buffer,
     ) -> wgpu::BindGroup {
         ViewportTextureBrushRenderer::create_bind_group_static(
             device,
@@ -34,6 +35,7 @@ impl<B> ViewportTextureBrushRenderer<B> {
             start,
             end,
             radius,
+            edge_width,
         )
     }
 }
@@ -80,7 +82,18 @@ impl ViewportTextureBrushRenderer {
                 // Radius uniform buffer
                 wgpu::BindGroupLayoutEntry {
                     binding: 3,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Edge width uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -99,11 +112,12 @@ impl ViewportTextureBrushRenderer {
         start: &ViewportTexturePosBuffer,
         end: &ViewportTexturePosBuffer,
         radius: &ViewportTextureF32Buffer,
+        edge_width: &ViewportTextureF32Buffer,
     ) -> Result<Self, RendererCreateError> {
         let this = ViewportTextureBrushRenderer::new_without_bind_group(device, texture)?;
 
         log::debug!("Creating viewport texture brush renderer bind group");
-        let bind_group = this.create_bind_group(device, camera, start, end, radius);
+        let bind_group = this.create_bind_group(device, camera, start, end, radius, edge_width);
 
         Ok(Self {
             bind_group_layout: this.bind_group_layout,
@@ -146,6 +160,7 @@ impl ViewportTextureBrushRenderer {
         start: &ViewportTexturePosBuffer,
         end: &ViewportTexturePosBuffer,
         radius: &ViewportTextureF32Buffer,
+        edge_width: &ViewportTextureF32Buffer,
     ) -> wgpu::BindGroup {
         device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Viewport Texture Brush Renderer Bind Group"),
@@ -171,6 +186,11 @@ impl ViewportTextureBrushRenderer {
                     binding: 3,
                     resource: radius.buffer().as_entire_binding(),
                 },
+                // Edge width uniform buffer
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: edge_width.buffer().as_entire_binding(),
+                },
             ],
         })
     }
@@ -228,7 +248,18 @@ impl ViewportTextureBrushRenderer<()> {
                 entry_point: Some("frag_main"),
                 targets: &[Some(wgpu::ColorTargetState {
                     format: texture.texture().format(),
-                    blend: None,
+                    blend: Some(wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::One,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Max,
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::One,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Max,
+                        },
+                    }),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
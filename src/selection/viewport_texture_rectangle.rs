@@ -1,7 +1,7 @@
 use crate::{
     CameraBuffer, RendererCreateError,
     core::BufferWrapper,
-    selection::{ViewportTexture, ViewportTexturePosBuffer},
+    selection::{ViewportTexture, ViewportTextureF32Buffer, ViewportTexturePosBuffer},
     wesl_utils,
 };
 
@@ -25,6 +25,7 @@ impl<B> ViewportTextureRectangleRenderer<B> {
         camera: &CameraBuffer,
         top_left: &ViewportTexturePosBuffer,
         bottom_right: &ViewportTexturePosBuffer,
+        edge_width: &ViewportTextureF32Buffer,
     ) -> wgpu::BindGroup {
         ViewportTextureRectangleRenderer::create_bind_group_static(
             device,
@@ -32,6 +33,7 @@ impl<B> ViewportTextureRectangleRenderer<B> {
             camera,
             top_left,
             bottom_right,
+            edge_width,
         )
     }
 }
@@ -56,7 +58,7 @@ impl ViewportTextureRectangleRenderer {
                 // Top left uniform buffer
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -67,7 +69,18 @@ impl ViewportTextureRectangleRenderer {
                 // Bottom right uniform buffer
                 wgpu::BindGroupLayoutEntry {
                     binding: 2,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Edge width uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -85,11 +98,12 @@ impl ViewportTextureRectangleRenderer {
         camera: &CameraBuffer,
         top_left: &ViewportTexturePosBuffer,
         bottom_right: &ViewportTexturePosBuffer,
+        edge_width: &ViewportTextureF32Buffer,
     ) -> Result<Self, RendererCreateError> {
         let this = ViewportTextureRectangleRenderer::new_without_bind_group(device, texture)?;
 
         log::debug!("Creating viewport texture rectangle renderer bind group");
-        let bind_group = this.create_bind_group(device, camera, top_left, bottom_right);
+        let bind_group = this.create_bind_group(device, camera, top_left, bottom_right, edge_width);
 
         Ok(Self {
             bind_group_layout: this.bind_group_layout,
@@ -131,6 +145,7 @@ impl ViewportTextureRectangleRenderer {
         camera: &CameraBuffer,
         top_left: &ViewportTexturePosBuffer,
         bottom_right: &ViewportTexturePosBuffer,
+        edge_width: &ViewportTextureF32Buffer,
     ) -> wgpu::BindGroup {
         device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Viewport Texture Rectangle Renderer Bind Group"),
@@ -151,6 +166,11 @@ impl ViewportTextureRectangleRenderer {
                     binding: 2,
                     resource: bottom_right.buffer().as_entire_binding(),
                 },
+                // Edge width uniform buffer
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: edge_width.buffer().as_entire_binding(),
+                },
             ],
         })
     }
@@ -209,7 +229,18 @@ impl ViewportTextureRectangleRenderer<()> {
                 entry_point: Some("frag_main"),
                 targets: &[Some(wgpu::ColorTargetState {
                     format: texture.texture().format(),
-                    blend: None,
+                    blend: Some(wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::One,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Max,
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::One,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Max,
+                        },
+                    }),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
@@ -0,0 +1,192 @@
+use std::ops::Range;
+
+use glam::*;
+
+use crate::core::{Gaussians, IterGaussian};
+
+/// The ranges of the SH coefficients of degree 1 to 3 in
+/// [`Gaussian::sh`](crate::core::Gaussian::sh).
+const SH_BANDS: [Range<usize>; 3] = [0..3, 3..8, 8..15];
+
+/// The number of sample directions to fit the rotation of each band.
+const SH_ROTATION_SAMPLES: usize = 64;
+
+/// The rotation of the SH coefficients of degree 1 to 3.
+///
+/// Each degree (band) of the SH is closed under rotation, so rotating the view dependent color
+/// of a Gaussian is a linear map within each band, i.e. a block diagonal matrix of the Wigner-D
+/// matrices in the real SH basis. The blocks are fitted by least squares over sample directions
+/// in the same basis and sign convention as the `view_color` function in the shaders, so they are
+/// exact up to floating point precision.
+///
+/// The rotation is computed once and can be applied to any number of Gaussians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShRotation {
+    /// The block diagonal matrix, each row is the weights of an output coefficient.
+    matrix: [[f32; 15]; 15],
+}
+
+impl ShRotation {
+    /// Compute the SH rotation of `rot`.
+    pub fn new(rot: Quat) -> Self {
+        let inv_rot = rot.as_dquat().inverse();
+
+        // Fibonacci sphere
+        let directions = (0..SH_ROTATION_SAMPLES)
+            .map(|i| {
+                let golden_angle = std::f64::consts::PI * (3.0 - 5f64.sqrt());
+                let y = 1.0 - 2.0 * (i as f64 + 0.5) / SH_ROTATION_SAMPLES as f64;
+                let r = (1.0 - y * y).sqrt();
+                let theta = golden_angle * i as f64;
+                DVec3::new(r * theta.cos(), y, r * theta.sin())
+            })
+            .collect::<Vec<_>>();
+        let basis = directions.iter().map(|d| sh_basis(*d)).collect::<Vec<_>>();
+        let rotated_basis = directions
+            .iter()
+            .map(|d| sh_basis(inv_rot * *d))
+            .collect::<Vec<_>>();
+
+        let mut matrix = [[0.0; 15]; 15];
+        for band in SH_BANDS {
+            // Solve the normal equations of basis * x = rotated_basis, where x[j][i] is the weight
+            // of the coefficient i in the rotated coefficient j
+            let normal = band
+                .clone()
+                .map(|j| {
+                    band.clone()
+                        .map(|k| basis.iter().map(|b| b[j] * b[k]).sum::<f64>())
+                        .collect()
+                })
+                .collect();
+            let rhs = band
+                .clone()
+                .map(|j| {
+                    band.clone()
+                        .map(|i| {
+                            basis
+                                .iter()
+                                .zip(&rotated_basis)
+                                .map(|(b, r)| b[j] * r[i])
+                                .sum::<f64>()
+                        })
+                        .collect()
+                })
+                .collect();
+
+            for (j, row) in band.clone().zip(solve(normal, rhs)) {
+                for (i, weight) in band.clone().zip(row) {
+                    matrix[j][i] = weight as f32;
+                }
+            }
+        }
+
+        Self { matrix }
+    }
+
+    /// Rotate the SH coefficients.
+    pub fn apply(&self, sh: &mut [Vec3; 15]) {
+        let original = *sh;
+        for (coefficient, weights) in sh.iter_mut().zip(&self.matrix) {
+            *coefficient = weights
+                .iter()
+                .zip(&original)
+                .map(|(weight, coefficient)| *weight * *coefficient)
+                .sum();
+        }
+    }
+}
+
+/// Rotate the SH coefficients of degree 1 to 3 by `rot`.
+///
+/// The view dependent color in the direction `rot * dir` after the rotation is the same as in
+/// `dir` before. Use [`ShRotation`] to rotate many coefficients by the same rotation.
+pub fn rotate_sh(sh: &mut [Vec3; 15], rot: Quat) {
+    ShRotation::new(rot).apply(sh);
+}
+
+/// Bake the rotation `rot` about the origin into the Gaussians.
+///
+/// The positions, rotations, and SH coefficients are rotated, so the Gaussians render the same as
+/// with a model transform of `rot`, including the view dependent colors. The source
+/// representation of the Gaussians is kept.
+pub fn rotate_gaussians(gaussians: &mut Gaussians, rot: Quat) {
+    let sh_rotation = ShRotation::new(rot);
+
+    *gaussians = Gaussians::from_gaussians_iter(
+        gaussians.iter_gaussian().map(|mut gaussian| {
+            gaussian.pos = rot * gaussian.pos;
+            gaussian.rot = (rot * gaussian.rot).normalize();
+            sh_rotation.apply(&mut gaussian.sh);
+            gaussian
+        }),
+        gaussians.source(),
+    );
+}
+
+/// Evaluate the SH basis functions of degree 1 to 3 in the direction, in the same order, sign, and
+/// normalization as `view_color` in the shaders.
+fn sh_basis(dir: DVec3) -> [f64; 15] {
+    const SH_C1: f64 = 0.4886025;
+    const SH_C2: [f64; 5] = [1.0925484, -1.0925484, 0.3153916, -1.0925484, 0.5462742];
+    const SH_C3: [f64; 7] = [
+        -0.5900436, 2.8906114, -0.4570458, 0.3731763, -0.4570458, 1.4453057, -0.5900436,
+    ];
+
+    let DVec3 { x, y, z } = dir;
+    let (xx, yy, zz) = (x * x, y * y, z * z);
+    let (xy, yz, xz) = (x * y, y * z, x * z);
+
+    [
+        -SH_C1 * y,
+        SH_C1 * z,
+        -SH_C1 * x,
+        SH_C2[0] * xy,
+        SH_C2[1] * yz,
+        SH_C2[2] * (2.0 * zz - xx - yy),
+        SH_C2[3] * xz,
+        SH_C2[4] * (xx - yy),
+        SH_C3[0] * y * (3.0 * xx - yy),
+        SH_C3[1] * xy * z,
+        SH_C3[2] * y * (4.0 * zz - xx - yy),
+        SH_C3[3] * z * (2.0 * zz - 3.0 * xx - 3.0 * yy),
+        SH_C3[4] * x * (4.0 * zz - xx - yy),
+        SH_C3[5] * z * (xx - yy),
+        SH_C3[6] * x * (xx - 3.0 * yy),
+    ]
+}
+
+/// Solve `lhs * x = rhs` by Gauss-Jordan elimination with partial pivoting.
+///
+/// `lhs` should be square and invertible.
+fn solve(mut lhs: Vec<Vec<f64>>, mut rhs: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let n = lhs.len();
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|a, b| lhs[*a][col].abs().total_cmp(&lhs[*b][col].abs()))
+            .unwrap_or(col);
+        lhs.swap(col, pivot);
+        rhs.swap(col, pivot);
+
+        let pivot_value = lhs[col][col];
+        lhs[col].iter_mut().for_each(|v| *v /= pivot_value);
+        rhs[col].iter_mut().for_each(|v| *v /= pivot_value);
+
+        let pivot_lhs = lhs[col].clone();
+        let pivot_rhs = rhs[col].clone();
+        for row in (0..n).filter(|row| *row != col) {
+            let factor = lhs[row][col];
+            lhs[row]
+                .iter_mut()
+                .zip(&pivot_lhs)
+                .for_each(|(v, pivot)| *v -= factor * pivot);
+            rhs[row]
+                .iter_mut()
+                .zip(&pivot_rhs)
+                .for_each(|(v, pivot)| *v -= factor * pivot);
+        }
+    }
+
+    rhs
+}
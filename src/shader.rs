@@ -19,6 +19,7 @@ pub const MODULE: CodegenModule = CodegenModule {
     source: "",
     submodules: &[
         &camera::MODULE,
+        &colormap::MODULE,
         &preprocess::MODULE,
         &render::MODULE,
         &utils::MODULE,
@@ -38,6 +39,17 @@ pub mod camera {
     };
 }
 
+pub mod colormap {
+    use super::CodegenModule;
+
+    #[doc = concat!("```wgsl\n", include_str!("shader/colormap.wesl"), "\n```")]
+    pub const MODULE: CodegenModule = CodegenModule {
+        name: "colormap",
+        source: include_str!("shader/colormap.wesl"),
+        submodules: &[],
+    };
+}
+
 pub mod preprocess {
     use super::CodegenModule;
 
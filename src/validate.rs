@@ -0,0 +1,149 @@
+use glam::*;
+
+use crate::core::{Gaussian, Gaussians, IterGaussian};
+
+/// The tolerance of the rotation length to be considered normalized.
+const ROTATION_NORMALIZED_TOLERANCE: f32 = 1e-3;
+
+/// An issue of a single Gaussian found by [`validate_gaussians`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GaussianIssue {
+    /// The position has NaN or infinite components.
+    NonFinitePosition,
+    /// The rotation has NaN or infinite components, or is zero, so it cannot be normalized.
+    InvalidRotation,
+    /// The rotation is not a unit quaternion.
+    NonNormalizedRotation,
+    /// The scale has NaN or infinite components.
+    NonFiniteScale,
+    /// The scale has zero or negative components.
+    DegenerateScale,
+    /// The SH coefficients have NaN or infinite components.
+    NonFiniteSh,
+    /// The 3D covariance computed from the rotation and scale overflows.
+    NonFiniteCovariance,
+}
+
+impl GaussianIssue {
+    /// Check whether the issue can be fixed by [`sanitize_gaussians`], otherwise the Gaussian is
+    /// dropped.
+    pub const fn is_fixable(&self) -> bool {
+        matches!(self, Self::NonNormalizedRotation | Self::DegenerateScale)
+    }
+}
+
+/// The report of [`validate_gaussians`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GaussianValidationReport {
+    /// The issues with the index of the Gaussian, in the order of the indices.
+    ///
+    /// A Gaussian may have multiple issues.
+    pub issues: Vec<(usize, GaussianIssue)>,
+}
+
+impl GaussianValidationReport {
+    /// Check whether there is no issue.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Get the indices of the Gaussians with any issue, without duplicates.
+    pub fn invalid_indices(&self) -> Vec<usize> {
+        let mut indices = self
+            .issues
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>();
+        indices.dedup();
+        indices
+    }
+
+    /// Get the number of Gaussians with the `issue`.
+    pub fn count(&self, issue: GaussianIssue) -> usize {
+        self.issues.iter().filter(|(_, i)| *i == issue).count()
+    }
+}
+
+/// Find the Gaussians with NaN or infinite values and degenerate shapes.
+///
+/// These usually come from broken model files, and render as artifacts or make the covariance in
+/// the shaders NaN, e.g. a model that renders as nothing.
+pub fn validate_gaussians(gaussians: &impl IterGaussian) -> GaussianValidationReport {
+    GaussianValidationReport {
+        issues: gaussians
+            .iter_gaussian()
+            .enumerate()
+            .flat_map(|(index, gaussian)| {
+                gaussian_issues(&gaussian)
+                    .into_iter()
+                    .map(move |issue| (index, issue))
+            })
+            .collect(),
+    }
+}
+
+/// Fix or drop the Gaussians with issues found by [`validate_gaussians`].
+///
+/// Non-normalized rotations are normalized and zero or negative scales are replaced by their
+/// absolute values clamped to at least `min_scale`. The Gaussians with other issues are dropped,
+/// and the source representation is kept.
+///
+/// Returns the number of dropped Gaussians.
+pub fn sanitize_gaussians(gaussians: &mut Gaussians, min_scale: f32) -> usize {
+    let count = gaussians.iter_gaussian().len();
+
+    *gaussians = Gaussians::from_gaussians_iter(
+        gaussians.iter_gaussian().filter_map(|mut gaussian| {
+            let issues = gaussian_issues(&gaussian);
+            if issues.iter().any(|issue| !issue.is_fixable()) {
+                return None;
+            }
+
+            gaussian.rot = gaussian.rot.normalize();
+            gaussian.scale = gaussian.scale.abs().max(Vec3::splat(min_scale));
+
+            // Clamping the scale up may still overflow the covariance
+            gaussian_issues(&gaussian).is_empty().then_some(gaussian)
+        }),
+        gaussians.source(),
+    );
+
+    count - gaussians.iter_gaussian().len()
+}
+
+/// Find the issues of a single Gaussian.
+fn gaussian_issues(gaussian: &Gaussian) -> Vec<GaussianIssue> {
+    let mut issues = Vec::new();
+
+    if !gaussian.pos.is_finite() {
+        issues.push(GaussianIssue::NonFinitePosition);
+    }
+
+    let rot_len = gaussian.rot.length();
+    let rot_valid = gaussian.rot.is_finite() && rot_len > 0.0;
+    if !rot_valid {
+        issues.push(GaussianIssue::InvalidRotation);
+    } else if (rot_len - 1.0).abs() > ROTATION_NORMALIZED_TOLERANCE {
+        issues.push(GaussianIssue::NonNormalizedRotation);
+    }
+
+    let scale_finite = gaussian.scale.is_finite();
+    if !scale_finite {
+        issues.push(GaussianIssue::NonFiniteScale);
+    } else if gaussian.scale.cmple(Vec3::ZERO).any() {
+        issues.push(GaussianIssue::DegenerateScale);
+    }
+
+    if !gaussian.sh.iter().all(|sh| sh.is_finite()) {
+        issues.push(GaussianIssue::NonFiniteSh);
+    }
+
+    if rot_valid && scale_finite {
+        let m = Mat3::from_quat(gaussian.rot.normalize()) * Mat3::from_diagonal(gaussian.scale);
+        if !(m * m.transpose()).is_finite() {
+            issues.push(GaussianIssue::NonFiniteCovariance);
+        }
+    }
+
+    issues
+}
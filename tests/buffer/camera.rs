@@ -1,6 +1,9 @@
 use glam::*;
 use wgpu::util::DeviceExt;
-use wgpu_3dgs_viewer::{Camera, CameraBuffer, CameraPod, CameraTrait, core::BufferWrapper};
+use wgpu_3dgs_viewer::{
+    Camera, CameraBuffer, CameraIntrinsics, CameraPod, CameraProjection, CameraTrait,
+    CoordinateSystem, SmoothCamera, core::BufferWrapper,
+};
 
 use crate::common::TestContext;
 
@@ -123,3 +126,264 @@ fn test_camera_pod_new_with_modified_camera_should_return_correct_pod() {
     assert_eq!(pod.proj, expected_proj);
     assert_eq!(pod.size, size.as_vec2());
 }
+
+#[test]
+fn test_camera_pod_new_when_size_is_zero_should_return_finite_pod() {
+    let camera = Camera::new(0.1..100.0, std::f32::consts::FRAC_PI_4);
+    let pod = CameraPod::new(&camera, UVec2::ZERO);
+
+    assert!(pod.view.is_finite());
+    assert!(pod.proj.is_finite());
+    assert_eq!(pod.size, Vec2::ONE);
+}
+
+#[test]
+fn test_camera_when_coordinate_system_is_set_should_look_forward_with_up_direction() {
+    for coordinate_system in [
+        CoordinateSystem::Y_UP_Z_FORWARD,
+        CoordinateSystem::GLTF,
+        CoordinateSystem::INRIA,
+    ] {
+        let mut camera = Camera::new_with_coordinate_system(
+            0.1..100.0,
+            std::f32::consts::FRAC_PI_4,
+            coordinate_system,
+        );
+        camera.pos = Vec3::new(1.0, 2.0, 3.0);
+
+        assert!(
+            camera
+                .get_forward()
+                .abs_diff_eq(coordinate_system.forward, 1e-6)
+        );
+        assert!(
+            camera
+                .get_right()
+                .abs_diff_eq(coordinate_system.right(), 1e-6)
+        );
+
+        let view = camera.view();
+        let forward = view.transform_point3(camera.pos + coordinate_system.forward);
+        let up = view.transform_point3(camera.pos + coordinate_system.up);
+
+        assert!(forward.abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        assert!(up.abs_diff_eq(Vec3::Y, 1e-5));
+
+        camera.move_up(1.0);
+
+        assert!(
+            camera
+                .pos
+                .abs_diff_eq(Vec3::new(1.0, 2.0, 3.0) + coordinate_system.up, 1e-6)
+        );
+    }
+}
+
+#[test]
+fn test_camera_projection_when_reverse_z_should_map_near_to_one_and_far_to_zero() {
+    let camera = Camera {
+        reverse_z: true,
+        ..Camera::new(0.1..100.0, std::f32::consts::FRAC_PI_4)
+    };
+    let proj = camera.projection(1.0);
+
+    let near = proj.project_point3(Vec3::new(0.0, 0.0, -0.1));
+    let far = proj.project_point3(Vec3::new(0.0, 0.0, -100.0));
+
+    assert!((near.z - 1.0).abs() < 1e-5);
+    assert!(far.z.abs() < 1e-5);
+}
+
+#[test]
+fn test_camera_projection_when_orthographic_should_keep_size_regardless_of_depth() {
+    let camera = Camera::new_orthographic(0.1..100.0, 4.0);
+    let proj = camera.projection(2.0);
+
+    let near = proj.project_point3(Vec3::new(4.0, 2.0, -0.1));
+    let far = proj.project_point3(Vec3::new(4.0, 2.0, -100.0));
+
+    assert!(near.truncate().abs_diff_eq(Vec2::ONE, 1e-5));
+    assert!(far.truncate().abs_diff_eq(Vec2::ONE, 1e-5));
+    assert!(near.z.abs() < 1e-5);
+    assert!((far.z - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_camera_projection_when_orthographic_and_reverse_z_should_map_near_to_one_and_far_to_zero() {
+    let camera = Camera {
+        reverse_z: true,
+        ..Camera::new_orthographic(0.1..100.0, 4.0)
+    };
+    let proj = camera.projection(1.0);
+
+    let near = proj.project_point3(Vec3::new(0.0, 0.0, -0.1));
+    let far = proj.project_point3(Vec3::new(0.0, 0.0, -100.0));
+
+    assert!((near.z - 1.0).abs() < 1e-5);
+    assert!(far.z.abs() < 1e-5);
+}
+
+#[test]
+fn test_camera_from_intrinsics_should_project_to_principal_point() {
+    let intrinsics = CameraIntrinsics::new(500.0, 400.0, 300.0, 200.0, 640, 480);
+    let camera = Camera::from_intrinsics(0.1..100.0, intrinsics);
+    let proj = camera.projection(640.0 / 480.0);
+
+    let CameraProjection::Perspective { fovy } = camera.projection else {
+        panic!(
+            "expected perspective projection, got {:?}",
+            camera.projection
+        );
+    };
+    assert!((fovy - 2.0 * (240.0f32 / 400.0).atan()).abs() < 1e-6);
+
+    let to_pixel = |ndc: Vec3| Vec2::new((ndc.x + 1.0) * 320.0, (1.0 - ndc.y) * 240.0);
+
+    // The optical axis hits the principal point
+    let center = proj.project_point3(Vec3::new(0.0, 0.0, -10.0));
+    assert!(to_pixel(center).abs_diff_eq(Vec2::new(300.0, 200.0), 1e-3));
+
+    // A point at (x, y, z) in camera space with y down and z forward hits
+    // (fx * x / z + cx, fy * y / z + cy)
+    let point = proj.project_point3(Vec3::new(1.0, -2.0, -10.0));
+    assert!(to_pixel(point).abs_diff_eq(Vec2::new(350.0, 280.0), 1e-3));
+
+    let near = proj.project_point3(Vec3::new(0.0, 0.0, -0.1));
+    let far = proj.project_point3(Vec3::new(0.0, 0.0, -100.0));
+    assert!(near.z.abs() < 1e-5);
+    assert!((far.z - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_camera_from_intrinsics_when_principal_point_is_centered_should_match_vertical_fov() {
+    let intrinsics = CameraIntrinsics::new(400.0, 400.0, 320.0, 240.0, 640, 480);
+    let camera = Camera::from_intrinsics(0.1..100.0, intrinsics);
+    let without_intrinsics = Camera {
+        intrinsics: None,
+        ..camera.clone()
+    };
+
+    assert!(
+        camera
+            .projection(640.0 / 480.0)
+            .abs_diff_eq(without_intrinsics.projection(640.0 / 480.0), 1e-5)
+    );
+}
+
+#[test]
+fn test_smooth_camera_update_when_framerate_differs_should_move_the_same() {
+    let camera = Camera::new(0.1..100.0, std::f32::consts::FRAC_PI_4);
+
+    let mut coarse = SmoothCamera::new_with(camera.clone(), 5.0, 2.0);
+    let mut fine = SmoothCamera::new_with(camera, 5.0, 2.0);
+
+    for smooth in [&mut coarse, &mut fine] {
+        smooth.set_target_velocity(1.0, 0.5, -0.25);
+        smooth.set_target_angular_velocity(0.1, 0.2);
+    }
+
+    coarse.update(0.5);
+    (0..50).for_each(|_| fine.update(0.01));
+
+    assert!(coarse.camera.pos.abs_diff_eq(fine.camera.pos, 1e-4));
+    assert!(coarse.velocity.abs_diff_eq(fine.velocity, 1e-4));
+    assert!((coarse.camera.pitch - fine.camera.pitch).abs() < 1e-4);
+    assert!((coarse.camera.yaw - fine.camera.yaw).abs() < 1e-4);
+    assert_eq!(coarse.view(), coarse.camera.view());
+}
+
+#[test]
+fn test_smooth_camera_update_when_target_exceeds_max_speed_should_clamp_velocity() {
+    let mut smooth = SmoothCamera::new_with(
+        Camera::new(0.1..100.0, std::f32::consts::FRAC_PI_4),
+        10.0,
+        2.0,
+    );
+    smooth.set_target_velocity(100.0, 0.0, 0.0);
+
+    (0..100).for_each(|_| smooth.update(0.1));
+
+    assert!(smooth.velocity.length() <= 2.0 + 1e-4);
+    assert!(smooth.velocity.abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1e-4));
+
+    smooth.stop();
+    let pos = smooth.camera.pos;
+    smooth.update(0.1);
+
+    assert_eq!(smooth.camera.pos, pos);
+}
+
+#[test]
+fn test_orbit_controller_apply_to_should_look_at_target_from_distance() {
+    let target = Vec3::new(1.0, 2.0, 3.0);
+    let mut controller = OrbitController::new(target, 5.0);
+    controller.orbit(Vec2::new(1.0, 0.5));
+
+    let mut camera = Camera::new(0.1..100.0, std::f32::consts::FRAC_PI_4);
+    controller.apply_to(&mut camera);
+
+    assert!((camera.pos.distance(target) - 5.0).abs() < 1e-5);
+    assert!(
+        (target - camera.pos)
+            .normalize()
+            .abs_diff_eq(camera.get_forward(), 1e-5)
+    );
+}
+
+#[test]
+fn test_orbit_controller_from_camera_should_keep_camera_position() {
+    let mut camera = Camera::new_with_coordinate_system(
+        0.1..100.0,
+        std::f32::consts::FRAC_PI_4,
+        CoordinateSystem::GLTF,
+    );
+    camera.pos = Vec3::new(-2.0, 3.0, 4.0);
+    let target = Vec3::new(1.0, 0.0, -1.0);
+
+    let controller = OrbitController::from_camera(&camera, target);
+    controller.apply_to(&mut camera);
+
+    assert!(camera.pos.abs_diff_eq(Vec3::new(-2.0, 3.0, 4.0), 1e-4));
+    assert!(
+        (target - camera.pos)
+            .normalize()
+            .abs_diff_eq(camera.get_forward(), 1e-5)
+    );
+}
+
+#[test]
+fn test_orbit_controller_orbit_when_pitch_exceeds_limit_should_clamp_pitch() {
+    let mut controller = OrbitController::new(Vec3::ZERO, 1.0);
+
+    controller.orbit(Vec2::new(0.0, 10.0));
+    assert_eq!(controller.pitch, Camera::PITCH_LIMIT.end);
+
+    controller.orbit(Vec2::new(0.0, -20.0));
+    assert_eq!(controller.pitch, Camera::PITCH_LIMIT.start);
+}
+
+#[test]
+fn test_orbit_controller_pan_should_move_target_and_camera_together() {
+    let mut controller = OrbitController::new(Vec3::ZERO, 2.0);
+    let mut camera = Camera::new(0.1..100.0, std::f32::consts::FRAC_PI_4);
+    controller.apply_to(&mut camera);
+    let pos = camera.pos;
+
+    controller.pan(Vec2::new(1.0, 2.0));
+    controller.apply_to(&mut camera);
+
+    let expected = camera.get_right() * 1.0 + camera.coordinate_system.up * 2.0;
+    assert!(controller.target.abs_diff_eq(expected, 1e-5));
+    assert!((camera.pos - pos).abs_diff_eq(expected, 1e-5));
+}
+
+#[test]
+fn test_orbit_controller_zoom_when_amount_is_large_should_keep_distance_positive() {
+    let mut controller = OrbitController::new(Vec3::ZERO, 4.0);
+
+    controller.zoom(std::f32::consts::LN_2);
+    assert!((controller.distance - 2.0).abs() < 1e-5);
+
+    controller.zoom(1e4);
+    assert_eq!(controller.distance, OrbitController::MIN_DISTANCE);
+}
@@ -0,0 +1,42 @@
+use glam::*;
+use wgpu_3dgs_viewer::{Conic2dBuffer, Conic2dPod, core::BufferWrapper};
+
+use crate::common::TestContext;
+
+#[test]
+fn test_conic2d_buffer_new_should_return_correct_buffer() {
+    let ctx = TestContext::new();
+    let buffer = Conic2dBuffer::new(&ctx.device, 10);
+
+    assert_eq!(buffer.len(), 10);
+    assert_eq!(
+        buffer.buffer().size(),
+        10 * std::mem::size_of::<Conic2dPod>() as wgpu::BufferAddress
+    );
+    assert_eq!(buffer.buffer().usage(), Conic2dBuffer::DEFAULT_USAGES);
+}
+
+#[test]
+fn test_conic2d_pod_covariance_should_invert_conic() {
+    let cov = Mat2::from_cols(Vec2::new(4.0, 1.0), Vec2::new(1.0, 2.0));
+    let inv = cov.inverse();
+    let pod = Conic2dPod {
+        conic: Vec3::new(inv.x_axis.x, inv.x_axis.y, inv.y_axis.y),
+        valid: 1,
+        ..Default::default()
+    };
+
+    assert!(
+        pod.covariance()
+            .expect("covariance")
+            .abs_diff_eq(Vec3::new(4.0, 1.0, 2.0), 1e-5)
+    );
+}
+
+#[test]
+fn test_conic2d_pod_covariance_when_not_valid_should_return_none() {
+    let pod = Conic2dPod::default();
+
+    assert!(!pod.is_valid());
+    assert_eq!(pod.covariance(), None);
+}
@@ -0,0 +1,107 @@
+use glam::*;
+use wgpu_3dgs_viewer::{
+    DefaultGaussianPod, DownloadGaussianError,
+    core::{BufferWrapper, Gaussian, GaussianPod, GaussiansBuffer},
+    create_gaussians_buffer_chunked, download_gaussian,
+};
+
+use crate::common::TestContext;
+
+#[test]
+fn test_create_gaussians_buffer_chunked_should_be_equal_to_gaussians_buffer_new() {
+    let ctx = TestContext::new();
+    let gaussians = (0..10)
+        .map(|i| Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::splat(i as f32),
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+    let usage =
+        GaussiansBuffer::<DefaultGaussianPod>::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC;
+
+    let mut progresses = Vec::new();
+    let chunked_buffer = create_gaussians_buffer_chunked::<DefaultGaussianPod>(
+        &ctx.device,
+        &ctx.queue,
+        &gaussians,
+        usage,
+        3,
+        |uploaded, total| progresses.push((uploaded, total)),
+    );
+    let buffer =
+        GaussiansBuffer::<DefaultGaussianPod>::new_with_usage(&ctx.device, &gaussians, usage);
+
+    assert_eq!(progresses, vec![(3, 10), (6, 10), (9, 10), (10, 10)]);
+    assert_eq!(chunked_buffer.len(), buffer.len());
+
+    let chunked_downloaded =
+        pollster::block_on(chunked_buffer.download_gaussians(&ctx.device, &ctx.queue))
+            .expect("download");
+    let downloaded =
+        pollster::block_on(buffer.download_gaussians(&ctx.device, &ctx.queue)).expect("download");
+
+    assert_eq!(chunked_downloaded, downloaded);
+}
+
+#[test]
+fn test_download_gaussian_should_return_gaussian_at_index() {
+    let ctx = TestContext::new();
+    let gaussians = (0..10)
+        .map(|i| Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::splat(i as f32),
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+    let buffer = GaussiansBuffer::<DefaultGaussianPod>::new_with_usage(
+        &ctx.device,
+        &gaussians,
+        GaussiansBuffer::<DefaultGaussianPod>::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+    );
+
+    let downloaded = pollster::block_on(download_gaussian(&ctx.device, &ctx.queue, &buffer, 7))
+        .expect("download");
+
+    assert_eq!(downloaded, DefaultGaussianPod::from_gaussian(&gaussians[7]));
+}
+
+#[test]
+fn test_download_gaussian_when_index_is_out_of_bounds_or_usage_is_missing_should_return_error() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+    let buffer = GaussiansBuffer::<DefaultGaussianPod>::new_with_usage(
+        &ctx.device,
+        &gaussians,
+        GaussiansBuffer::<DefaultGaussianPod>::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+    );
+    let buffer_without_copy_src = GaussiansBuffer::<DefaultGaussianPod>::new_with_usage(
+        &ctx.device,
+        &gaussians,
+        GaussiansBuffer::<DefaultGaussianPod>::DEFAULT_USAGES,
+    );
+
+    assert!(matches!(
+        pollster::block_on(download_gaussian(&ctx.device, &ctx.queue, &buffer, 1)),
+        Err(DownloadGaussianError::IndexOutOfBounds { index: 1, len: 1 })
+    ));
+    assert!(matches!(
+        pollster::block_on(download_gaussian(
+            &ctx.device,
+            &ctx.queue,
+            &buffer_without_copy_src,
+            0
+        )),
+        Err(DownloadGaussianError::MissingCopySrcUsage(_))
+    ));
+}
@@ -1,4 +1,9 @@
 mod camera;
+mod conic2d;
+mod gaussians;
 mod indirect_args;
+mod preprocessor_settings;
+mod renderer_settings;
 #[cfg(feature = "selection")]
 mod selection;
+mod visible_bounds;
@@ -0,0 +1,151 @@
+use wgpu_3dgs_viewer::{
+    PreprocessorSettings, PreprocessorSettingsBuffer, PreprocessorSettingsPod, core::BufferWrapper,
+};
+
+use crate::common::TestContext;
+
+#[test]
+fn test_preprocessor_settings_buffer_new_should_return_correct_buffer() {
+    let ctx = TestContext::new();
+    let buffer = PreprocessorSettingsBuffer::new(&ctx.device);
+
+    assert_eq!(
+        buffer.buffer().size(),
+        std::mem::size_of::<PreprocessorSettingsPod>() as wgpu::BufferAddress
+    );
+    assert_eq!(
+        buffer.buffer().usage(),
+        PreprocessorSettingsBuffer::DEFAULT_USAGES
+    );
+}
+
+#[test]
+fn test_preprocessor_settings_buffer_update_should_update_buffer_correctly() {
+    let ctx = TestContext::new();
+    let buffer =
+        PreprocessorSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Preprocessor Settings Buffer"),
+            size: std::mem::size_of::<PreprocessorSettingsPod>() as wgpu::BufferAddress,
+            usage: PreprocessorSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let downloaded =
+        pollster::block_on(buffer.download::<PreprocessorSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(downloaded, PreprocessorSettingsPod::default());
+
+    let settings = PreprocessorSettings {
+        min_opacity: 0.5,
+        min_pixel_size: 2.0,
+    };
+
+    buffer.update(&ctx.queue, &settings);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<PreprocessorSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(downloaded, PreprocessorSettingsPod::new(&settings));
+}
+
+#[test]
+fn test_preprocessor_settings_buffer_update_min_opacity_should_only_update_min_opacity() {
+    let ctx = TestContext::new();
+    let buffer =
+        PreprocessorSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Preprocessor Settings Buffer"),
+            size: std::mem::size_of::<PreprocessorSettingsPod>() as wgpu::BufferAddress,
+            usage: PreprocessorSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let pod = PreprocessorSettingsPod::new(&PreprocessorSettings {
+        min_opacity: 0.0,
+        min_pixel_size: 2.0,
+    });
+
+    buffer.update_with_pod(&ctx.queue, &pod);
+    buffer.update_min_opacity(&ctx.queue, 0.5);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<PreprocessorSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(
+        downloaded,
+        PreprocessorSettingsPod {
+            min_opacity: 0.5,
+            ..pod
+        }
+    );
+}
+
+#[test]
+fn test_preprocessor_settings_buffer_update_min_pixel_size_should_only_update_min_pixel_size() {
+    let ctx = TestContext::new();
+    let buffer =
+        PreprocessorSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Preprocessor Settings Buffer"),
+            size: std::mem::size_of::<PreprocessorSettingsPod>() as wgpu::BufferAddress,
+            usage: PreprocessorSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let pod = PreprocessorSettingsPod::new(&PreprocessorSettings {
+        min_opacity: 0.5,
+        min_pixel_size: 0.0,
+    });
+
+    buffer.update_with_pod(&ctx.queue, &pod);
+    buffer.update_min_pixel_size(&ctx.queue, 2.0);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<PreprocessorSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(
+        downloaded,
+        PreprocessorSettingsPod {
+            min_pixel_size: 2.0,
+            ..pod
+        }
+    );
+}
+
+#[test]
+fn test_preprocessor_settings_buffer_update_skip_depth_should_only_update_skip_depth() {
+    let ctx = TestContext::new();
+    let buffer =
+        PreprocessorSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Preprocessor Settings Buffer"),
+            size: std::mem::size_of::<PreprocessorSettingsPod>() as wgpu::BufferAddress,
+            usage: PreprocessorSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let pod = PreprocessorSettingsPod::new(&PreprocessorSettings {
+        min_opacity: 0.5,
+        min_pixel_size: 2.0,
+    });
+
+    buffer.update_with_pod(&ctx.queue, &pod);
+    buffer.update_skip_depth(&ctx.queue, true);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<PreprocessorSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(
+        downloaded,
+        PreprocessorSettingsPod {
+            skip_depth: 1,
+            ..pod
+        }
+    );
+}
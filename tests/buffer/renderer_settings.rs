@@ -0,0 +1,170 @@
+use wgpu_3dgs_viewer::{
+    Colormap, Cov2dRegularization, DepthDisplay, RendererSettings, RendererSettingsBuffer,
+    RendererSettingsPod, ShClamp, core::BufferWrapper,
+};
+
+use crate::common::TestContext;
+
+#[test]
+fn test_renderer_settings_buffer_new_should_return_correct_buffer() {
+    let ctx = TestContext::new();
+    let buffer = RendererSettingsBuffer::new(&ctx.device);
+
+    assert_eq!(
+        buffer.buffer().size(),
+        std::mem::size_of::<RendererSettingsPod>() as wgpu::BufferAddress
+    );
+    assert_eq!(
+        buffer.buffer().usage(),
+        RendererSettingsBuffer::DEFAULT_USAGES
+    );
+}
+
+#[test]
+fn test_renderer_settings_buffer_update_should_update_buffer_correctly() {
+    let ctx = TestContext::new();
+    let buffer =
+        RendererSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Renderer Settings Buffer"),
+            size: std::mem::size_of::<RendererSettingsPod>() as wgpu::BufferAddress,
+            usage: RendererSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let downloaded =
+        pollster::block_on(buffer.download::<RendererSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(downloaded, RendererSettingsPod::default());
+
+    let settings = RendererSettings {
+        sh_dither: true,
+        sh_clamp: ShClamp::SoftCeiling,
+        depth_display: Some(DepthDisplay {
+            colormap: Colormap::Viridis,
+            range: 1.0..10.0,
+        }),
+        cov2d_regularization: Cov2dRegularization {
+            epsilon: 0.5,
+            highlight: true,
+        },
+    };
+
+    buffer.update(&ctx.queue, &settings);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<RendererSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(downloaded, RendererSettingsPod::new(&settings));
+}
+
+#[test]
+fn test_renderer_settings_pod_new_when_settings_are_default_should_equal_zeroed() {
+    assert_eq!(
+        RendererSettingsPod::new(&RendererSettings::default()),
+        RendererSettingsPod::default()
+    );
+}
+
+#[test]
+fn test_renderer_settings_buffer_update_distance_fade_should_only_update_distance_fade() {
+    let ctx = TestContext::new();
+    let buffer =
+        RendererSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Renderer Settings Buffer"),
+            size: std::mem::size_of::<RendererSettingsPod>() as wgpu::BufferAddress,
+            usage: RendererSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let pod = RendererSettingsPod::new(&RendererSettings {
+        sh_dither: true,
+        sh_clamp: ShClamp::SoftCeiling,
+        ..Default::default()
+    });
+
+    buffer.update_with_pod(&ctx.queue, &pod);
+    buffer.update_distance_fade(&ctx.queue, 5.0, 10.0);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<RendererSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(
+        downloaded,
+        RendererSettingsPod {
+            distance_fade_start: 5.0,
+            distance_fade_end: 10.0,
+            ..pod
+        }
+    );
+}
+
+#[test]
+fn test_renderer_settings_buffer_update_sh_dither_should_only_update_sh_dither() {
+    let ctx = TestContext::new();
+    let buffer =
+        RendererSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Renderer Settings Buffer"),
+            size: std::mem::size_of::<RendererSettingsPod>() as wgpu::BufferAddress,
+            usage: RendererSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let pod = RendererSettingsPod::new(&RendererSettings {
+        sh_clamp: ShClamp::SoftCeiling,
+        ..Default::default()
+    });
+
+    buffer.update_with_pod(&ctx.queue, &pod);
+    buffer.update_sh_dither(&ctx.queue, true);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<RendererSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(
+        downloaded,
+        RendererSettingsPod {
+            sh_dither: 1,
+            ..pod
+        }
+    );
+}
+
+#[test]
+fn test_renderer_settings_buffer_update_sh_clamp_should_only_update_sh_clamp() {
+    let ctx = TestContext::new();
+    let buffer =
+        RendererSettingsBuffer::try_from(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Renderer Settings Buffer"),
+            size: std::mem::size_of::<RendererSettingsPod>() as wgpu::BufferAddress,
+            usage: RendererSettingsBuffer::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }))
+        .expect("try_from");
+
+    let pod = RendererSettingsPod::new(&RendererSettings {
+        sh_dither: true,
+        ..Default::default()
+    });
+
+    buffer.update_with_pod(&ctx.queue, &pod);
+    buffer.update_sh_clamp(&ctx.queue, ShClamp::SoftCeiling);
+
+    let downloaded =
+        pollster::block_on(buffer.download::<RendererSettingsPod>(&ctx.device, &ctx.queue))
+            .expect("download")[0];
+
+    assert_eq!(
+        downloaded,
+        RendererSettingsPod {
+            sh_clamp: ShClamp::SoftCeiling as u32,
+            ..pod
+        }
+    );
+}
@@ -1,9 +1,11 @@
 use glam::*;
 use wgpu::util::DeviceExt;
+use wgpu_3dgs_viewer::SelectionIsSelectedError;
 use wgpu_3dgs_viewer::core::BufferWrapper;
+use wgpu_3dgs_viewer::editor::SelectionBuffer;
 use wgpu_3dgs_viewer::selection::{
-    PreprocessorInvertSelectionBuffer, ViewportTexture, ViewportTextureF32Buffer,
-    ViewportTexturePosBuffer,
+    PreprocessorInvertSelectionBuffer, SelectionHistory, ViewportTexture, ViewportTextureF32Buffer,
+    ViewportTexturePosBuffer, is_selected,
 };
 
 use crate::common::TestContext;
@@ -35,6 +37,39 @@ fn test_viewport_texture_new_should_return_correct_texture() {
     );
 }
 
+#[test]
+fn test_viewport_texture_is_empty_when_cleared_or_filled_should_return_correct_result() {
+    let ctx = TestContext::new();
+    let texture = ViewportTexture::new(&ctx.device, UVec2::new(100, 50));
+
+    let fill = |color: wgpu::Color| {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Test Encoder"),
+            });
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Test Fill Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: texture.view(),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(color),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            ..Default::default()
+        });
+        ctx.queue.submit(Some(encoder.finish()));
+
+        pollster::block_on(texture.is_empty(&ctx.device, &ctx.queue)).expect("is_empty")
+    };
+
+    assert!(fill(wgpu::Color::TRANSPARENT));
+    assert!(!fill(wgpu::Color::WHITE));
+}
+
 #[test]
 fn test_viewport_texture_pos_buffer_new_should_return_correct_buffer() {
     let ctx = TestContext::new();
@@ -235,3 +270,99 @@ fn test_preprocessor_invert_selection_buffer_try_from_and_into_wgpu_buffer_shoul
     assert_eq!(wgpu_downloaded, converted_downloaded);
     assert_eq!(wgpu_downloaded, wgpu_converted_downloaded);
 }
+
+#[test]
+fn test_is_selected_should_return_selection_bit_at_index() {
+    let ctx = TestContext::new();
+    let buffer = SelectionBuffer::new(&ctx.device, 64);
+    ctx.queue.write_buffer(
+        buffer.buffer(),
+        0,
+        bytemuck::cast_slice(&[0b101u32, 1 << 31]),
+    );
+
+    for (index, expected) in [(0, true), (1, false), (2, true), (32, false), (63, true)] {
+        let selected = pollster::block_on(is_selected(&ctx.device, &ctx.queue, &buffer, index))
+            .expect("is selected");
+
+        assert_eq!(selected, expected);
+    }
+}
+
+#[test]
+fn test_is_selected_when_index_is_out_of_bounds_should_return_error() {
+    let ctx = TestContext::new();
+    let buffer = SelectionBuffer::new(&ctx.device, 64);
+
+    let result = pollster::block_on(is_selected(&ctx.device, &ctx.queue, &buffer, 64));
+
+    assert!(matches!(
+        result,
+        Err(SelectionIsSelectedError::IndexOutOfBounds { index: 64, .. })
+    ));
+}
+
+#[test]
+fn test_selection_history_undo_and_redo_should_restore_selection() {
+    let ctx = TestContext::new();
+    let buffer = SelectionBuffer::new(&ctx.device, 64);
+    let mut history = SelectionHistory::default();
+
+    let record = |f: &mut dyn FnMut(&mut wgpu::CommandEncoder)| {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Test Encoder"),
+            });
+        f(&mut encoder);
+        ctx.queue.submit(Some(encoder.finish()));
+    };
+    let download =
+        || pollster::block_on(buffer.download::<u32>(&ctx.device, &ctx.queue)).expect("download");
+
+    assert!(!history.can_undo());
+
+    ctx.queue
+        .write_buffer(buffer.buffer(), 0, bytemuck::cast_slice(&[0b1u32, 0]));
+    record(&mut |encoder| history.push(&ctx.device, encoder, &buffer));
+
+    ctx.queue
+        .write_buffer(buffer.buffer(), 0, bytemuck::cast_slice(&[0b11u32, 1]));
+    assert!(history.can_undo());
+
+    let mut undone = false;
+    record(&mut |encoder| undone = history.undo(&ctx.device, encoder, &buffer));
+    assert!(undone);
+    assert_eq!(download(), vec![0b1, 0]);
+    assert!(!history.can_undo());
+    assert!(history.can_redo());
+
+    let mut redone = false;
+    record(&mut |encoder| redone = history.redo(&ctx.device, encoder, &buffer));
+    assert!(redone);
+    assert_eq!(download(), vec![0b11, 1]);
+    assert!(history.can_undo());
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn test_selection_history_push_when_capacity_is_reached_should_discard_oldest() {
+    let ctx = TestContext::new();
+    let buffer = SelectionBuffer::new(&ctx.device, 32);
+    let mut history = SelectionHistory::new(2);
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Test Encoder"),
+        });
+    for _ in 0..3 {
+        history.push(&ctx.device, &mut encoder, &buffer);
+    }
+
+    assert!(history.undo(&ctx.device, &mut encoder, &buffer));
+    assert!(history.undo(&ctx.device, &mut encoder, &buffer));
+    assert!(!history.undo(&ctx.device, &mut encoder, &buffer));
+
+    ctx.queue.submit(Some(encoder.finish()));
+}
@@ -0,0 +1,49 @@
+use glam::*;
+use wgpu_3dgs_viewer::{VisibleBoundsBuffer, VisibleBoundsPod, core::BufferWrapper};
+
+use crate::common::TestContext;
+
+#[test]
+fn test_visible_bounds_buffer_new_should_return_correct_buffer() {
+    let ctx = TestContext::new();
+    let buffer = VisibleBoundsBuffer::new(&ctx.device);
+
+    assert_eq!(
+        buffer.buffer().size(),
+        std::mem::size_of::<VisibleBoundsPod>() as wgpu::BufferAddress
+    );
+    assert_eq!(buffer.buffer().usage(), VisibleBoundsBuffer::DEFAULT_USAGES);
+}
+
+#[test]
+fn test_visible_bounds_pod_key_should_preserve_order_and_round_trip() {
+    let values = [
+        f32::NEG_INFINITY,
+        -1e30,
+        -2.0,
+        -1.0,
+        -1e-30,
+        0.0,
+        1e-30,
+        1.0,
+        2.0,
+        1e30,
+        f32::INFINITY,
+    ];
+
+    let keys = values.map(VisibleBoundsPod::key);
+
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(keys.map(VisibleBoundsPod::value), values);
+}
+
+#[test]
+fn test_visible_bounds_pod_bounds_should_return_corners_or_none_when_empty() {
+    let min = Vec3::new(-1.0, 0.5, -3.0);
+    let max = Vec3::new(2.0, 0.5, 4.0);
+
+    assert_eq!(VisibleBoundsPod::new(min, max).bounds(), Some((min, max)));
+    assert_eq!(VisibleBoundsPod::default(), VisibleBoundsPod::EMPTY);
+    assert!(VisibleBoundsPod::EMPTY.is_empty());
+    assert_eq!(VisibleBoundsPod::EMPTY.bounds(), None);
+}
@@ -1,7 +1,9 @@
 mod assert_render_target;
 pub mod given;
 mod inline_wesl_pkg;
+mod reference;
 mod test_context;
 
 pub use assert_render_target::*;
+pub use reference::*;
 pub use test_context::*;
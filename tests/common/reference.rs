@@ -0,0 +1,297 @@
+use glam::*;
+use pollster::FutureExt;
+use wgpu_3dgs_core::BufferWrapper;
+use wgpu_3dgs_viewer::{
+    Camera, CameraPod, Cov2dRegularization, Viewer,
+    core::{Gaussian, GaussianPodWithShSingleCov3dSingleConfigs, IterGaussian},
+};
+
+use crate::common::TestContext;
+
+/// The format of the reference images, 4 bytes per pixel in RGBA order.
+pub const REFERENCE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// The maximum standard deviation of the default Gaussian transform.
+const STD_DEV: f32 = 3.0;
+
+/// The SH coefficients constants of degree 1 to 3, same as `view_color` in `utils.wesl`.
+const SH_C1: f32 = 0.4886025;
+const SH_C2: [f32; 5] = [1.0925484, -1.0925484, 0.3153916, -1.0925484, 0.5462742];
+const SH_C3: [f32; 7] = [
+    -0.5900436, 2.8906114, -0.4570458, 0.3731763, -0.4570458, 1.4453057, -0.5900436,
+];
+
+/// Render the Gaussians headlessly with a [`Viewer`] and read back the RGBA8 pixels.
+///
+/// The single precision SH and covariance configuration is used so that the only differences to
+/// [`rasterize_reference`] come from the GPU arithmetic.
+pub fn render_reference(
+    ctx: &TestContext,
+    gaussians: &impl IterGaussian,
+    camera: &Camera,
+    size: UVec2,
+) -> Vec<u8> {
+    let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Reference Render Target"),
+        size: wgpu::Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: REFERENCE_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut viewer = Viewer::<GaussianPodWithShSingleCov3dSingleConfigs>::new(
+        &ctx.device,
+        REFERENCE_FORMAT,
+        gaussians,
+    )
+    .expect("viewer");
+    viewer.update_camera_with_pod(&ctx.queue, &CameraPod::new(camera, size));
+
+    let unpadded_bytes_per_row = size.x * 4;
+    let bytes_per_row = unpadded_bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+        * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Reference Readback Buffer"),
+        size: (bytes_per_row * size.y) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.render(&mut encoder, &view);
+
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(size.y),
+            },
+        },
+        texture.size(),
+    );
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let padded = buffer
+        .download::<u8>(&ctx.device, &ctx.queue)
+        .block_on()
+        .expect("downloaded reference image");
+
+    padded
+        .chunks(bytes_per_row as usize)
+        .flat_map(|row| &row[..unpadded_bytes_per_row as usize])
+        .copied()
+        .collect()
+}
+
+/// Rasterize the Gaussians on the CPU and return the RGBA8 pixels.
+///
+/// This mirrors the default preprocess, sort, and render path of [`Viewer`] with the default
+/// model and Gaussian transforms: Gaussians are projected with the same 2D covariance, blended back
+/// to front with [`wgpu::BlendState::ALPHA_BLENDING`] over a black background.
+pub fn rasterize_reference(gaussians: &impl IterGaussian, camera: &Camera, size: UVec2) -> Vec<u8> {
+    let camera = CameraPod::new(camera, size);
+    let camera_pos = camera.view.inverse().w_axis.truncate();
+    let focal = vec2(camera.proj.x_axis.x, camera.proj.y_axis.y) * camera.size * 0.5;
+    let w = Mat3::from_mat4(camera.view);
+
+    let mut splats = gaussians
+        .iter_gaussian()
+        .filter_map(|gaussian| {
+            let view_pos = camera.view * gaussian.pos.extend(1.0);
+            let proj_pos = camera.proj * view_pos;
+            let ndc_pos = proj_pos.truncate() / proj_pos.w;
+            if !(ndc_pos.cmpge(vec3(-1.0, -1.0, 0.0)).all() && ndc_pos.cmple(Vec3::ONE).all()) {
+                return None;
+            }
+
+            let t = view_pos.truncate();
+            let j = Mat3::from_cols(
+                vec3(focal.x / t.z, 0.0, 0.0),
+                vec3(0.0, focal.y / t.z, 0.0),
+                vec3(
+                    -(focal.x * t.x) / (t.z * t.z),
+                    -(focal.y * t.y) / (t.z * t.z),
+                    0.0,
+                ),
+            );
+            let m = Mat3::from_quat(gaussian.rot) * Mat3::from_diagonal(gaussian.scale);
+            let jw = j * w;
+            let cov2d = jw * (m * m.transpose()) * jw.transpose();
+            let (a, b, c) = (
+                cov2d.x_axis.x + Cov2dRegularization::DEFAULT_EPSILON,
+                cov2d.x_axis.y,
+                cov2d.y_axis.y + Cov2dRegularization::DEFAULT_EPSILON,
+            );
+
+            let mid = 0.5 * (a + c);
+            let radius = vec2(0.5 * (a - c), b).length();
+            let major_lambda = mid + radius;
+            let minor_lambda = mid - radius;
+            if minor_lambda < 0.0 {
+                return None;
+            }
+
+            let diag = vec2(b, major_lambda - a);
+            let diag_dir = if diag == Vec2::ZERO {
+                Vec2::Y
+            } else {
+                diag.normalize()
+            };
+            let major_axis = diag_dir * (STD_DEV * major_lambda.sqrt()).min(1024.0);
+            let minor_axis =
+                vec2(diag_dir.y, -diag_dir.x) * (STD_DEV * minor_lambda.sqrt()).min(1024.0);
+
+            let center = (ndc_pos.truncate() * vec2(1.0, -1.0) + Vec2::ONE) * camera.size * 0.5;
+            let color = view_color(&gaussian, (gaussian.pos - camera_pos).normalize());
+
+            Some((ndc_pos.z, center, major_axis, minor_axis, color))
+        })
+        .collect::<Vec<_>>();
+
+    // Back to front, the same order as the sorted depth keys `1 - ndc.z`
+    splats.sort_by(|(a, ..), (b, ..)| b.total_cmp(a));
+
+    let mut image = vec![Vec4::new(0.0, 0.0, 0.0, 1.0); (size.x * size.y) as usize];
+    for (_, center, major_axis, minor_axis, color) in splats {
+        for (i, dst) in image.iter_mut().enumerate() {
+            let frag_pos = vec2((i as u32 % size.x) as f32, (i as u32 / size.x) as f32) + 0.5;
+
+            // A unit of quad offset spans half of the axes in pixels, with y flipped from NDC
+            let offset = (frag_pos - center) * vec2(1.0, -1.0) * 2.0;
+            let quad_offset = vec2(
+                offset.dot(major_axis) / major_axis.length_squared(),
+                offset.dot(minor_axis) / minor_axis.length_squared(),
+            );
+            let radius_sq = quad_offset.length_squared();
+            if radius_sq > STD_DEV * STD_DEV {
+                continue;
+            }
+
+            let src = color
+                .with_w(color.w * (-radius_sq).exp())
+                .clamp(Vec4::ZERO, Vec4::ONE);
+            let rgb = src.truncate() * src.w + dst.truncate() * (1.0 - src.w);
+            let alpha = src.w + dst.w * (1.0 - src.w);
+            *dst = rgb.extend(alpha);
+        }
+    }
+
+    image
+        .into_iter()
+        .flat_map(|pixel| (pixel * 255.0).round().as_u8vec4().to_array())
+        .collect()
+}
+
+/// Evaluate the Gaussian color in the view direction, same as `view_color` in `utils.wesl`.
+fn view_color(gaussian: &Gaussian, dir: Vec3) -> Vec4 {
+    let Vec3 { x, y, z } = dir;
+    let (xx, yy, zz) = (x * x, y * y, z * z);
+    let (xy, yz, xz) = (x * y, y * z, x * z);
+    let sh = &gaussian.sh;
+
+    let color = gaussian.color.as_vec4() / 255.0;
+    let result = color.truncate()
+        + SH_C1 * (-sh[0] * y + sh[1] * z - sh[2] * x)
+        + SH_C2[0] * xy * sh[3]
+        + SH_C2[1] * yz * sh[4]
+        + SH_C2[2] * (2.0 * zz - xx - yy) * sh[5]
+        + SH_C2[3] * xz * sh[6]
+        + SH_C2[4] * (xx - yy) * sh[7]
+        + SH_C3[0] * y * (3.0 * xx - yy) * sh[8]
+        + SH_C3[1] * xy * z * sh[9]
+        + SH_C3[2] * y * (4.0 * zz - xx - yy) * sh[10]
+        + SH_C3[3] * z * (2.0 * zz - 3.0 * xx - 3.0 * yy) * sh[11]
+        + SH_C3[4] * x * (4.0 * zz - xx - yy) * sh[12]
+        + SH_C3[5] * z * (xx - yy) * sh[13]
+        + SH_C3[6] * x * (xx - 3.0 * yy) * sh[14];
+
+    result.max(Vec3::ZERO).extend(color.w)
+}
+
+/// The difference between two RGBA8 images.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageDiff {
+    /// The maximum absolute difference of any channel.
+    pub max: u8,
+    /// The mean absolute difference of all channels.
+    pub mean: f32,
+    /// The number of pixels with any channel differing by more than the threshold.
+    pub mismatched_pixels: usize,
+}
+
+/// Compare two RGBA8 images of the same size.
+///
+/// A pixel is mismatched if any of its channels differs by more than `threshold`.
+pub fn image_diff(a: &[u8], b: &[u8], threshold: u8) -> ImageDiff {
+    assert_eq!(a.len(), b.len(), "image sizes differ");
+
+    let mut max = 0;
+    let mut sum = 0u64;
+    let mut mismatched_pixels = 0;
+    for (a, b) in a.chunks(4).zip(b.chunks(4)) {
+        let pixel_max = a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| a.abs_diff(*b))
+            .inspect(|diff| sum += *diff as u64)
+            .max()
+            .unwrap_or(0);
+        max = max.max(pixel_max);
+        mismatched_pixels += (pixel_max > threshold) as usize;
+    }
+
+    ImageDiff {
+        max,
+        mean: sum as f32 / a.len().max(1) as f32,
+        mismatched_pixels,
+    }
+}
+
+/// Assert that the GPU render of the Gaussians matches the CPU reference.
+///
+/// At most `max_mismatched_fraction` of the pixels may differ by more than `threshold` in any
+/// channel, which allows for the rasterization differences on the splat boundaries.
+pub fn assert_matches_reference(
+    ctx: &TestContext,
+    gaussians: &impl IterGaussian,
+    camera: &Camera,
+    size: UVec2,
+    threshold: u8,
+    max_mismatched_fraction: f32,
+) {
+    let gpu = render_reference(ctx, gaussians, camera, size);
+    let cpu = rasterize_reference(gaussians, camera, size);
+    let diff = image_diff(&gpu, &cpu, threshold);
+
+    let max_mismatched_pixels = ((size.x * size.y) as f32 * max_mismatched_fraction) as usize;
+    assert!(
+        diff.mismatched_pixels <= max_mismatched_pixels,
+        "GPU render differs from CPU reference: {} mismatched pixels (at most {} allowed), \
+        max difference {}, mean difference {}",
+        diff.mismatched_pixels,
+        max_mismatched_pixels,
+        diff.max,
+        diff.mean,
+    );
+    assert!(
+        cpu.chunks(4).any(|pixel| pixel[..3] != [0, 0, 0]),
+        "CPU reference is empty, the fixture is out of view",
+    );
+}
@@ -1,5 +1,7 @@
 #[cfg(feature = "multi-model")]
 mod multi_model;
+mod quick;
+mod reference;
 #[cfg(feature = "viewer-selection")]
 mod selection;
 mod viewer;
@@ -1,7 +1,9 @@
 use glam::*;
-use wgpu_3dgs_core::{BufferWrapper, GaussianMaxStdDev};
+use wgpu_3dgs_core::{BufferWrapper, GaussianMaxStdDev, GaussiansBuffer};
 use wgpu_3dgs_viewer::{
-    CameraPod, MultiModelViewer, MultiModelViewerGaussianBuffers,
+    CameraPod, DepthMapping, MultiModelViewer, MultiModelViewerAppendError,
+    MultiModelViewerCreateOptions, MultiModelViewerGaussianBuffers, ViewerCreateError,
+    ViewerCreateOptions,
     core::{
         Gaussian, GaussianDisplayMode, GaussianPodWithShSingleCov3dSingleConfigs, GaussianShDegree,
         GaussianTransformPod, ModelTransformPod,
@@ -52,6 +54,73 @@ fn test_multi_model_viewer_gaussian_buffers_new_empty_should_create_buffer_with_
     );
 }
 
+#[test]
+fn test_multi_model_viewer_new_with_options_should_use_gaussians_buffer_usage() {
+    let ctx = TestContext::new();
+    let usage = MultiModelViewerCreateOptions::default().gaussians_buffer_usage
+        | wgpu::BufferUsages::VERTEX;
+
+    let viewer = MultiModelViewer::<G, &str>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        MultiModelViewerCreateOptions {
+            gaussians_buffer_usage: usage,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    assert_eq!(viewer.gaussians_buffer_usage, usage);
+
+    let viewer = MultiModelViewer::<G, &str>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        ViewerCreateOptions {
+            gaussians_buffer_usage: usage,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    assert_eq!(viewer.gaussians_buffer_usage, usage);
+}
+
+#[test]
+fn test_multi_model_viewer_create_options_default_should_allow_append() {
+    let usage = MultiModelViewerCreateOptions::default().gaussians_buffer_usage;
+
+    assert!(usage.contains(GaussiansBuffer::<G>::DEFAULT_USAGES | wgpu::BufferUsages::COPY_SRC));
+    assert!(
+        MultiModelViewerCreateOptions::from(ViewerCreateOptions::default())
+            .gaussians_buffer_usage
+            .contains(wgpu::BufferUsages::COPY_SRC)
+    );
+}
+
+#[test]
+fn test_multi_model_viewer_new_with_options_should_use_label_prefix() {
+    let ctx = TestContext::new();
+
+    let viewer = MultiModelViewer::<G, &str>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        ViewerCreateOptions {
+            label_prefix: Some("Scene".to_string()),
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    assert_eq!(
+        viewer.preprocessor.create_options().label_prefix.as_deref(),
+        Some("Scene")
+    );
+    assert_eq!(
+        viewer.renderer.create_options().label_prefix.as_deref(),
+        Some("Scene")
+    );
+}
+
 #[test]
 fn test_multi_model_viewer_update_camera_when_with_or_without_pod_should_be_equal() {
     let ctx = TestContext::new();
@@ -233,6 +302,77 @@ fn test_multi_model_viewer_update_gaussian_transform_with_pod_when_no_sh0_is_set
     });
 }
 
+#[test]
+fn test_multi_model_viewer_update_model_gaussian_transform_when_no_sh0_is_set_should_only_affect_model()
+ {
+    let ctx = TestContext::new();
+    let red_gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let green_gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::new(1.0, 0.0, 1.0),
+        color: U8Vec4::new(0, 255, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = MultiModelViewer::<G, &str>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm)
+        .expect("viewer");
+
+    viewer.insert_model(&ctx.device, "red", &red_gaussians);
+    viewer.insert_model(&ctx.device, "green", &green_gaussians);
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer
+        .update_model_gaussian_transform(
+            &ctx.device,
+            &ctx.queue,
+            &"red",
+            1.0,
+            GaussianDisplayMode::Splat,
+            GaussianShDegree::new(3).expect("sh deg"),
+            true,
+            GaussianMaxStdDev::new(3.0).expect("max std dev"),
+        )
+        .expect("update model gaussian transform");
+
+    render_and_assert(
+        &ctx,
+        &viewer,
+        &render_target,
+        &[&"red"],
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x > 1);
+            assert!(sum.y > 1);
+            assert!(sum.z > 1);
+            assert!(sum.w > 1);
+        },
+    );
+
+    render_and_assert(
+        &ctx,
+        &viewer,
+        &render_target,
+        &[&"green"],
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x < 1);
+            assert!(sum.y > 1);
+            assert!(sum.z < 1);
+            assert!(sum.w > 1);
+        },
+    );
+}
+
 fn test_multi_model_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(
     update_model_transform: impl FnOnce(&mut MultiModelViewer<G, &str>, &wgpu::Queue),
 ) {
@@ -373,3 +513,167 @@ fn test_multi_model_viewer_remove_model_should_not_render_removed_model() {
         },
     );
 }
+
+#[test]
+fn test_multi_model_viewer_set_solo_when_key_is_some_should_only_render_solo_model() {
+    let ctx = TestContext::new();
+    let red_gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let green_gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::new(1.0, 0.0, 1.0),
+        color: U8Vec4::new(0, 255, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = MultiModelViewer::<G, &str>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm)
+        .expect("viewer");
+
+    viewer.insert_model(&ctx.device, "red", &red_gaussians);
+    viewer.insert_model(&ctx.device, "green", &green_gaussians);
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    viewer.set_solo(Some("green"));
+    assert_eq!(viewer.solo(), Some(&"green"));
+
+    render_and_assert(
+        &ctx,
+        &viewer,
+        &render_target,
+        &[&"red", &"green"],
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x < 1);
+            assert!(sum.y > 1);
+            assert!(sum.z < 1);
+            assert!(sum.w > 1);
+        },
+    );
+
+    viewer.set_solo(None);
+
+    render_and_assert(
+        &ctx,
+        &viewer,
+        &render_target,
+        &[&"red", &"green"],
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x > 1);
+            assert!(sum.y > 1);
+        },
+    );
+}
+
+#[test]
+fn test_multi_model_viewer_append_to_model_should_grow_buffers_and_render_appended_gaussians() {
+    let ctx = TestContext::new();
+    let gaussian = Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    };
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = MultiModelViewer::<G, &str>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm)
+        .expect("viewer");
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer.insert_model(&ctx.device, "model", &vec![gaussian]);
+
+    viewer
+        .append_to_model(&ctx.device, &ctx.queue, &"model", &[gaussian])
+        .expect("append");
+    viewer
+        .append_to_model(&ctx.device, &ctx.queue, &"model", &[gaussian, gaussian])
+        .expect("append");
+
+    let buffers = &viewer.models[&"model"].gaussian_buffers;
+    assert_eq!(buffers.gaussians_buffer.len(), 4);
+
+    let downloaded = pollster::block_on(
+        buffers
+            .gaussians_buffer
+            .download_gaussians(&ctx.device, &ctx.queue),
+    )
+    .expect("download");
+    assert!(
+        downloaded
+            .iter()
+            .all(|downloaded| downloaded.pos == gaussian.pos)
+    );
+
+    render_and_assert(
+        &ctx,
+        &viewer,
+        &render_target,
+        &[&"model"],
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x > 0);
+        },
+    );
+}
+
+#[test]
+fn test_multi_model_viewer_append_to_model_when_usage_has_no_copy_src_should_return_error() {
+    let ctx = TestContext::new();
+    let gaussian = Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    };
+
+    let mut viewer = MultiModelViewer::<G, &str>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm)
+        .expect("viewer");
+    viewer.insert_model_with(
+        &ctx.device,
+        "model",
+        GaussiansBuffer::<G>::DEFAULT_USAGES,
+        &vec![gaussian],
+    );
+
+    assert!(matches!(
+        viewer.append_to_model(&ctx.device, &ctx.queue, &"model", &[gaussian]),
+        Err(MultiModelViewerAppendError::MissingCopySrcUsage(_))
+    ));
+    assert!(matches!(
+        viewer.append_to_model(&ctx.device, &ctx.queue, &"missing", &[gaussian]),
+        Err(MultiModelViewerAppendError::ModelNotFound)
+    ));
+}
+
+#[test]
+fn test_multi_model_viewer_new_with_options_when_depth_mapping_is_custom_should_return_error() {
+    let ctx = TestContext::new();
+
+    let result = MultiModelViewer::<G, &str>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        MultiModelViewerCreateOptions {
+            depth_mapping: DepthMapping::Custom,
+            ..Default::default()
+        },
+    );
+
+    assert!(matches!(
+        result,
+        Err(ViewerCreateError::UnsupportedDepthMapping(
+            DepthMapping::Custom
+        ))
+    ));
+}
@@ -0,0 +1,58 @@
+use glam::*;
+use wgpu_3dgs_viewer::{DefaultGaussianPod, Viewer, core::Gaussian, quick};
+
+use crate::common::{TestContext, assert_render_target, given};
+
+#[test]
+fn test_quick_render_should_be_equal_to_viewer_render() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+    let render_target_view1 = render_target1.create_view(&wgpu::TextureViewDescriptor::default());
+    let render_target_view2 = render_target2.create_view(&wgpu::TextureViewDescriptor::default());
+    let camera = given::camera_pod();
+
+    quick::render(
+        &ctx.device,
+        &ctx.queue,
+        &render_target_view1,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &camera,
+        &gaussians,
+    )
+    .expect("quick render");
+
+    let mut viewer =
+        Viewer::<DefaultGaussianPod>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians)
+            .expect("viewer");
+    viewer.update_camera_with_pod(&ctx.queue, &camera);
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.render(&mut encoder, &render_target_view2);
+
+    ctx.queue.submit(Some(encoder.finish()));
+    ctx.device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .expect("device poll");
+
+    assert_render_target(&ctx, &render_target_view1, |pixels1: &[UVec4]| {
+        assert!(pixels1.iter().sum::<UVec4>().x > 1);
+
+        assert_render_target(&ctx, &render_target_view2, |pixels2: &[UVec4]| {
+            assert_eq!(pixels1, pixels2);
+        });
+    });
+}
@@ -0,0 +1,68 @@
+use glam::*;
+use wgpu_3dgs_viewer::core::Gaussian;
+
+use crate::common::{TestContext, assert_matches_reference, given};
+
+const SIZE: UVec2 = UVec2::new(64, 64);
+
+fn gaussian(pos: Vec3, color: U8Vec4, scale: Vec3) -> Gaussian {
+    Gaussian {
+        rot: Quat::IDENTITY,
+        pos,
+        color,
+        sh: [Vec3::ZERO; 15],
+        scale,
+    }
+}
+
+#[test]
+fn test_render_when_single_splat_should_match_reference() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::from_rotation_z(0.5),
+        ..gaussian(
+            Vec3::new(0.2, 0.1, 4.0),
+            U8Vec4::new(255, 128, 0, 200),
+            Vec3::new(0.3, 0.15, 0.2),
+        )
+    }];
+
+    assert_matches_reference(&ctx, &gaussians, &given::camera(), SIZE, 2, 0.01);
+}
+
+#[test]
+fn test_render_when_two_overlapping_splats_should_match_reference() {
+    let ctx = TestContext::new();
+    let gaussians = vec![
+        gaussian(
+            Vec3::new(0.1, 0.0, 4.0),
+            U8Vec4::new(255, 0, 0, 180),
+            Vec3::splat(0.3),
+        ),
+        gaussian(
+            Vec3::new(0.3, 0.2, 5.0),
+            U8Vec4::new(0, 0, 255, 220),
+            Vec3::splat(0.4),
+        ),
+    ];
+
+    assert_matches_reference(&ctx, &gaussians, &given::camera(), SIZE, 2, 0.01);
+}
+
+#[test]
+fn test_render_when_known_sh_should_match_reference() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        sh: std::array::from_fn(|i| {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            Vec3::new(0.1, -0.05, 0.08) * sign / (i + 1) as f32
+        }),
+        ..gaussian(
+            Vec3::new(0.2, 0.1, 4.0),
+            U8Vec4::new(128, 128, 128, 255),
+            Vec3::splat(0.3),
+        )
+    }];
+
+    assert_matches_reference(&ctx, &gaussians, &given::camera(), SIZE, 2, 0.01);
+}
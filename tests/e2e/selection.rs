@@ -1,9 +1,9 @@
 use wgpu_3dgs_editor::{BasicColorRgbOverrideOrHsvModifiersPod, Modifier};
 use wgpu_3dgs_viewer::{
-    Viewer,
+    CameraBuffer, SelectionLayerError, Viewer, ViewerCreateOptions,
     core::{
-        BufferWrapper, Gaussian, GaussianPodWithShSingleCov3dSingleConfigs, GaussiansBuffer,
-        glam::*,
+        BufferWrapper, Gaussian, GaussianPodWithShSingleCov3dSingleConfigs, Gaussians,
+        GaussiansBuffer, glam::*,
     },
     editor::{BasicSelectionModifier, NonDestructiveModifier, SelectionExpr},
     selection::{ViewportSelector, ViewportSelectorType, create_viewport_bundle},
@@ -33,7 +33,7 @@ fn test_select_modify_render_and_assert(
         &ctx.device,
         wgpu::TextureFormat::Rgba8Unorm,
         &gaussians,
-        wgpu_3dgs_viewer::ViewerCreateOptions {
+        ViewerCreateOptions {
             gaussians_buffer_usage: GaussiansBuffer::<G>::DEFAULT_USAGES
                 | wgpu::BufferUsages::COPY_SRC,
             ..Default::default()
@@ -194,6 +194,47 @@ fn test_viewer_when_gaussian_is_not_in_selected_brush_should_not_be_selected_and
     );
 }
 
+#[test]
+fn test_viewer_when_gaussian_is_in_antialiased_brush_should_be_selected_and_modified() {
+    test_select_modify_render_and_assert(
+        |ctx: &TestContext, encoder: &mut wgpu::CommandEncoder, selector: &mut ViewportSelector| {
+            selector.selector_type = ViewportSelectorType::Brush;
+            selector.set_scale_factor(&ctx.queue, 2.0);
+            selector.set_edge_width(&ctx.queue, 4.0);
+            selector.start(&ctx.queue, Vec2::splat(256.0));
+            selector.update(&ctx.queue, Vec2::splat(1024.0 - 256.0));
+            selector.render(encoder);
+        },
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x < 1);
+            assert!(sum.y < 1);
+            assert!(sum.z > 1);
+            assert!(sum.w > 1);
+        },
+    );
+}
+
+#[test]
+fn test_viewer_when_gaussian_is_in_antialiased_rectangle_should_be_selected_and_modified() {
+    test_select_modify_render_and_assert(
+        |ctx: &TestContext, encoder: &mut wgpu::CommandEncoder, selector: &mut ViewportSelector| {
+            selector.selector_type = ViewportSelectorType::Rectangle;
+            selector.set_edge_width(&ctx.queue, 4.0);
+            selector.start(&ctx.queue, Vec2::splat(1024.0 - 256.0));
+            selector.update(&ctx.queue, Vec2::splat(256.0));
+            selector.render(encoder);
+        },
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x < 1);
+            assert!(sum.y < 1);
+            assert!(sum.z > 1);
+            assert!(sum.w > 1);
+        },
+    );
+}
+
 #[test]
 fn test_viewer_when_brush_radius_is_zero_should_not_be_selected_and_modified() {
     test_select_modify_render_and_assert(
@@ -214,6 +255,131 @@ fn test_viewer_when_brush_radius_is_zero_should_not_be_selected_and_modified() {
     );
 }
 
+#[test]
+fn test_viewer_when_brush_radius_is_clamped_by_bounds_should_be_selected_and_modified() {
+    test_select_modify_render_and_assert(
+        |ctx: &TestContext, encoder: &mut wgpu::CommandEncoder, selector: &mut ViewportSelector| {
+            selector.selector_type = ViewportSelectorType::Brush;
+            selector.set_brush_radius(&ctx.queue, 0.0);
+            selector.set_brush_radius_bounds(&ctx.queue, Some(10.0..=20.0));
+            assert_eq!(selector.brush_radius(), 10.0);
+
+            selector.set_brush_radius(&ctx.queue, 1e6);
+            assert_eq!(selector.brush_radius(), 20.0);
+
+            selector.start(&ctx.queue, Vec2::splat(256.0));
+            selector.update(&ctx.queue, Vec2::splat(1024.0 - 256.0));
+            selector.render(encoder);
+        },
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x < 1);
+            assert!(sum.y < 1);
+            assert!(sum.z > 1);
+            assert!(sum.w > 1);
+        },
+    );
+}
+
+#[test]
+fn test_viewport_selector_when_brush_radius_bounds_are_default_should_clamp_to_fraction_of_viewport()
+ {
+    let ctx = TestContext::new();
+    let camera_buffer = CameraBuffer::new(&ctx.device);
+
+    let mut selector = ViewportSelector::new(
+        &ctx.device,
+        &ctx.queue,
+        UVec2::new(1024, 512),
+        &camera_buffer,
+    )
+    .expect("selector");
+
+    selector.set_brush_radius(&ctx.queue, 1e6);
+    assert_eq!(selector.brush_radius(), 256.0);
+
+    selector.set_scale_factor(&ctx.queue, 2.0);
+    assert_eq!(selector.brush_radius(), 128.0);
+
+    selector.resize(&ctx.device, UVec2::new(128, 128));
+    assert_eq!(selector.brush_radius(), 32.0);
+    assert_eq!(selector.brush_radius_bounds(), 0.0..=32.0);
+
+    selector.set_brush_radius_bounds(&ctx.queue, Some(0.0..=1000.0));
+    selector.set_brush_radius(&ctx.queue, 1e6);
+    assert_eq!(selector.brush_radius(), 1000.0);
+}
+
+#[test]
+fn test_viewport_selector_when_brush_radius_bounds_are_reversed_should_swap_bounds() {
+    let ctx = TestContext::new();
+    let camera_buffer = CameraBuffer::new(&ctx.device);
+
+    let mut selector = ViewportSelector::new(
+        &ctx.device,
+        &ctx.queue,
+        UVec2::new(1024, 512),
+        &camera_buffer,
+    )
+    .expect("selector");
+
+    selector.set_radius_bounds(&ctx.queue, 20.0, 10.0);
+    assert_eq!(selector.brush_radius_bounds(), 10.0..=20.0);
+    assert_eq!(selector.brush_radius(), 20.0);
+}
+
+#[test]
+fn test_viewport_selector_when_brush_radius_bounds_are_nan_should_keep_current_bounds() {
+    let ctx = TestContext::new();
+    let camera_buffer = CameraBuffer::new(&ctx.device);
+
+    let mut selector = ViewportSelector::new(
+        &ctx.device,
+        &ctx.queue,
+        UVec2::new(1024, 512),
+        &camera_buffer,
+    )
+    .expect("selector");
+
+    selector.set_radius_bounds(&ctx.queue, 10.0, 20.0);
+    selector.set_radius_bounds(&ctx.queue, f32::NAN, 30.0);
+    selector.set_brush_radius_bounds(&ctx.queue, Some(0.0..=f32::NAN));
+    assert_eq!(selector.brush_radius_bounds(), 10.0..=20.0);
+
+    selector.set_brush_radius(&ctx.queue, 1e6);
+    assert_eq!(selector.brush_radius(), 20.0);
+}
+
+#[test]
+fn test_viewer_when_selector_type_is_set_should_keep_previous_selection() {
+    test_select_modify_render_and_assert(
+        |ctx: &TestContext, encoder: &mut wgpu::CommandEncoder, selector: &mut ViewportSelector| {
+            let mut brush_encoder =
+                ctx.device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                        label: Some("Brush Command Encoder"),
+                    });
+            selector.set_type(&mut brush_encoder, ViewportSelectorType::Brush);
+            selector.start(&ctx.queue, Vec2::splat(256.0));
+            selector.update(&ctx.queue, Vec2::splat(1024.0 - 256.0));
+            selector.render(&mut brush_encoder);
+            ctx.queue.submit(Some(brush_encoder.finish()));
+
+            selector.set_type(encoder, ViewportSelectorType::Rectangle);
+            selector.start(&ctx.queue, Vec2::splat(0.0));
+            selector.update(&ctx.queue, Vec2::splat(256.0));
+            selector.render(encoder);
+        },
+        |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert!(sum.x < 1);
+            assert!(sum.y < 1);
+            assert!(sum.z > 1);
+            assert!(sum.w > 1);
+        },
+    );
+}
+
 #[test]
 fn test_viewer_when_selection_is_cleared_should_not_be_selected_and_modified() {
     test_select_modify_render_and_assert(
@@ -233,3 +399,299 @@ fn test_viewer_when_selection_is_cleared_should_not_be_selected_and_modified() {
         },
     );
 }
+
+#[test]
+fn test_viewer_extract_selected_should_return_only_selected_gaussians() {
+    let ctx = TestContext::new();
+    let gaussians = (0..3)
+        .map(|i| Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::splat(i as f32),
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+
+    let viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            gaussians_buffer_usage: GaussiansBuffer::<G>::DEFAULT_USAGES
+                | wgpu::BufferUsages::COPY_SRC,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    let extracted = pollster::block_on(viewer.extract_selected(&ctx.device, &ctx.queue))
+        .expect("extract selected");
+
+    assert_eq!(extracted, Gaussians::Internal(Vec::new()));
+
+    ctx.queue.write_buffer(
+        viewer.selection_buffer.buffer(),
+        0,
+        bytemuck::bytes_of(&0b101u32),
+    );
+
+    let downloaded = pollster::block_on(
+        viewer
+            .gaussians_buffer
+            .download_gaussians(&ctx.device, &ctx.queue),
+    )
+    .expect("download");
+    let extracted = pollster::block_on(viewer.extract_selected(&ctx.device, &ctx.queue))
+        .expect("extract selected");
+
+    assert_eq!(
+        extracted,
+        Gaussians::Internal(vec![downloaded[0], downloaded[2]])
+    );
+}
+
+#[test]
+fn test_viewer_set_show_selection_only_should_render_only_selected_gaussians() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+    let render_target_view = render_target.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer.set_show_selection_only(&ctx.queue, true);
+
+    let render = |viewer: &Viewer<G>| {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder"),
+            });
+
+        viewer.render(&mut encoder, &render_target_view);
+
+        ctx.queue.submit(Some(encoder.finish()));
+        ctx.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("device poll");
+    };
+
+    render(&viewer);
+
+    assert_render_target(&ctx, &render_target_view, |pixels: &[UVec4]| {
+        assert_eq!(pixels.iter().sum::<UVec4>().x, 0);
+    });
+
+    ctx.queue.write_buffer(
+        viewer.selection_buffer.buffer(),
+        0,
+        bytemuck::bytes_of(&0b1u32),
+    );
+
+    render(&viewer);
+
+    assert_render_target(&ctx, &render_target_view, |pixels: &[UVec4]| {
+        assert!(pixels.iter().sum::<UVec4>().x > 1);
+    });
+}
+
+#[test]
+fn test_viewer_clear_selection_should_deselect_all_gaussians() {
+    let ctx = TestContext::new();
+    let gaussians = (0..3)
+        .map(|i| Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::splat(i as f32),
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+
+    let viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    ctx.queue.write_buffer(
+        viewer.selection_buffer.buffer(),
+        0,
+        bytemuck::bytes_of(&0b111u32),
+    );
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.clear_selection(&mut encoder);
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let selection = pollster::block_on(
+        viewer
+            .selection_buffer
+            .download::<u32>(&ctx.device, &ctx.queue),
+    )
+    .expect("download");
+
+    assert!(selection.iter().all(|word| *word == 0));
+}
+
+#[test]
+fn test_viewer_set_active_selection_layer_should_keep_each_layer() {
+    let ctx = TestContext::new();
+    let gaussians = (0..3)
+        .map(|i| Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::splat(i as f32),
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            selection_layer_count: 2,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    assert_eq!(viewer.selection_layer_count(), 2);
+    assert_eq!(viewer.active_selection_layer(), 0);
+
+    let download = |viewer: &Viewer<G>| {
+        pollster::block_on(
+            viewer
+                .selection_buffer
+                .download::<u32>(&ctx.device, &ctx.queue),
+        )
+        .expect("download")[0]
+    };
+
+    let switch = |viewer: &mut Viewer<G>, layer: u32| {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder"),
+            });
+
+        let result = viewer.set_active_selection_layer(&ctx.queue, &mut encoder, layer);
+
+        ctx.queue.submit(Some(encoder.finish()));
+        result
+    };
+
+    ctx.queue.write_buffer(
+        viewer.selection_buffer.buffer(),
+        0,
+        bytemuck::bytes_of(&0b101u32),
+    );
+
+    switch(&mut viewer, 1).expect("switch to layer 1");
+    assert_eq!(viewer.active_selection_layer(), 1);
+    assert_eq!(download(&viewer), 0);
+
+    ctx.queue.write_buffer(
+        viewer.selection_buffer.buffer(),
+        0,
+        bytemuck::bytes_of(&0b010u32),
+    );
+
+    switch(&mut viewer, 0).expect("switch to layer 0");
+    assert_eq!(download(&viewer), 0b101);
+
+    switch(&mut viewer, 1).expect("switch to layer 1");
+    assert_eq!(download(&viewer), 0b010);
+
+    assert!(matches!(
+        switch(&mut viewer, 2),
+        Err(SelectionLayerError::LayerOutOfBounds {
+            layer: 2,
+            layer_count: 2
+        })
+    ));
+    assert_eq!(viewer.active_selection_layer(), 1);
+}
+
+#[test]
+fn test_viewer_update_selection_highlight_should_color_gaussians_in_highlighted_layer() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+    let render_target_view = render_target.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            selection_layer_count: 2,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    // Select the Gaussian in layer 1, then switch back to layer 0 so it is not culled
+    for (layer, selection) in [(1, 0b1u32), (0, 0b0u32)] {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder"),
+            });
+        viewer
+            .set_active_selection_layer(&ctx.queue, &mut encoder, layer)
+            .expect("switch layer");
+        ctx.queue.submit(Some(encoder.finish()));
+
+        ctx.queue.write_buffer(
+            viewer.selection_buffer.buffer(),
+            0,
+            bytemuck::bytes_of(&selection),
+        );
+    }
+
+    viewer.update_selection_highlight(&ctx.queue, &[Vec4::ZERO, Vec4::new(0.0, 1.0, 0.0, 1.0)]);
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.render(&mut encoder, &render_target_view);
+
+    ctx.queue.submit(Some(encoder.finish()));
+    ctx.device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .expect("device poll");
+
+    assert_render_target(&ctx, &render_target_view, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x < 1);
+        assert!(sum.y > 1);
+    });
+}
@@ -1,10 +1,15 @@
 use glam::*;
-use wgpu_3dgs_core::GaussianMaxStdDev;
+use wgpu_3dgs_core::{BufferWrapper, GaussianMaxStdDev};
 use wgpu_3dgs_viewer::{
-    CameraPod, Viewer,
+    Camera, CameraPod, CameraTrait, Colormap, Cov2dRegularization, CustomShaderError, DepthDisplay,
+    DepthMapping, Preprocessor, PreprocessorCreateError, PreprocessorCreateOptions,
+    PreprocessorSettings, RenderQuadMode, Renderer, RendererCreateError, RendererCreateOptions,
+    RendererSettings, ShClamp, SortKeyBuffer, Tonemap, Viewer, ViewerCreateError,
+    ViewerCreateOptions, ViewerDownloadError, ViewerPreprocessorSettingsError,
     core::{
-        Gaussian, GaussianDisplayMode, GaussianPodWithShSingleCov3dSingleConfigs, GaussianShDegree,
-        GaussianTransformPod, ModelTransformPod,
+        Gaussian, GaussianDisplayMode, GaussianPod, GaussianPodWithShNorm8Cov3dSingleConfigs,
+        GaussianPodWithShSingleCov3dSingleConfigs, GaussianShDegree, GaussianTransformPod,
+        Gaussians, ModelTransformPod,
     },
 };
 
@@ -12,9 +17,9 @@ use crate::common::{TestContext, assert_render_target, given};
 
 type G = GaussianPodWithShSingleCov3dSingleConfigs;
 
-fn render_and_assert(
+fn render_and_assert<P: GaussianPod>(
     ctx: &TestContext,
-    viewer: &Viewer<G>,
+    viewer: &Viewer<P>,
     render_target: &wgpu::Texture,
     assertion: impl Fn(&[UVec4]),
 ) {
@@ -95,9 +100,8 @@ fn test_viewer_render_should_render_correctly() {
     });
 }
 
-fn test_viewer_when_no_sh0_is_set_should_and_render_as_grayscale(
-    update_gaussian_transform: impl FnOnce(&mut Viewer<G>, &wgpu::Queue),
-) {
+#[test]
+fn test_viewer_render_when_sorting_is_disabled_should_render_correctly() {
     let ctx = TestContext::new();
     let gaussians = vec![Gaussian {
         rot: Quat::IDENTITY,
@@ -113,50 +117,382 @@ fn test_viewer_when_no_sh0_is_set_should_and_render_as_grayscale(
         Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
 
     viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
-    update_gaussian_transform(&mut viewer, &ctx.queue);
+    viewer.set_sorting_enabled(&ctx.queue, false);
+
+    assert!(!viewer.sorting_enabled());
 
     render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
         let sum = pixels.iter().sum::<UVec4>();
         assert!(sum.x > 1);
-        assert!(sum.y > 1);
-        assert!(sum.z > 1);
+        assert!(sum.y < 1);
+        assert!(sum.z < 1);
         assert!(sum.w > 1);
     });
 }
 
 #[test]
-fn test_viewer_update_gaussian_transform_when_no_sh0_is_set_should_render_as_grayscale() {
-    test_viewer_when_no_sh0_is_set_should_and_render_as_grayscale(|viewer, queue| {
-        viewer.update_gaussian_transform(
-            queue,
-            1.0,
-            GaussianDisplayMode::Splat,
-            GaussianShDegree::new(3).expect("sh deg"),
-            true,
-            GaussianMaxStdDev::new(3.0).expect("max std dev"),
+fn test_viewer_new_with_options_when_label_prefix_is_set_should_render_correctly() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            label_prefix: Some("Model A".to_string()),
+            linear_depth: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    assert_eq!(
+        viewer.preprocessor.create_options().label_prefix.as_deref(),
+        Some("Model A")
+    );
+    assert_eq!(
+        viewer.renderer.create_options().label_prefix.as_deref(),
+        Some("Model A")
+    );
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+        assert!(sum.y < 1);
+        assert!(sum.z < 1);
+        assert!(sum.w > 1);
+    });
+}
+
+#[test]
+fn test_viewer_render_when_sort_is_frozen_should_reuse_last_order() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    render_and_assert(&ctx, &viewer, &render_target, |_: &[UVec4]| {});
+
+    viewer.freeze_sort();
+
+    assert!(viewer.sort_frozen());
+
+    // The Gaussian is behind the camera, so it would be culled if preprocessed again
+    let camera = Camera {
+        yaw: std::f32::consts::PI,
+        ..given::camera()
+    };
+    viewer.update_camera_with_pod(&ctx.queue, &CameraPod::new(&camera, UVec2::splat(1024)));
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+    });
+
+    viewer.unfreeze_sort();
+
+    assert!(!viewer.sort_frozen());
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x < 1);
+    });
+}
+
+#[test]
+fn test_viewer_render_when_depth_mapping_is_logarithmic_should_render_correctly() {
+    let ctx = TestContext::new();
+    let gaussians = vec![
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z * 2.0,
+            color: U8Vec4::new(0, 0, 255, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+    ];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let viewers = [DepthMapping::Linear, DepthMapping::Logarithmic].map(|depth_mapping| {
+        let mut viewer = Viewer::<G>::new_with_options(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            &gaussians,
+            ViewerCreateOptions {
+                depth_mapping,
+                ..Default::default()
+            },
+        )
+        .expect("viewer");
+        viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+        viewer
+    });
+
+    assert_eq!(
+        viewers[1].preprocessor.depth_mapping(),
+        DepthMapping::Logarithmic
+    );
+
+    render_and_assert(&ctx, &viewers[0], &render_target1, |pixels1: &[UVec4]| {
+        render_and_assert(&ctx, &viewers[1], &render_target2, |pixels2: &[UVec4]| {
+            assert_eq!(pixels1, pixels2);
+        });
+    });
+}
+
+#[test]
+fn test_viewer_render_when_depth_mapping_is_linear_reverse_z_should_render_correctly() {
+    let ctx = TestContext::new();
+    let gaussians = vec![
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z * 2.0,
+            color: U8Vec4::new(0, 0, 255, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+    ];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let viewers = [
+        (DepthMapping::Linear, false),
+        (DepthMapping::LinearReverseZ, true),
+    ]
+    .map(|(depth_mapping, reverse_z)| {
+        let mut viewer = Viewer::<G>::new_with_options(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            &gaussians,
+            ViewerCreateOptions {
+                depth_mapping,
+                ..Default::default()
+            },
+        )
+        .expect("viewer");
+        let camera = Camera {
+            reverse_z,
+            ..given::camera()
+        };
+        viewer.update_camera_with_pod(&ctx.queue, &CameraPod::new(&camera, UVec2::splat(1024)));
+        viewer
+    });
+
+    render_and_assert(&ctx, &viewers[0], &render_target1, |pixels1: &[UVec4]| {
+        render_and_assert(&ctx, &viewers[1], &render_target2, |pixels2: &[UVec4]| {
+            assert_eq!(pixels1, pixels2);
+        });
+    });
+}
+
+#[test]
+fn test_viewer_render_when_depth_mapping_is_custom_should_render_in_order_of_sort_keys() {
+    let ctx = TestContext::new();
+    let gaussians = vec![
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z * 2.0,
+            color: U8Vec4::new(0, 0, 255, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+    ];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let viewers = [[0.0, 1.0], [1.0, 0.0]].map(|keys| {
+        let viewer = Viewer::<G>::new_with_options(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            &gaussians,
+            ViewerCreateOptions {
+                depth_mapping: DepthMapping::Custom,
+                sort_keys: Some(SortKeyBuffer::new(&ctx.device, &keys)),
+                ..Default::default()
+            },
+        )
+        .expect("viewer");
+        viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+        viewer
+    });
+
+    assert_eq!(
+        viewers[0].preprocessor.depth_mapping(),
+        DepthMapping::Custom
+    );
+    assert!(viewers[0].preprocessor.sort_key_bind_group().is_some());
+
+    render_and_assert(&ctx, &viewers[0], &render_target1, |pixels1: &[UVec4]| {
+        render_and_assert(&ctx, &viewers[1], &render_target2, |pixels2: &[UVec4]| {
+            assert_ne!(pixels1, pixels2);
+        });
+    });
+}
+
+#[test]
+fn test_viewer_new_with_options_when_depth_mapping_is_custom_without_sort_keys_should_return_error()
+{
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let result = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            depth_mapping: DepthMapping::Custom,
+            ..Default::default()
+        },
+    );
+
+    assert!(matches!(result, Err(ViewerCreateError::MissingSortKeys)));
+}
+
+#[test]
+fn test_viewer_set_sh_dither_should_render_similarly() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::splat(0.1); 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let mut viewer = Viewer::<GaussianPodWithShNorm8Cov3dSingleConfigs>::new(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target1, |_| {});
+
+    viewer.set_sh_dither(&ctx.queue, true);
+
+    render_and_assert(&ctx, &viewer, &render_target2, |pixels2: &[UVec4]| {
+        assert_render_target(
+            &ctx,
+            &render_target1.create_view(&Default::default()),
+            |pixels1| {
+                assert!(
+                    pixels1
+                        .iter()
+                        .zip(pixels2)
+                        .all(|(p1, p2)| p1.as_ivec4().distance_squared(p2.as_ivec4()) <= 3 * 2 * 2)
+                );
+            },
         );
     });
 }
 
 #[test]
-fn test_viewer_update_gaussian_transform_with_pod_when_no_sh0_is_set_should_render_as_grayscale() {
-    test_viewer_when_no_sh0_is_set_should_and_render_as_grayscale(|viewer, queue| {
-        viewer.update_gaussian_transform_with_pod(
-            queue,
-            &GaussianTransformPod::new(
-                1.0,
-                GaussianDisplayMode::Splat,
-                GaussianShDegree::new(3).expect("sh deg"),
-                true,
-                GaussianMaxStdDev::new(3.0).expect("max std dev"),
-            ),
+fn test_viewer_render_when_camera_is_orthographic_should_render_same_sh_color_at_any_distance() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z + Vec3::X * 0.5,
+        color: U8Vec4::new(128, 128, 128, 255),
+        sh: [Vec3::splat(0.3); 15],
+        scale: Vec3::splat(0.2),
+    }];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+    let size = UVec2::new(render_target1.size().width, render_target1.size().height);
+
+    let mut camera = Camera {
+        yaw: 0.1,
+        pitch: 0.1,
+        ..Camera::new_orthographic(0.1..1e4, 4.0)
+    };
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera(&ctx.queue, &camera, size);
+
+    render_and_assert(&ctx, &viewer, &render_target1, |_| {});
+
+    camera.move_by(-5.0, 0.0);
+    viewer.update_camera(&ctx.queue, &camera, size);
+
+    render_and_assert(&ctx, &viewer, &render_target2, |pixels2: &[UVec4]| {
+        assert_render_target(
+            &ctx,
+            &render_target1.create_view(&Default::default()),
+            |pixels1| {
+                assert!(pixels1.iter().sum::<UVec4>().x > 1);
+                assert!(
+                    pixels1
+                        .iter()
+                        .zip(pixels2)
+                        .all(|(p1, p2)| p1.as_ivec4().distance_squared(p2.as_ivec4()) <= 3 * 2 * 2)
+                );
+            },
         );
     });
 }
 
-fn test_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(
-    update_model_transform: impl FnOnce(&mut Viewer<G>, &wgpu::Queue),
-) {
+#[test]
+fn test_viewer_update_renderer_settings_when_depth_display_is_set_should_render_depth_color() {
     let ctx = TestContext::new();
     let gaussians = vec![Gaussian {
         rot: Quat::IDENTITY,
@@ -172,30 +508,1779 @@ fn test_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(
         Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
 
     viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
-    update_model_transform(&mut viewer, &ctx.queue);
+    viewer.update_renderer_settings(
+        &ctx.queue,
+        &RendererSettings {
+            depth_display: Some(DepthDisplay {
+                colormap: Colormap::Grayscale,
+                range: 0.0..10.0,
+            }),
+            ..Default::default()
+        },
+    );
 
     render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
         let sum = pixels.iter().sum::<UVec4>();
-        assert_eq!(sum.x, 0);
-        assert_eq!(sum.y, 0);
-        assert_eq!(sum.z, 0);
+        assert!(sum.x > 1);
+        assert!(sum.y > 1);
+        assert!(sum.z > 1);
     });
 }
 
 #[test]
-fn test_viewer_update_model_transform_when_model_pos_is_behind_camera_should_not_render_gaussian() {
-    test_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(|viewer, queue| {
-        viewer.update_model_transform(queue, Vec3::ZERO - Vec3::Z, Quat::IDENTITY, Vec3::ONE);
+fn test_viewer_update_renderer_settings_when_cov2d_epsilon_is_larger_should_render_more_pixels() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::new(1.0, 0.0, 0.0),
+    }];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer.update_renderer_settings(
+        &ctx.queue,
+        &RendererSettings {
+            cov2d_regularization: Cov2dRegularization {
+                epsilon: 0.0,
+                highlight: false,
+            },
+            ..Default::default()
+        },
+    );
+
+    render_and_assert(&ctx, &viewer, &render_target1, |_| {});
+
+    viewer.update_renderer_settings(
+        &ctx.queue,
+        &RendererSettings {
+            cov2d_regularization: Cov2dRegularization {
+                epsilon: 4.0,
+                highlight: false,
+            },
+            ..Default::default()
+        },
+    );
+
+    render_and_assert(&ctx, &viewer, &render_target2, |pixels2: &[UVec4]| {
+        assert_render_target(
+            &ctx,
+            &render_target1.create_view(&Default::default()),
+            |pixels1| {
+                assert!(pixels2.iter().sum::<UVec4>().x > pixels1.iter().sum::<UVec4>().x);
+            },
+        );
     });
 }
 
 #[test]
-fn test_viewer_update_model_transform_with_pod_when_model_pos_is_behind_camera_should_not_render_gaussian()
- {
-    test_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(|viewer, queue| {
-        viewer.update_model_transform_with_pod(
-            queue,
-            &ModelTransformPod::new(Vec3::ZERO - Vec3::Z, Quat::IDENTITY, Vec3::ONE),
-        );
+fn test_viewer_update_renderer_settings_when_cov2d_highlight_is_set_should_render_magenta() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::new(1.0, 0.0, 0.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer.update_renderer_settings(
+        &ctx.queue,
+        &RendererSettings {
+            cov2d_regularization: Cov2dRegularization {
+                epsilon: 4.0,
+                highlight: true,
+            },
+            ..Default::default()
+        },
+    );
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+        assert_eq!(sum.y, 0);
+        assert!(sum.z > 1);
+    });
+}
+
+#[test]
+fn test_viewer_set_min_opacity_when_opacity_is_below_min_opacity_should_not_render_gaussian() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 128),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer.set_min_opacity(&ctx.queue, 0.4);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
     });
+
+    viewer.set_min_opacity(&ctx.queue, 0.6);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x < 1);
+    });
+}
+
+#[test]
+fn test_viewer_set_min_pixel_size_when_size_is_below_min_pixel_size_should_not_render_gaussian() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(0.01),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer
+        .set_min_pixel_size(&ctx.queue, 1.0)
+        .expect("min pixel size");
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+    });
+
+    viewer
+        .set_min_pixel_size(&ctx.queue, 1024.0)
+        .expect("min pixel size");
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x < 1);
+    });
+}
+
+#[test]
+fn test_viewer_gaussians_when_created_retaining_gaussians_should_return_gaussians() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    assert!(viewer.gaussians().is_none());
+
+    let viewer = Viewer::<G>::new_retaining_gaussians(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        Gaussians::Internal(gaussians.clone()),
+        ViewerCreateOptions::default(),
+    )
+    .expect("viewer");
+
+    assert_eq!(
+        viewer.gaussians(),
+        Some(&Gaussians::Internal(gaussians.clone()))
+    );
+    assert_eq!(viewer.gaussians_buffer.len(), gaussians.len());
+}
+
+fn test_viewer_when_no_sh0_is_set_should_and_render_as_grayscale(
+    update_gaussian_transform: impl FnOnce(&mut Viewer<G>, &wgpu::Queue),
+) {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    update_gaussian_transform(&mut viewer, &ctx.queue);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+        assert!(sum.y > 1);
+        assert!(sum.z > 1);
+        assert!(sum.w > 1);
+    });
+}
+
+#[test]
+fn test_viewer_update_gaussian_transform_when_no_sh0_is_set_should_render_as_grayscale() {
+    test_viewer_when_no_sh0_is_set_should_and_render_as_grayscale(|viewer, queue| {
+        viewer.update_gaussian_transform(
+            queue,
+            1.0,
+            GaussianDisplayMode::Splat,
+            GaussianShDegree::new(3).expect("sh deg"),
+            true,
+            GaussianMaxStdDev::new(3.0).expect("max std dev"),
+        );
+    });
+}
+
+#[test]
+fn test_viewer_update_gaussian_transform_with_pod_when_no_sh0_is_set_should_render_as_grayscale() {
+    test_viewer_when_no_sh0_is_set_should_and_render_as_grayscale(|viewer, queue| {
+        viewer.update_gaussian_transform_with_pod(
+            queue,
+            &GaussianTransformPod::new(
+                1.0,
+                GaussianDisplayMode::Splat,
+                GaussianShDegree::new(3).expect("sh deg"),
+                true,
+                GaussianMaxStdDev::new(3.0).expect("max std dev"),
+            ),
+        );
+    });
+}
+
+fn test_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(
+    update_model_transform: impl FnOnce(&mut Viewer<G>, &wgpu::Queue),
+) {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    update_model_transform(&mut viewer, &ctx.queue);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert_eq!(sum.x, 0);
+        assert_eq!(sum.y, 0);
+        assert_eq!(sum.z, 0);
+    });
+}
+
+#[test]
+fn test_viewer_update_model_transform_when_model_pos_is_behind_camera_should_not_render_gaussian() {
+    test_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(|viewer, queue| {
+        viewer.update_model_transform(queue, Vec3::ZERO - Vec3::Z, Quat::IDENTITY, Vec3::ONE);
+    });
+}
+
+#[test]
+fn test_viewer_update_model_transform_with_pod_when_model_pos_is_behind_camera_should_not_render_gaussian()
+ {
+    test_viewer_when_model_pos_is_behind_camera_should_not_render_gaussian(|viewer, queue| {
+        viewer.update_model_transform_with_pod(
+            queue,
+            &ModelTransformPod::new(Vec3::ZERO - Vec3::Z, Quat::IDENTITY, Vec3::ONE),
+        );
+    });
+}
+
+#[test]
+fn test_viewer_model_matrix_when_model_transform_is_updated_should_transform_gaussian_to_world() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::new(1.0, 2.0, 3.0),
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    assert_eq!(viewer.model_matrix(), Mat4::IDENTITY);
+
+    viewer.update_model_transform(
+        &ctx.queue,
+        Vec3::new(0.0, 0.0, 5.0),
+        Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+        Vec3::splat(2.0),
+    );
+
+    let world_pos = viewer.model_matrix().transform_point3(gaussians[0].pos);
+
+    assert!(world_pos.abs_diff_eq(Vec3::new(6.0, 4.0, 3.0), 1e-5));
+}
+
+#[test]
+fn test_viewer_render_when_linear_depth_is_enabled_should_write_view_space_depth() {
+    let ctx = TestContext::new();
+    let gaussians = [Vec3::Z, Vec3::new(1.0, 0.0, 5.0), Vec3::new(0.0, 2.0, -3.0)]
+        .into_iter()
+        .map(|pos| Gaussian {
+            rot: Quat::IDENTITY,
+            pos,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+
+    let render_target = given::render_target_texture(&ctx);
+    let camera = given::camera();
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            linear_depth: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target, |_: &[UVec4]| {});
+
+    let depths = pollster::block_on(
+        viewer
+            .gaussians_linear_depth_buffer
+            .as_ref()
+            .expect("linear depth buffer")
+            .download::<f32>(&ctx.device, &ctx.queue),
+    )
+    .expect("download");
+
+    assert_eq!(depths.len(), gaussians.len());
+    for (gaussian, depth) in gaussians.iter().zip(depths) {
+        let expected = -(camera.view() * gaussian.pos.extend(1.0)).z;
+        assert!((depth - expected).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_viewer_render_when_conic2d_is_enabled_should_write_projected_conics() {
+    let ctx = TestContext::new();
+    let gaussians = [Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -5.0)]
+        .into_iter()
+        .map(|pos| Gaussian {
+            rot: Quat::IDENTITY,
+            pos,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+
+    let render_target = given::render_target_texture(&ctx);
+    let camera = given::camera();
+    let camera_pod = given::camera_pod();
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            conic2d: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &camera_pod);
+
+    render_and_assert(&ctx, &viewer, &render_target, |_: &[UVec4]| {});
+
+    let conics = pollster::block_on(
+        viewer
+            .conic2d_buffer
+            .as_ref()
+            .expect("conic 2D buffer")
+            .download_conics(&ctx.device, &ctx.queue),
+    )
+    .expect("download");
+
+    assert_eq!(conics.len(), gaussians.len());
+
+    // In front of the camera
+    let ndc = (camera_pod.proj * camera_pod.view).project_point3(gaussians[0].pos);
+    let expected_center = (ndc.truncate() * Vec2::new(1.0, -1.0) + 1.0) * camera_pod.size * 0.5;
+    assert!(conics[0].is_valid());
+    assert!(conics[0].center.abs_diff_eq(expected_center, 1e-2));
+
+    // A unit sphere projects to a circle with a radius of the focal length over the depth
+    let cov = conics[0].covariance().expect("covariance");
+    let depth = -(camera.view() * gaussians[0].pos.extend(1.0)).z;
+    let focal = camera_pod.proj.y_axis.y * camera_pod.size.y * 0.5;
+    let expected_variance = (focal / depth).powi(2);
+    assert!((cov.x - expected_variance).abs() / expected_variance < 0.1);
+    assert!((cov.z - expected_variance).abs() / expected_variance < 0.1);
+
+    // Behind the camera
+    assert!(!conics[1].is_valid());
+    assert_eq!(conics[1].center, Vec2::ZERO);
+}
+
+#[test]
+fn test_viewer_render_when_conic2d_is_enabled_should_regularize_with_renderer_settings() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::new(0.0, 0.0, 5.0),
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            conic2d: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    let download_covariance = |viewer: &Viewer<G>| {
+        render_and_assert(&ctx, viewer, &render_target, |_: &[UVec4]| {});
+
+        pollster::block_on(
+            viewer
+                .conic2d_buffer
+                .as_ref()
+                .expect("conic 2D buffer")
+                .download_conics(&ctx.device, &ctx.queue),
+        )
+        .expect("download")[0]
+            .covariance()
+            .expect("covariance")
+    };
+
+    let default_cov = download_covariance(&viewer);
+
+    let epsilon = 100.0;
+    viewer.update_renderer_settings(
+        &ctx.queue,
+        &RendererSettings {
+            cov2d_regularization: Cov2dRegularization {
+                epsilon,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let cov = download_covariance(&viewer);
+    let expected_diff = epsilon - Cov2dRegularization::DEFAULT_EPSILON;
+
+    assert!(((cov.x - default_cov.x) - expected_diff).abs() / expected_diff < 1e-2);
+    assert!(((cov.z - default_cov.z) - expected_diff).abs() / expected_diff < 1e-2);
+}
+
+#[test]
+#[cfg(not(feature = "viewer-selection"))]
+fn test_viewer_render_when_cpu_sort_is_enabled_should_render_gaussians_sorted_on_cpu() {
+    let ctx = TestContext::new();
+    let gaussians = [Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -5.0)]
+        .into_iter()
+        .map(|pos| Gaussian {
+            rot: Quat::IDENTITY,
+            pos,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        })
+        .collect::<Vec<_>>();
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            cpu_sort: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    assert!(viewer.radix_sorter.is_none());
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+        assert!(sum.y < 1);
+    });
+}
+
+#[test]
+#[cfg(not(feature = "viewer-selection"))]
+fn test_viewer_set_min_opacity_when_cpu_sort_is_enabled_should_not_render_gaussian() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 128),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            cpu_sort: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer.set_min_opacity(&ctx.queue, 0.4);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+    });
+
+    viewer.set_min_opacity(&ctx.queue, 0.6);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x < 1);
+    });
+}
+
+#[test]
+#[cfg(not(feature = "viewer-selection"))]
+fn test_viewer_set_min_pixel_size_when_cpu_sort_is_enabled_should_return_error() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            cpu_sort: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    assert!(viewer.set_min_pixel_size(&ctx.queue, 0.0).is_ok());
+    assert!(matches!(
+        viewer.set_min_pixel_size(&ctx.queue, 1.0),
+        Err(ViewerPreprocessorSettingsError::UnsupportedMinPixelSize(
+            1.0
+        ))
+    ));
+    assert!(matches!(
+        viewer.update_preprocessor_settings(
+            &ctx.queue,
+            &PreprocessorSettings {
+                min_opacity: 0.0,
+                min_pixel_size: 1.0,
+            }
+        ),
+        Err(ViewerPreprocessorSettingsError::UnsupportedMinPixelSize(
+            1.0
+        ))
+    ));
+}
+
+#[test]
+fn test_viewer_new_with_options_when_cpu_sort_with_custom_depth_mapping_should_return_error() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+    let sort_keys = SortKeyBuffer::new(&ctx.device, &[0.0]);
+
+    let result = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            cpu_sort: true,
+            depth_mapping: DepthMapping::Custom,
+            sort_keys: Some(sort_keys),
+            ..Default::default()
+        },
+    );
+
+    assert!(matches!(
+        result,
+        Err(ViewerCreateError::UnsupportedDepthMapping(
+            DepthMapping::Custom
+        ))
+    ));
+}
+
+#[test]
+#[cfg(feature = "viewer-selection")]
+fn test_viewer_new_with_options_when_cpu_sort_with_selection_should_return_error() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let result = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            cpu_sort: true,
+            ..Default::default()
+        },
+    );
+
+    assert!(matches!(
+        result,
+        Err(ViewerCreateError::UnsupportedCpuSortSelection)
+    ));
+}
+
+#[test]
+fn test_viewer_render_when_quad_mode_is_triangle_should_be_similar_to_quad() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let viewers = [RenderQuadMode::Quad, RenderQuadMode::Triangle].map(|quad_mode| {
+        let mut viewer = Viewer::<G>::new_with_options(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            &gaussians,
+            ViewerCreateOptions {
+                quad_mode,
+                ..Default::default()
+            },
+        )
+        .expect("viewer");
+        viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+        viewer
+    });
+
+    render_and_assert(&ctx, &viewers[0], &render_target1, |pixels1: &[UVec4]| {
+        render_and_assert(&ctx, &viewers[1], &render_target2, |pixels2: &[UVec4]| {
+            let sum1 = pixels1.iter().sum::<UVec4>();
+            let sum2 = pixels2.iter().sum::<UVec4>();
+            assert!(sum1.x > 1);
+            assert!(sum1.x.abs_diff(sum2.x) <= sum1.x / 100);
+            assert!(sum1.w.abs_diff(sum2.w) <= sum1.w / 100);
+            assert_eq!(sum2.y, 0);
+            assert_eq!(sum2.z, 0);
+        });
+    });
+}
+
+#[test]
+fn test_viewer_render_supersampled_should_render_averaged_image() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    let size = UVec2::new(256, 256);
+    let pixels = pollster::block_on(viewer.render_supersampled(
+        &ctx.device,
+        &ctx.queue,
+        &given::camera(),
+        size,
+        4,
+    ))
+    .expect("render supersampled");
+
+    assert_eq!(pixels.len(), (size.x * size.y) as usize);
+
+    let sum = pixels.iter().sum::<Vec4>();
+    assert!(sum.x > 1.0);
+    assert_eq!(sum.y, 0.0);
+    assert_eq!(sum.z, 0.0);
+    assert!(sum.w > 1.0);
+    assert!(pixels.iter().all(|p| p.cmple(Vec4::ONE).all()));
+}
+
+#[test]
+fn test_viewer_render_stereo_should_render_both_halves() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+    let render_target_view = render_target.create_view(&wgpu::TextureViewDescriptor::default());
+    let size = UVec2::new(render_target.size().width, render_target.size().height);
+
+    let viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.render_stereo(
+        &ctx.device,
+        &mut encoder,
+        &render_target_view,
+        Renderer::<G>::DEFAULT_COLOR_OPS,
+        None,
+        &given::camera(),
+        size,
+        0.1,
+    );
+
+    ctx.queue.submit(Some(encoder.finish()));
+    ctx.device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .expect("device poll");
+
+    assert_render_target(&ctx, &render_target_view, |pixels: &[UVec4]| {
+        let (left, right) = pixels
+            .chunks_exact(size.x as usize)
+            .flat_map(|row| row.iter().enumerate())
+            .fold((UVec4::ZERO, UVec4::ZERO), |(left, right), (x, pixel)| {
+                if x < size.x as usize / 2 {
+                    (left + pixel, right)
+                } else {
+                    (left, right + pixel)
+                }
+            });
+
+        assert!(left.x > 1);
+        assert!(right.x > 1);
+        assert!(left.y < 1);
+        assert!(right.y < 1);
+    });
+}
+
+#[test]
+fn test_viewer_stages_when_run_individually_should_render_same_as_render() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target1, |_| {});
+
+    let render_target2_view = render_target2.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.preprocess_only(&mut encoder);
+    viewer.sort_only(&mut encoder);
+    viewer.render_only(&mut encoder, &render_target2_view);
+
+    ctx.queue.submit(Some(encoder.finish()));
+    ctx.device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .expect("device poll");
+
+    assert_render_target(&ctx, &render_target2_view, |pixels2: &[UVec4]| {
+        assert!(pixels2.iter().sum::<UVec4>().x > 1);
+
+        assert_render_target(
+            &ctx,
+            &render_target1.create_view(&Default::default()),
+            |pixels1| {
+                assert_eq!(pixels1, pixels2);
+            },
+        );
+    });
+}
+
+#[test]
+fn test_viewer_render_cubemap_should_render_gaussian_in_face_of_its_direction() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::Z * 2.0,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(0.2),
+    }];
+
+    let render_target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Cubemap Render Target"),
+        size: wgpu::Extent3d {
+            width: 1024,
+            height: 1024,
+            depth_or_array_layers: 6,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let face_views: [wgpu::TextureView; 6] = std::array::from_fn(|i| {
+        render_target.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            base_array_layer: i as u32,
+            array_layer_count: Some(1),
+            ..Default::default()
+        })
+    });
+
+    let viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.render_cubemap(
+        &ctx.device,
+        &mut encoder,
+        face_views.each_ref(),
+        Vec3::ZERO,
+        1024,
+        0.1..1e4,
+    );
+
+    ctx.queue.submit(Some(encoder.finish()));
+    ctx.device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .expect("device poll");
+
+    for (i, face_view) in face_views.iter().enumerate() {
+        assert_render_target(&ctx, face_view, |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+
+            // Only the +Z face
+            match i {
+                4 => assert!(sum.x > 1),
+                _ => assert_eq!(sum.x, 0),
+            }
+        });
+    }
+}
+
+#[test]
+#[cfg(not(feature = "viewer-selection"))]
+fn test_viewer_render_cubemap_when_cpu_sort_is_enabled_should_sort_for_each_face() {
+    let ctx = TestContext::new();
+    let gaussians = [Vec3::Z * 2.0, Vec3::NEG_X * 2.0]
+        .into_iter()
+        .map(|pos| Gaussian {
+            rot: Quat::IDENTITY,
+            pos,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(0.2),
+        })
+        .collect::<Vec<_>>();
+
+    let render_target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Cubemap Render Target"),
+        size: wgpu::Extent3d {
+            width: 1024,
+            height: 1024,
+            depth_or_array_layers: 6,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let face_views: [wgpu::TextureView; 6] = std::array::from_fn(|i| {
+        render_target.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            base_array_layer: i as u32,
+            array_layer_count: Some(1),
+            ..Default::default()
+        })
+    });
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            cpu_sort: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    // Both Gaussians are outside the depth range of the main camera looking towards +X
+    viewer.update_camera_with_pod(
+        &ctx.queue,
+        &CameraPod {
+            view: Mat4::look_to_rh(Vec3::ZERO, Vec3::X, Vec3::Y),
+            proj: Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 1e4),
+            size: Vec2::splat(1024.0),
+            _padding: [0; 2],
+        },
+    );
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Command Encoder"),
+        });
+
+    viewer.render_cubemap(
+        &ctx.device,
+        &mut encoder,
+        face_views.each_ref(),
+        Vec3::ZERO,
+        1024,
+        0.1..1e4,
+    );
+
+    ctx.queue.submit(Some(encoder.finish()));
+    ctx.device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .expect("device poll");
+
+    for (i, face_view) in face_views.iter().enumerate() {
+        assert_render_target(&ctx, face_view, |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+
+            // Only the -X and +Z faces, sorted for each face instead of the main camera
+            match i {
+                1 | 4 => assert!(sum.x > 1),
+                _ => assert_eq!(sum.x, 0),
+            }
+        });
+    }
+}
+
+#[test]
+fn test_viewer_render_with_depth_when_depth_is_cleared_to_near_plane_should_not_render_gaussian() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+    let render_target_view = render_target.create_view(&wgpu::TextureViewDescriptor::default());
+    let depth_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth Texture"),
+        size: render_target.size(),
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Depth32Float,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    for (depth, expected) in [(1.0, true), (0.0, false)] {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder"),
+            });
+
+        viewer.render_with_depth(
+            &mut encoder,
+            &render_target_view,
+            Renderer::<G>::DEFAULT_COLOR_OPS,
+            &depth_view,
+            wgpu::Operations {
+                load: wgpu::LoadOp::Clear(depth),
+                store: wgpu::StoreOp::Store,
+            },
+        );
+
+        ctx.queue.submit(Some(encoder.finish()));
+        ctx.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("device poll");
+
+        assert_render_target(&ctx, &render_target_view, |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert_eq!(sum.x > 1, expected);
+        });
+    }
+}
+
+#[test]
+fn test_viewer_render_stereo_when_depth_is_cleared_to_near_plane_should_not_render_gaussian() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+    let render_target_view = render_target.create_view(&wgpu::TextureViewDescriptor::default());
+    let size = UVec2::new(render_target.size().width, render_target.size().height);
+    let depth_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth Texture"),
+        size: render_target.size(),
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Depth32Float,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    for (depth, expected) in [(1.0, true), (0.0, false)] {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder"),
+            });
+
+        viewer.render_stereo(
+            &ctx.device,
+            &mut encoder,
+            &render_target_view,
+            Renderer::<G>::DEFAULT_COLOR_OPS,
+            Some((
+                &depth_view,
+                wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(depth),
+                    store: wgpu::StoreOp::Store,
+                },
+            )),
+            &given::camera(),
+            size,
+            0.1,
+        );
+
+        ctx.queue.submit(Some(encoder.finish()));
+        ctx.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("device poll");
+
+        assert_render_target(&ctx, &render_target_view, |pixels: &[UVec4]| {
+            let sum = pixels.iter().sum::<UVec4>();
+            assert_eq!(sum.x > 1, expected);
+        });
+    }
+}
+
+#[test]
+fn test_viewer_set_texture_format_should_render_to_new_format() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Bgra8Unorm, &gaussians).expect("viewer");
+
+    viewer
+        .set_texture_format(&ctx.device, wgpu::TextureFormat::Rgba8Unorm)
+        .expect("set texture format");
+
+    assert_eq!(
+        viewer.renderer.texture_format(),
+        wgpu::TextureFormat::Rgba8Unorm
+    );
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+        assert!(sum.y < 1);
+        assert!(sum.z < 1);
+        assert!(sum.w > 1);
+    });
+}
+
+#[test]
+fn test_renderer_new_with_wgsl_when_wgsl_is_compiled_wgsl_should_render_correctly() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    let wgsl =
+        Renderer::<G>::compiled_wgsl(&RendererCreateOptions::default()).expect("compiled wgsl");
+    viewer.renderer = Renderer::new_with_wgsl(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        RendererCreateOptions::default(),
+        &wgsl,
+        &viewer.camera_buffer,
+        &viewer.model_transform_buffer,
+        &viewer.gaussian_transform_buffer,
+        &viewer.gaussians_buffer,
+        &viewer.indirect_indices_buffer,
+        &viewer.renderer_settings_buffer,
+        #[cfg(feature = "viewer-selection")]
+        &viewer.selection_buffer,
+        #[cfg(feature = "viewer-selection")]
+        &viewer.selection_layers_buffer,
+        #[cfg(feature = "viewer-selection")]
+        &viewer.selection_highlight_buffer,
+    )
+    .expect("renderer");
+
+    assert_eq!(viewer.renderer.custom_wgsl(), Some(wgsl.as_str()));
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+        assert!(sum.y < 1);
+        assert!(sum.z < 1);
+        assert!(sum.w > 1);
+    });
+}
+
+#[test]
+fn test_renderer_new_with_wgsl_when_entry_point_is_missing_should_return_error() {
+    let ctx = TestContext::new();
+
+    let wgsl = Renderer::<G>::compiled_wgsl(&RendererCreateOptions::default())
+        .expect("compiled wgsl")
+        .replace("fn frag_main(", "fn frag_main_fork(");
+
+    let result = Renderer::<G, ()>::new_without_bind_group_with_wgsl(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        RendererCreateOptions::default(),
+        &wgsl,
+    );
+
+    assert!(matches!(
+        result,
+        Err(RendererCreateError::CustomShader(
+            CustomShaderError::MissingEntryPoint {
+                name: "frag_main",
+                ..
+            }
+        ))
+    ));
+}
+
+#[test]
+fn test_preprocessor_new_with_wgsl_when_wgsl_is_compiled_wgsl_should_create_preprocessor() {
+    let ctx = TestContext::new();
+
+    for depth_mapping in [DepthMapping::Linear, DepthMapping::Custom] {
+        let options = PreprocessorCreateOptions {
+            depth_mapping,
+            visible_bounds: true,
+            linear_depth: true,
+            conic2d: true,
+            label_prefix: Some("Test".to_string()),
+        };
+        let wgsl = Preprocessor::<G>::compiled_wgsl(&options).expect("compiled wgsl");
+
+        let preprocessor = Preprocessor::<G, ()>::new_without_bind_group_with_wgsl(
+            &ctx.device,
+            options.clone(),
+            &wgsl,
+        )
+        .expect("preprocessor");
+
+        assert_eq!(preprocessor.create_options(), options);
+        assert!(preprocessor.workgroup_size() > 0);
+    }
+}
+
+#[test]
+fn test_preprocessor_new_with_wgsl_when_wgsl_is_compiled_wgsl_should_render_correctly() {
+    let ctx = TestContext::new();
+    let gaussians = vec![
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z * 2.0,
+            color: U8Vec4::new(0, 0, 255, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+        Gaussian {
+            rot: Quat::IDENTITY,
+            pos: Vec3::ZERO + Vec3::Z,
+            color: U8Vec4::new(255, 0, 0, 255),
+            sh: [Vec3::ZERO; 15],
+            scale: Vec3::splat(1.0),
+        },
+    ];
+
+    let render_target1 = given::render_target_texture(&ctx);
+    let render_target2 = given::render_target_texture(&ctx);
+
+    let viewers = [false, true].map(|custom_wgsl| {
+        let mut viewer = Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians)
+            .expect("viewer");
+
+        if custom_wgsl {
+            let options = PreprocessorCreateOptions::default();
+            let wgsl = Preprocessor::<G>::compiled_wgsl(&options).expect("compiled wgsl");
+            viewer.preprocessor = Preprocessor::new_with_wgsl(
+                &ctx.device,
+                options,
+                &wgsl,
+                &viewer.camera_buffer,
+                &viewer.model_transform_buffer,
+                &viewer.gaussian_transform_buffer,
+                &viewer.gaussians_buffer,
+                &viewer.indirect_args_buffer,
+                &viewer.radix_sort_indirect_args_buffer,
+                &viewer.indirect_indices_buffer,
+                &viewer.gaussians_depth_buffer,
+                &viewer.preprocessor_settings_buffer,
+                None,
+                None,
+                None,
+                None,
+                #[cfg(feature = "viewer-selection")]
+                &viewer.selection_buffer,
+                #[cfg(feature = "viewer-selection")]
+                &viewer.invert_selection_buffer,
+            )
+            .expect("preprocessor");
+        }
+
+        viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+        viewer
+    });
+
+    render_and_assert(&ctx, &viewers[0], &render_target1, |pixels1: &[UVec4]| {
+        render_and_assert(&ctx, &viewers[1], &render_target2, |pixels2: &[UVec4]| {
+            assert_eq!(pixels1, pixels2);
+        });
+    });
+}
+
+#[test]
+fn test_preprocessor_new_with_wgsl_when_binding_is_unknown_should_return_error() {
+    let ctx = TestContext::new();
+
+    // The sort keys binding is in group 1, which is only in the layouts of the custom mapping
+    let wgsl = Preprocessor::<G>::compiled_wgsl(&PreprocessorCreateOptions {
+        depth_mapping: DepthMapping::Custom,
+        ..Default::default()
+    })
+    .expect("compiled wgsl");
+
+    let result = Preprocessor::<G, ()>::new_without_bind_group_with_wgsl(
+        &ctx.device,
+        PreprocessorCreateOptions::default(),
+        &wgsl,
+    );
+
+    assert!(matches!(
+        result,
+        Err(PreprocessorCreateError::CustomShader(
+            CustomShaderError::UnknownBinding {
+                group: 1,
+                binding: 0
+            }
+        ))
+    ));
+}
+
+#[test]
+fn test_viewer_download_visible_bounds_should_cover_only_visible_gaussians() {
+    let ctx = TestContext::new();
+    let gaussian = |pos: Vec3| Gaussian {
+        rot: Quat::IDENTITY,
+        pos,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(0.1),
+    };
+    let gaussians = vec![
+        gaussian(Vec3::new(0.0, 0.0, 5.0)),
+        gaussian(Vec3::new(-0.5, 0.2, 3.0)),
+        // Behind the camera
+        gaussian(Vec3::new(0.0, 0.0, -5.0)),
+    ];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer = Viewer::<G>::new_with_options(
+        &ctx.device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        &gaussians,
+        ViewerCreateOptions {
+            visible_bounds: true,
+            ..Default::default()
+        },
+    )
+    .expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    render_and_assert(&ctx, &viewer, &render_target, |_| {});
+
+    let (min, max) = pollster::block_on(viewer.download_visible_bounds(&ctx.device, &ctx.queue))
+        .expect("download")
+        .expect("visible bounds");
+
+    assert!(min.abs_diff_eq(Vec3::new(-0.5, 0.0, 3.0), 1e-5));
+    assert!(max.abs_diff_eq(Vec3::new(0.0, 0.2, 5.0), 1e-5));
+
+    viewer.update_camera(
+        &ctx.queue,
+        &Camera {
+            yaw: std::f32::consts::PI,
+            pitch: 0.1,
+            ..given::camera()
+        },
+        UVec2::new(1024, 1024),
+    );
+
+    render_and_assert(&ctx, &viewer, &render_target, |_| {});
+
+    let (min, max) = pollster::block_on(viewer.download_visible_bounds(&ctx.device, &ctx.queue))
+        .expect("download")
+        .expect("visible bounds");
+
+    assert!(min.abs_diff_eq(Vec3::new(0.0, 0.0, -5.0), 1e-5));
+    assert!(max.abs_diff_eq(Vec3::new(0.0, 0.0, -5.0), 1e-5));
+}
+
+#[test]
+fn test_viewer_download_visible_bounds_when_visible_bounds_is_disabled_should_return_error() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    assert!(matches!(
+        pollster::block_on(viewer.download_visible_bounds(&ctx.device, &ctx.queue)),
+        Err(ViewerDownloadError::VisibleBoundsDisabled)
+    ));
+}
+
+#[test]
+fn test_viewer_new_with_options_when_primitive_culls_either_face_should_cull_all_or_none() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let rendered_sums = [wgpu::Face::Front, wgpu::Face::Back].map(|face| {
+        let primitive = wgpu::PrimitiveState {
+            cull_mode: Some(face),
+            ..Default::default()
+        };
+
+        let mut viewer = Viewer::<G>::new_with_options(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            &gaussians,
+            ViewerCreateOptions {
+                primitive,
+                ..Default::default()
+            },
+        )
+        .expect("viewer");
+
+        assert_eq!(viewer.renderer.primitive(), primitive);
+
+        viewer
+            .set_texture_format(&ctx.device, wgpu::TextureFormat::Rgba8Unorm)
+            .expect("set texture format");
+
+        assert_eq!(viewer.renderer.primitive(), primitive);
+
+        viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+        let sum = std::cell::Cell::new(UVec4::ZERO);
+        render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+            sum.set(pixels.iter().sum::<UVec4>());
+        });
+        sum.get()
+    });
+
+    // The quads share the same winding, so exactly one of the faces is culled
+    assert_eq!(rendered_sums.iter().filter(|sum| sum.x == 0).count(), 1);
+}
+
+#[test]
+fn test_viewer_set_distance_fade_should_fade_out_distant_gaussians() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+
+    viewer.set_distance_fade(&ctx.queue, 0.0, f32::INFINITY);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x > 1);
+        assert!(sum.w > 1);
+    });
+
+    viewer.set_distance_fade(&ctx.queue, 0.1, 0.5);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x < 1);
+    });
+}
+
+#[test]
+fn test_viewer_update_renderer_settings_when_distance_fade_is_set_should_keep_distance_fade() {
+    let ctx = TestContext::new();
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(1.0),
+    }];
+
+    let render_target = given::render_target_texture(&ctx);
+
+    let mut viewer =
+        Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians).expect("viewer");
+
+    viewer.update_camera_with_pod(&ctx.queue, &given::camera_pod());
+    viewer.update_renderer_settings(
+        &ctx.queue,
+        &RendererSettings {
+            distance_fade: Some(0.1..0.5),
+            ..Default::default()
+        },
+    );
+    viewer.set_sh_dither(&ctx.queue, true);
+    viewer.set_sh_clamp(&ctx.queue, ShClamp::SoftCeiling);
+
+    render_and_assert(&ctx, &viewer, &render_target, |pixels: &[UVec4]| {
+        let sum = pixels.iter().sum::<UVec4>();
+        assert!(sum.x < 1);
+    });
+}
+
+#[test]
+fn test_renderer_render_with_hdr_target_when_tonemap_is_set_should_tonemap_only_render_target() {
+    const SIZE: u32 = 256;
+
+    fn download_texture(ctx: &TestContext, texture: &wgpu::Texture) -> Vec<u8> {
+        let bytes_per_row = SIZE * texture.format().block_copy_size(None).expect("block size");
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Download Buffer"),
+            size: (bytes_per_row * SIZE) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(SIZE),
+                },
+            },
+            texture.size(),
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        pollster::block_on(buffer.download::<u8>(&ctx.device, &ctx.queue)).expect("download")
+    }
+
+    let ctx = TestContext::new();
+    let mut sh = [Vec3::ZERO; 15];
+    // Brighten the red component above 1 for any view direction close to the Z axis
+    sh[5] = Vec3::splat(1.0);
+    let gaussians = vec![Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO + Vec3::Z,
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh,
+        scale: Vec3::splat(1.0),
+    }];
+
+    let create_texture = |format| {
+        ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Render Target"),
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    };
+
+    for tonemap in [Tonemap::Reinhard, Tonemap::Aces] {
+        let render_target = create_texture(wgpu::TextureFormat::Rgba8Unorm);
+        let hdr_render_target = create_texture(wgpu::TextureFormat::Rgba16Float);
+
+        let mut viewer = Viewer::<G>::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm, &gaussians)
+            .expect("viewer");
+        viewer.renderer = Renderer::new_with_hdr_target(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::TextureFormat::Rgba16Float,
+            RendererCreateOptions::default(),
+            &viewer.camera_buffer,
+            &viewer.model_transform_buffer,
+            &viewer.gaussian_transform_buffer,
+            &viewer.gaussians_buffer,
+            &viewer.indirect_indices_buffer,
+            &viewer.renderer_settings_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &viewer.selection_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &viewer.selection_layers_buffer,
+            #[cfg(feature = "viewer-selection")]
+            &viewer.selection_highlight_buffer,
+        )
+        .expect("renderer");
+
+        viewer.update_camera(&ctx.queue, &given::camera(), UVec2::splat(SIZE));
+        viewer.update_renderer_settings(
+            &ctx.queue,
+            &RendererSettings {
+                tonemap,
+                ..Default::default()
+            },
+        );
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder"),
+            });
+
+        viewer.preprocess_only(&mut encoder);
+        viewer.sort_only(&mut encoder);
+        viewer.renderer.render_with_hdr_target(
+            &mut encoder,
+            &render_target.create_view(&Default::default()),
+            &hdr_render_target.create_view(&Default::default()),
+            &viewer.indirect_args_buffer,
+        );
+
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let pixels = download_texture(&ctx, &render_target);
+        let hdr_pixels = download_texture(&ctx, &hdr_render_target)
+            .chunks_exact(8)
+            .map(|texel| {
+                Vec4::from_array(
+                    bytemuck::pod_read_unaligned::<[half::f16; 4]>(texel).map(half::f16::to_f32),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (index, hdr) = hdr_pixels
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.x.total_cmp(&b.x))
+            .expect("brightest pixel");
+
+        assert!(hdr.x > 1.0, "{tonemap:?}: {hdr}");
+
+        // The render target is blended with the alpha of the Gaussian, same as the HDR target
+        let expected = tonemap.map(hdr.truncate() / hdr.w) * hdr.w;
+        let red = pixels[index * 4] as f32 / 255.0;
+
+        assert!(red < 1.0, "{tonemap:?}: {red}");
+        assert!(
+            (red - expected.x).abs() <= 2.0 / 255.0,
+            "{tonemap:?}: {red} != {expected}"
+        );
+    }
 }
@@ -1,3 +1,4 @@
 mod buffer;
 mod common;
 mod e2e;
+mod unit;
@@ -0,0 +1,90 @@
+use glam::*;
+use wgpu_3dgs_viewer::{Camera, CameraProjection, bounding_sphere_weighted, core::Gaussian};
+
+fn gaussian(pos: Vec3, opacity: u8) -> Gaussian {
+    Gaussian {
+        rot: Quat::IDENTITY,
+        pos,
+        color: U8Vec4::new(255, 255, 255, opacity),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(0.1),
+    }
+}
+
+fn cluster_with_floater() -> Vec<Gaussian> {
+    (-5..=5)
+        .flat_map(|x| (-5..=5).map(move |y| gaussian(Vec3::new(x as f32, y as f32, 0.0), 255)))
+        .chain(std::iter::once(gaussian(Vec3::splat(1000.0), 255)))
+        .collect()
+}
+
+#[test]
+fn test_bounding_sphere_weighted_when_gaussians_is_empty_should_return_zero() {
+    assert_eq!(
+        bounding_sphere_weighted(&Vec::<Gaussian>::new(), 0.1),
+        (Vec3::ZERO, 0.0)
+    );
+}
+
+#[test]
+fn test_bounding_sphere_weighted_when_outlier_fraction_is_zero_should_include_floater() {
+    let (center, radius) = bounding_sphere_weighted(&cluster_with_floater(), 0.0);
+
+    assert!(radius >= center.distance(Vec3::splat(1000.0)) - 1e-3);
+}
+
+#[test]
+fn test_bounding_sphere_weighted_when_outlier_fraction_is_positive_should_reject_floater() {
+    let (center, radius) = bounding_sphere_weighted(&cluster_with_floater(), 0.05);
+
+    assert!(center.abs_diff_eq(Vec3::ZERO, 0.5));
+    assert!(radius < 10.0);
+}
+
+#[test]
+fn test_bounding_sphere_weighted_when_gaussian_is_transparent_should_ignore_it() {
+    let gaussians = vec![
+        gaussian(Vec3::new(1.0, 0.0, 0.0), 255),
+        gaussian(Vec3::new(-1.0, 0.0, 0.0), 255),
+        gaussian(Vec3::splat(1000.0), 0),
+    ];
+
+    let (center, radius) = bounding_sphere_weighted(&gaussians, 0.0);
+
+    assert!(center.abs_diff_eq(Vec3::ZERO, 1e-6));
+    assert!((radius - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_camera_frame_sphere_should_fit_sphere_in_vertical_fov() {
+    let mut camera = Camera::new(0.1..1e4, 60f32.to_radians());
+    let center = Vec3::new(1.0, 2.0, 3.0);
+
+    camera.frame_sphere(center, 1.0);
+
+    assert!((camera.pos.distance(center) - 2.0).abs() < 1e-5);
+    assert!(
+        (center - camera.pos)
+            .normalize()
+            .abs_diff_eq(camera.get_forward(), 1e-5)
+    );
+}
+
+#[test]
+fn test_camera_frame_sphere_when_orthographic_should_fit_sphere_in_height() {
+    let mut camera = Camera::new_orthographic(0.1..1e4, 1.0);
+    let center = Vec3::new(1.0, 2.0, 3.0);
+
+    camera.frame_sphere(center, 2.0);
+
+    assert_eq!(
+        camera.projection,
+        CameraProjection::Orthographic { height: 4.0 }
+    );
+    assert!(camera.pos.distance(center) > 2.0 + camera.near() - 1e-5);
+    assert!(
+        (center - camera.pos)
+            .normalize()
+            .abs_diff_eq(camera.get_forward(), 1e-5)
+    );
+}
@@ -0,0 +1,38 @@
+use glam::*;
+use wgpu_3dgs_viewer::Colormap;
+
+const COLORMAPS: [Colormap; 3] = [Colormap::Grayscale, Colormap::Viridis, Colormap::Turbo];
+
+#[test]
+fn test_colormap_sample_when_t_is_at_endpoints_should_return_first_and_last_control_points() {
+    for colormap in COLORMAPS {
+        let table = colormap.table();
+
+        assert_eq!(colormap.sample(0.0), table[0]);
+        assert_eq!(colormap.sample(1.0), table[table.len() - 1]);
+    }
+}
+
+#[test]
+fn test_colormap_sample_when_t_is_out_of_range_should_clamp() {
+    for colormap in COLORMAPS {
+        assert_eq!(colormap.sample(-1.0), colormap.sample(0.0));
+        assert_eq!(colormap.sample(2.0), colormap.sample(1.0));
+        assert_eq!(colormap.sample(f32::NAN), colormap.sample(0.0));
+    }
+}
+
+#[test]
+fn test_colormap_sample_when_t_is_between_control_points_should_interpolate_linearly() {
+    assert_eq!(
+        Colormap::Grayscale.sample(0.25),
+        Vec4::new(0.25, 0.25, 0.25, 1.0)
+    );
+
+    let viridis = Colormap::VIRIDIS[0].lerp(Colormap::VIRIDIS[1], 0.5);
+    assert!(
+        Colormap::Viridis
+            .sample(1.0 / 16.0)
+            .abs_diff_eq(viridis, 1e-6)
+    );
+}
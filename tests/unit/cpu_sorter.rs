@@ -0,0 +1,91 @@
+use glam::*;
+use wgpu_3dgs_viewer::{
+    Camera, CameraPod, CpuSorter,
+    core::{Gaussian, ModelTransformPod},
+};
+
+fn gaussian(pos: Vec3) -> Gaussian {
+    Gaussian {
+        rot: Quat::IDENTITY,
+        pos,
+        color: U8Vec4::new(255, 255, 255, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(0.1),
+    }
+}
+
+fn camera_pod() -> CameraPod {
+    CameraPod::new(
+        &Camera {
+            yaw: 0.1,
+            pitch: 0.1,
+            ..Camera::new(0.1..1e4, 60f32.to_radians())
+        },
+        UVec2::new(1024, 1024),
+    )
+}
+
+#[test]
+fn test_cpu_sorter_sorted_indices_should_order_from_back_to_front() {
+    let gaussians = [2.0, 8.0, 4.0, 6.0]
+        .into_iter()
+        .map(|z| gaussian(Vec3::new(0.0, 0.0, z)))
+        .collect::<Vec<_>>();
+    let sorter = CpuSorter::new(&gaussians);
+
+    assert_eq!(sorter.len(), gaussians.len());
+    assert_eq!(
+        sorter.sorted_indices(&camera_pod(), &ModelTransformPod::default(), 0.0),
+        vec![1, 3, 2, 0]
+    );
+}
+
+#[test]
+fn test_cpu_sorter_sorted_indices_when_gaussian_is_behind_camera_should_cull_it() {
+    let gaussians = vec![
+        gaussian(Vec3::new(0.0, 0.0, 5.0)),
+        gaussian(Vec3::new(0.0, 0.0, -5.0)),
+    ];
+    let sorter = CpuSorter::new(&gaussians);
+
+    assert_eq!(
+        sorter.sorted_indices(&camera_pod(), &ModelTransformPod::default(), 0.0),
+        vec![0]
+    );
+}
+
+#[test]
+fn test_cpu_sorter_sorted_indices_when_model_is_transformed_should_sort_in_world_space() {
+    let gaussians = vec![
+        gaussian(Vec3::new(0.0, 0.0, 2.0)),
+        gaussian(Vec3::new(0.0, 0.0, 4.0)),
+    ];
+    let sorter = CpuSorter::new(&gaussians);
+    let model_transform = ModelTransformPod::new(
+        Vec3::new(0.0, 0.0, 6.0),
+        Quat::from_rotation_y(std::f32::consts::PI),
+        Vec3::ONE,
+    );
+
+    assert_eq!(
+        sorter.sorted_indices(&camera_pod(), &model_transform, 0.0),
+        vec![0, 1]
+    );
+}
+
+#[test]
+fn test_cpu_sorter_sorted_indices_when_opacity_is_below_min_opacity_should_cull_it() {
+    let gaussians = [64, 192]
+        .into_iter()
+        .map(|alpha| Gaussian {
+            color: U8Vec4::new(255, 255, 255, alpha),
+            ..gaussian(Vec3::new(0.0, 0.0, 5.0))
+        })
+        .collect::<Vec<_>>();
+    let sorter = CpuSorter::new(&gaussians);
+
+    assert_eq!(
+        sorter.sorted_indices(&camera_pod(), &ModelTransformPod::default(), 0.5),
+        vec![1]
+    );
+}
@@ -0,0 +1,6 @@
+mod bounding_sphere;
+mod colormap;
+mod cpu_sorter;
+mod normalize;
+mod sh_rotation;
+mod validate;
@@ -0,0 +1,71 @@
+use glam::*;
+use wgpu_3dgs_viewer::{
+    bounding_box,
+    core::{Gaussian, Gaussians, IterGaussian},
+    normalize_gaussians,
+};
+
+fn gaussian(pos: Vec3) -> Gaussian {
+    Gaussian {
+        rot: Quat::IDENTITY,
+        pos,
+        color: U8Vec4::new(255, 255, 255, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(0.5),
+    }
+}
+
+#[test]
+fn test_bounding_box_when_gaussians_is_empty_should_return_none() {
+    assert_eq!(bounding_box(&Vec::<Gaussian>::new()), None);
+}
+
+#[test]
+fn test_bounding_box_should_return_min_and_max_positions() {
+    let gaussians = vec![
+        gaussian(Vec3::new(1.0, -2.0, 3.0)),
+        gaussian(Vec3::new(-4.0, 5.0, 0.0)),
+    ];
+
+    assert_eq!(
+        bounding_box(&gaussians),
+        Some((Vec3::new(-4.0, -2.0, 0.0), Vec3::new(1.0, 5.0, 3.0)))
+    );
+}
+
+#[test]
+fn test_normalize_gaussians_should_fit_in_unit_cube_and_return_transform() {
+    let original = vec![
+        gaussian(Vec3::new(10.0, 20.0, 30.0)),
+        gaussian(Vec3::new(14.0, 22.0, 30.0)),
+    ];
+    let mut gaussians = Gaussians::from(original.clone());
+
+    let transform = normalize_gaussians(&mut gaussians);
+
+    let (min, max) = bounding_box(&gaussians).expect("bounding box");
+    assert!(min.abs_diff_eq(Vec3::new(-0.5, -0.25, 0.0), 1e-6));
+    assert!(max.abs_diff_eq(Vec3::new(0.5, 0.25, 0.0), 1e-6));
+
+    for (normalized, original) in gaussians.iter_gaussian().zip(&original) {
+        assert!(
+            transform
+                .transform_point3(original.pos)
+                .abs_diff_eq(normalized.pos, 1e-6)
+        );
+        assert!(
+            transform
+                .inverse()
+                .transform_point3(normalized.pos)
+                .abs_diff_eq(original.pos, 1e-4)
+        );
+        assert!(normalized.scale.abs_diff_eq(Vec3::splat(0.125), 1e-6));
+    }
+}
+
+#[test]
+fn test_normalize_gaussians_when_gaussians_is_empty_should_return_identity() {
+    let mut gaussians = Gaussians::from(Vec::<Gaussian>::new());
+
+    assert_eq!(normalize_gaussians(&mut gaussians), Mat4::IDENTITY);
+}
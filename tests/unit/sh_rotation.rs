@@ -0,0 +1,107 @@
+use glam::*;
+use wgpu_3dgs_viewer::{
+    ShRotation,
+    core::{Gaussian, Gaussians, IterGaussian},
+    rotate_gaussians, rotate_sh,
+};
+
+/// Evaluate the view dependent color of degree 1 to 3, same as `view_color` in the shaders.
+fn eval_sh(sh: &[Vec3; 15], dir: Vec3) -> Vec3 {
+    let Vec3 { x, y, z } = dir;
+    let (xx, yy, zz) = (x * x, y * y, z * z);
+    let basis = [
+        -0.4886025 * y,
+        0.4886025 * z,
+        -0.4886025 * x,
+        1.0925484 * x * y,
+        -1.0925484 * y * z,
+        0.3153916 * (2.0 * zz - xx - yy),
+        -1.0925484 * x * z,
+        0.5462742 * (xx - yy),
+        -0.5900436 * y * (3.0 * xx - yy),
+        2.8906114 * x * y * z,
+        -0.4570458 * y * (4.0 * zz - xx - yy),
+        0.3731763 * z * (2.0 * zz - 3.0 * xx - 3.0 * yy),
+        -0.4570458 * x * (4.0 * zz - xx - yy),
+        1.4453057 * z * (xx - yy),
+        -0.5900436 * x * (xx - 3.0 * yy),
+    ];
+
+    sh.iter().zip(basis).map(|(c, b)| *c * b).sum()
+}
+
+fn directional_sh(dir: Vec3) -> [Vec3; 15] {
+    // Degree 1 only, brighter towards `dir`
+    let mut sh = [Vec3::ZERO; 15];
+    sh[0] = Vec3::splat(-dir.y);
+    sh[1] = Vec3::splat(dir.z);
+    sh[2] = Vec3::splat(-dir.x);
+    sh
+}
+
+#[test]
+fn test_rotate_sh_when_directional_splat_is_rotated_should_point_to_rotated_direction() {
+    let mut sh = directional_sh(Vec3::X);
+
+    rotate_sh(&mut sh, Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
+
+    let expected = directional_sh(Vec3::Y);
+    assert!(
+        sh.iter()
+            .zip(&expected)
+            .all(|(a, b)| a.abs_diff_eq(*b, 1e-5))
+    );
+}
+
+#[test]
+fn test_rotate_sh_when_rotated_by_half_turn_about_z_should_negate_x_and_y() {
+    let mut sh = directional_sh(Vec3::new(1.0, 2.0, 3.0).normalize());
+
+    rotate_sh(&mut sh, Quat::from_rotation_z(std::f32::consts::PI));
+
+    let expected = directional_sh(Vec3::new(-1.0, -2.0, 3.0).normalize());
+    assert!(
+        sh.iter()
+            .zip(&expected)
+            .all(|(a, b)| a.abs_diff_eq(*b, 1e-5))
+    );
+}
+
+#[test]
+fn test_sh_rotation_apply_should_preserve_color_in_rotated_direction() {
+    let rot = Quat::from_euler(EulerRot::YXZ, 0.3, -1.2, 2.5);
+    let original = std::array::from_fn(|i| Vec3::new(0.1, -0.2, 0.3) * (i as f32 + 1.0).sin());
+    let mut sh = original;
+
+    ShRotation::new(rot).apply(&mut sh);
+
+    for dir in [
+        Vec3::X,
+        Vec3::NEG_Y,
+        Vec3::new(1.0, 2.0, 3.0).normalize(),
+        Vec3::new(-0.5, 0.1, -0.8).normalize(),
+    ] {
+        assert!(eval_sh(&sh, rot * dir).abs_diff_eq(eval_sh(&original, dir), 1e-4));
+    }
+}
+
+#[test]
+fn test_rotate_gaussians_should_rotate_pos_rot_and_sh() {
+    let rot = Quat::from_rotation_z(std::f32::consts::PI);
+    let gaussian = Gaussian {
+        rot: Quat::from_rotation_x(0.5),
+        pos: Vec3::new(1.0, 2.0, 3.0),
+        color: U8Vec4::new(255, 0, 0, 255),
+        sh: directional_sh(Vec3::X),
+        scale: Vec3::splat(0.1),
+    };
+    let mut gaussians = Gaussians::from(vec![gaussian]);
+
+    rotate_gaussians(&mut gaussians, rot);
+
+    let rotated = gaussians.iter_gaussian().next().expect("gaussian");
+    assert!(rotated.pos.abs_diff_eq(Vec3::new(-1.0, -2.0, 3.0), 1e-5));
+    assert!(rotated.rot.abs_diff_eq(rot * gaussian.rot, 1e-5));
+    assert!(rotated.sh[2].abs_diff_eq(Vec3::ONE, 1e-5));
+    assert_eq!(rotated.color, gaussian.color);
+}
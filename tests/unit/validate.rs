@@ -0,0 +1,101 @@
+use glam::*;
+use wgpu_3dgs_viewer::{
+    GaussianIssue,
+    core::{Gaussian, Gaussians, IterGaussian},
+    sanitize_gaussians, validate_gaussians,
+};
+
+fn gaussian() -> Gaussian {
+    Gaussian {
+        rot: Quat::IDENTITY,
+        pos: Vec3::ZERO,
+        color: U8Vec4::new(255, 255, 255, 255),
+        sh: [Vec3::ZERO; 15],
+        scale: Vec3::splat(0.5),
+    }
+}
+
+#[test]
+fn test_validate_gaussians_when_gaussians_are_valid_should_return_no_issue() {
+    let report = validate_gaussians(&vec![gaussian(), gaussian()]);
+
+    assert!(report.is_valid());
+    assert!(report.invalid_indices().is_empty());
+}
+
+#[test]
+fn test_validate_gaussians_when_gaussians_are_invalid_should_report_issues() {
+    let gaussians = vec![
+        gaussian(),
+        Gaussian {
+            pos: Vec3::new(f32::NAN, 0.0, 0.0),
+            ..gaussian()
+        },
+        Gaussian {
+            rot: Quat::from_xyzw(0.0, 0.0, 0.0, 2.0),
+            scale: Vec3::new(0.5, 0.0, -0.5),
+            ..gaussian()
+        },
+        Gaussian {
+            rot: Quat::from_xyzw(0.0, 0.0, 0.0, 0.0),
+            ..gaussian()
+        },
+        Gaussian {
+            scale: Vec3::splat(f32::INFINITY),
+            ..gaussian()
+        },
+        Gaussian {
+            scale: Vec3::splat(1e30),
+            ..gaussian()
+        },
+        Gaussian {
+            sh: [Vec3::splat(f32::NAN); 15],
+            ..gaussian()
+        },
+    ];
+
+    let report = validate_gaussians(&gaussians);
+
+    assert!(!report.is_valid());
+    assert_eq!(report.invalid_indices(), vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(
+        report.issues,
+        vec![
+            (1, GaussianIssue::NonFinitePosition),
+            (2, GaussianIssue::NonNormalizedRotation),
+            (2, GaussianIssue::DegenerateScale),
+            (3, GaussianIssue::InvalidRotation),
+            (4, GaussianIssue::NonFiniteScale),
+            (5, GaussianIssue::NonFiniteCovariance),
+            (6, GaussianIssue::NonFiniteSh),
+        ]
+    );
+    assert_eq!(report.count(GaussianIssue::DegenerateScale), 1);
+}
+
+#[test]
+fn test_sanitize_gaussians_should_fix_fixable_and_drop_others() {
+    let mut gaussians = Gaussians::from(vec![
+        gaussian(),
+        Gaussian {
+            pos: Vec3::splat(f32::INFINITY),
+            ..gaussian()
+        },
+        Gaussian {
+            rot: Quat::from_xyzw(0.0, 0.0, 0.0, 2.0),
+            scale: Vec3::new(0.5, 0.0, -0.25),
+            ..gaussian()
+        },
+    ]);
+
+    let dropped = sanitize_gaussians(&mut gaussians, 1e-4);
+
+    assert_eq!(dropped, 1);
+    assert!(validate_gaussians(&gaussians).is_valid());
+
+    let sanitized = gaussians.iter_gaussian().collect::<Vec<_>>();
+    assert_eq!(sanitized.len(), 2);
+    assert_eq!(sanitized[0], gaussian());
+    assert!(sanitized[1].rot.is_near_identity());
+    assert_eq!(sanitized[1].scale, Vec3::new(0.5, 1e-4, 0.25));
+}